@@ -0,0 +1,175 @@
+//! Authentication methods supported by `TweetyClient`.
+//!
+//! [Docs](https://developer.x.com/en/docs/authentication/overview)
+
+use crate::api::client::TweetyClient;
+use crate::api::credentials::{Credentials, Secret, REDACTED};
+use crate::api::error::TweetyError;
+use crate::api::oauth2::OAuth2Session;
+use reqwest::Method;
+use reqwest_oauth1::OAuthClientProvider;
+use serde::{Deserialize, Serialize, Serializer};
+use url::Url;
+
+/// How requests are authenticated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Auth {
+    /// OAuth 1.0a User Context, signed with the app's consumer key and a
+    /// user's access token.
+    OAuth1(Credentials),
+    /// OAuth 2.0 App-only, sent as `Authorization: Bearer <token>`.
+    /// Only works with endpoints that don't act on behalf of a user.
+    Bearer { token: Secret },
+    /// OAuth 2.0 Authorization Code with PKCE, refreshed automatically.
+    /// See [`crate::api::oauth2`].
+    #[serde(skip)]
+    OAuth2(OAuth2Session),
+}
+
+impl Auth {
+    /// Serializes like `Auth` itself, with every secret replaced by `[REDACTED]`.
+    pub(crate) fn serialize_redacted<S: Serializer>(
+        auth: &Auth,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match auth {
+            Auth::OAuth1(_) => {
+                Auth::OAuth1(Credentials::new(REDACTED, REDACTED, REDACTED, REDACTED))
+                    .serialize(serializer)
+            }
+            Auth::Bearer { .. } => Auth::Bearer {
+                token: Secret::new(REDACTED),
+            }
+            .serialize(serializer),
+            Auth::OAuth2(_) => auth.serialize(serializer),
+        }
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        match self {
+            Auth::OAuth1(credentials) => credentials.is_complete(),
+            Auth::Bearer { token } => !token.is_empty(),
+            Auth::OAuth2(session) => !session.access_token().is_empty(),
+        }
+    }
+
+    /// Refreshes an expired OAuth 2.0 user token, a no-op for the other methods.
+    pub(crate) async fn refresh_if_expired(&self) -> Result<(), TweetyError> {
+        match self {
+            Auth::OAuth2(session) => session.refresh_if_expired().await,
+            _ => Ok(()),
+        }
+    }
+
+    /// Builds an authenticated request. `query` is part of the OAuth 1.0a
+    /// signature, so it has to be passed here rather than added afterwards.
+    pub(crate) fn authorize(
+        &self,
+        http: &reqwest::Client,
+        method: Method,
+        url: Url,
+        query: &[(&str, &str)],
+    ) -> Result<reqwest::RequestBuilder, TweetyError> {
+        match self {
+            Auth::OAuth1(credentials) => {
+                let secrets = reqwest_oauth1::Secrets::new(
+                    credentials.consumer_key.expose(),
+                    credentials.consumer_key_secret.expose(),
+                )
+                .token(
+                    credentials.access_token.expose(),
+                    credentials.access_token_secret.expose(),
+                );
+                let mut request = http.clone().oauth1(secrets).request(method, url);
+                if !query.is_empty() {
+                    request = request.query(query);
+                }
+                request
+                    .generate_signature()
+                    .map_err(|_| TweetyError::AuthError)
+            }
+            Auth::Bearer { token } => {
+                let mut request = http.request(method, url);
+                if !query.is_empty() {
+                    request = request.query(query);
+                }
+                Ok(request.bearer_auth(token.expose()))
+            }
+            Auth::OAuth2(session) => {
+                let mut request = http.request(method, url);
+                if !query.is_empty() {
+                    request = request.query(query);
+                }
+                Ok(request.bearer_auth(session.access_token().expose()))
+            }
+        }
+    }
+}
+
+/// Response of `POST /oauth2/token`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BearerToken {
+    pub token_type: String,
+    pub access_token: Secret,
+}
+
+/// Response of `POST /oauth2/invalidate_token`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvalidatedToken {
+    pub access_token: Secret,
+}
+
+impl TweetyClient {
+    /// Exchanges a consumer key and secret for an OAuth 2.0 App-only bearer token
+    /// (client-credentials grant).
+    /// The client itself can use any kind of credentials, it only provides the
+    /// base URL and the HTTP client.
+    /// [Docs](https://developer.x.com/en/docs/authentication/api-reference/token)
+    pub async fn request_bearer_token(
+        &self,
+        consumer_key: &str,
+        consumer_key_secret: &str,
+    ) -> Result<BearerToken, TweetyError> {
+        let url = format!("{}/oauth2/token", self.api_base_url);
+
+        let request = self
+            .http
+            .post(&url)
+            .basic_auth(consumer_key, Some(consumer_key_secret))
+            .form(&[("grant_type", "client_credentials")]);
+        let response = self
+            .execute(request)
+            .await?
+            .map_err(|err| TweetyError::NetworkError(err.to_string()))?;
+
+        let value = response.into_result()?;
+        serde_json::from_value::<BearerToken>(value)
+            .map_err(|err| TweetyError::JsonParseError(err.to_string()))
+    }
+
+    /// Revokes an OAuth 2.0 App-only bearer token. Once invalidated, new
+    /// requests with the token fail and `request_bearer_token` returns a new one.
+    /// [Docs](https://developer.x.com/en/docs/authentication/api-reference/invalidate_bearer_token)
+    pub async fn invalidate_bearer_token(
+        &self,
+        consumer_key: &str,
+        consumer_key_secret: &str,
+        token: &str,
+    ) -> Result<InvalidatedToken, TweetyError> {
+        let url = format!("{}/oauth2/invalidate_token", self.api_base_url);
+
+        let request = self
+            .http
+            .post(&url)
+            .basic_auth(consumer_key, Some(consumer_key_secret))
+            .form(&[("access_token", token)]);
+        let response = self
+            .execute(request)
+            .await?
+            .map_err(|err| TweetyError::NetworkError(err.to_string()))?;
+
+        let value = response.into_result()?;
+        serde_json::from_value::<InvalidatedToken>(value)
+            .map_err(|err| TweetyError::JsonParseError(err.to_string()))
+    }
+}
@@ -1,121 +1,40 @@
 use crate::api::client::TweetyClient;
 use crate::api::error::TweetyError;
+use crate::api::fields::{Fields, QueryString};
+use crate::api::pagination::{Paginator, TokenParam};
+use crate::api::tweet::TargetTweet;
+use crate::types::id::{TweetId, UserId};
+use crate::types::response::Response;
+use crate::types::tweet::{Tweet, TweetsResponse};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum Expansions {
-    AttachmentsPollIds,
-    AttachmentsMediaKeys,
-    AuthorId,
-    EditHistoryTweetIds,
-    EntitiesMentionsUsername(String), // Allow username for mentions
-    GeoPlaceId,
-    InReplyToUserId,
-    ReferencedTweetsId,
-    ReferencedTweetsIdAuthorId,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum MediaFields {
-    DurationMs,
-    Height,
-    MediaKey,
-    PreviewImageUrl,
-    Type,
-    Url,
-    Width,
-    PublicMetrics,
-    NonPublicMetrics,
-    OrganicMetrics,
-    PromotedMetrics,
-    AltText,
-    Variants,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum PlaceFields {
-    ContainedWithin,
-    Country,
-    CountryCode,
-    FullName,
-    Geo,
-    Id,
-    Name,
-    PlaceType,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum PollFields {
-    DurationMinutes,
-    EndDatetime,
-    Id,
-    Options,
-    VotingStatus,
+#[derive(Debug, Clone, Default)]
+pub struct BookmarkParams {
+    pub max_results: Option<u32>, // Use u32 for integer parameters
+    pub pagination_token: Option<String>,
+    pub fields: Fields,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum TweetFields {
-    Attachments,
-    AuthorId,
-    ContextAnnotations,
-    ConversationId,
-    CreatedAt,
-    Entities,
-    EditControls,
-    Geo,
-    Id,
-    InReplyToUserId,
-    Lang,
-    NonPublicMetrics,
-    PublicMetrics,
-    OrganicMetrics,
-    PromotedMetrics,
-    PossiblySensitive,
-    ReferencedTweets,
-    ReplySettings,
-    Source,
-    Text,
-    Withheld,
+impl BookmarkParams {
+    pub fn to_query_string(&self) -> String {
+        let mut query = QueryString::new();
+        query
+            .push_opt("max_results", self.max_results)
+            .push_opt("pagination_token", self.pagination_token.as_ref());
+        self.fields.append_to(&mut query);
+        query.to_string()
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum UserFields {
-    CreatedAt,
-    Description,
-    Entities,
-    Id,
-    Location,
-    MostRecentTweetId,
-    Name,
-    PinnedTweetId,
-    ProfileImageUrl,
-    Protected,
-    PublicMetrics,
-    Url,
-    Username,
-    Verified,
-    VerifiedType,
-    Withheld,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookmarkResponse {
+    pub data: BookmarkData,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BookmarkParams {
-    pub expansions: Option<Vec<Expansions>>,
-    pub max_results: Option<u32>, // Use u32 for integer parameters
-    pub media_fields: Option<Vec<MediaFields>>,
-    pub pagination_token: Option<String>,
-    pub place_fields: Option<Vec<PlaceFields>>,
-    pub poll_fields: Option<Vec<PollFields>>,
-    pub tweet_fields: Option<Vec<TweetFields>>,
-    pub user_fields: Option<Vec<UserFields>>,
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookmarkData {
+    pub bookmarked: bool,
 }
 
 /// # REFERENCE LINK
@@ -126,28 +45,58 @@ pub struct BookmarkParams {
 impl TweetyClient {
     /// Bookmarks lookup
     /// Lookup a user's Bookmarks
-    pub async fn get_user_bookmark(self, user_id: &str) -> Result<Value, TweetyError> {
-        let url = format!("https://api.x.com/2/users/{}/bookmarks", user_id);
+    pub async fn get_user_bookmark(
+        &self,
+        user_id: UserId,
+        params: Option<BookmarkParams>,
+    ) -> Result<Response<TweetsResponse>, TweetyError> {
+        let url = self.user_bookmarks_url(user_id, params);
 
-        self.send_request::<()>(&url, Method::GET, None).await
+        self.request::<_, ()>(&url, Method::GET, None).await
+    }
+    /// Pages through all of a user's Bookmarks.
+    /// See [`crate::api::pagination`].
+    pub fn paginate_user_bookmarks(
+        &self,
+        user_id: UserId,
+        params: Option<BookmarkParams>,
+    ) -> Paginator<'_, Tweet> {
+        let url = self.user_bookmarks_url(user_id, params);
+
+        Paginator::new(self, &url, TokenParam::PaginationToken)
     }
     /// Bookmark a Post
-    pub async fn bookmark_post(self, post_id: &str) -> Result<Value, TweetyError> {
-        let url = format!("https://api.x.com/2/users/{}/bookmarks", post_id);
+    /// [Docs](https://developer.x.com/en/docs/x-api/tweets/bookmarks/api-reference/post-users-id-bookmarks)
+    pub async fn bookmark_post(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<BookmarkResponse>, TweetyError> {
+        let url = format!("{}/2/users/{}/bookmarks", self.api_base_url, user_id);
 
-        self.send_request::<()>(&url, Method::POST, None).await
+        self.request(&url, Method::POST, Some(TargetTweet { tweet_id }))
+            .await
     }
     /// Remove a Bookmark of a Post
     pub async fn delete_bookmark(
-        self,
-        user_id: &str,
-        tweet_id: &str,
-    ) -> Result<Value, TweetyError> {
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<BookmarkResponse>, TweetyError> {
         let url = format!(
-            "https://api.x.com/2/users/{}/bookmarks/{}",
-            user_id, tweet_id
+            "{}/2/users/{}/bookmarks/{}",
+            self.api_base_url, user_id, tweet_id
         );
 
-        self.send_request::<()>(&url, Method::DELETE, None).await
+        self.request::<_, ()>(&url, Method::DELETE, None).await
+    }
+
+    fn user_bookmarks_url(&self, user_id: UserId, params: Option<BookmarkParams>) -> String {
+        let url = format!("{}/2/users/{}/bookmarks", self.api_base_url, user_id);
+
+        match params.map(|params| params.to_query_string()) {
+            Some(query) if !query.is_empty() => format!("{}?{}", url, query),
+            _ => url,
+        }
     }
 }
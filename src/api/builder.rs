@@ -0,0 +1,286 @@
+use crate::api::auth::Auth;
+use crate::api::cassette::Cassette;
+use crate::api::client::{
+    TweetyClient, DEFAULT_API_BASE_URL, DEFAULT_STREAM_BASE_URL, DEFAULT_UPLOAD_BASE_URL,
+};
+use crate::api::credentials::{Credentials, Secret};
+use crate::api::error::TweetyError;
+use crate::api::oauth2::{OAuth2Client, OAuth2Session, OAuth2Token};
+use crate::api::rate_limit::RateLimitPolicy;
+use crate::api::retry::RetryPolicy;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// User agent sent with every request unless overridden.
+pub const DEFAULT_USER_AGENT: &str = concat!("tweety-rs/", env!("CARGO_PKG_VERSION"));
+
+/// Builds a [`TweetyClient`] that owns a single, long-lived `reqwest::Client`,
+/// so connection pooling and TLS sessions are reused across calls.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use tweety_rs::TweetyClient;
+///
+/// let client = TweetyClient::builder()
+///     .consumer_key("your_consumer_key")
+///     .consumer_key_secret("your_consumer_key_secret")
+///     .access_token("your_access_token")
+///     .access_token_secret("your_access_token_secret")
+///     .timeout(Duration::from_secs(30))
+///     .build()
+///     .unwrap();
+///
+/// assert!(client.is_initialized());
+/// ```
+#[derive(Debug, Default)]
+pub struct TweetyClientBuilder {
+    consumer_key: Option<Secret>,
+    consumer_key_secret: Option<Secret>,
+    access_token: Option<Secret>,
+    access_token_secret: Option<Secret>,
+    api_base_url: Option<String>,
+    upload_base_url: Option<String>,
+    stream_base_url: Option<String>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: Option<reqwest::Proxy>,
+    user_agent: Option<String>,
+    http_client: Option<reqwest::Client>,
+    bearer_token: Option<Secret>,
+    oauth2: Option<OAuth2Session>,
+    rate_limit_policy: RateLimitPolicy,
+    retry_policy: Option<RetryPolicy>,
+    cassette: Option<Cassette>,
+}
+
+impl TweetyClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The OAuth consumer key (API key).
+    pub fn consumer_key(mut self, consumer_key: &str) -> Self {
+        self.consumer_key = Some(Secret::new(consumer_key));
+        self
+    }
+
+    /// The OAuth consumer key secret (API secret key).
+    pub fn consumer_key_secret(mut self, consumer_key_secret: &str) -> Self {
+        self.consumer_key_secret = Some(Secret::new(consumer_key_secret));
+        self
+    }
+
+    /// The OAuth access token.
+    pub fn access_token(mut self, access_token: &str) -> Self {
+        self.access_token = Some(Secret::new(access_token));
+        self
+    }
+
+    /// The OAuth access token secret.
+    pub fn access_token_secret(mut self, access_token_secret: &str) -> Self {
+        self.access_token_secret = Some(Secret::new(access_token_secret));
+        self
+    }
+
+    /// Sets all four OAuth 1.0a credentials at once, e.g. from
+    /// [`Credentials::from_env`] or [`Credentials::from_profile`].
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.consumer_key = Some(credentials.consumer_key);
+        self.consumer_key_secret = Some(credentials.consumer_key_secret);
+        self.access_token = Some(credentials.access_token);
+        self.access_token_secret = Some(credentials.access_token_secret);
+        self
+    }
+
+    /// Base URL for the REST endpoints. Defaults to [`DEFAULT_API_BASE_URL`].
+    pub fn api_base_url(mut self, base_url: &str) -> Self {
+        self.api_base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Base URL for media uploads. Defaults to [`DEFAULT_UPLOAD_BASE_URL`].
+    pub fn upload_base_url(mut self, base_url: &str) -> Self {
+        self.upload_base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Base URL for the streaming endpoints. Defaults to [`DEFAULT_STREAM_BASE_URL`].
+    pub fn stream_base_url(mut self, base_url: &str) -> Self {
+        self.stream_base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Total timeout applied to every request.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for establishing the connection only.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Routes every request through the given proxy.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Overrides the `User-Agent` header. Defaults to [`DEFAULT_USER_AGENT`].
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Uses an existing `reqwest::Client` instead of building one.
+    /// The client is used as-is: `timeout`, `connect_timeout`, `proxy` and
+    /// `user_agent` are ignored when this is set.
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// What to do on HTTP 429. Defaults to [`RateLimitPolicy::Fail`].
+    pub fn rate_limit_policy(mut self, policy: RateLimitPolicy) -> Self {
+        self.rate_limit_policy = policy;
+        self
+    }
+
+    /// How to retry transient failures. Defaults to [`RetryPolicy::none`].
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Records requests to, or replays them from, a cassette file.
+    /// See [`crate::api::cassette`].
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// An OAuth 2.0 App-only bearer token, used instead of the OAuth 1.0a
+    /// credentials. See [`TweetyClientBuilder::build_app_only`] to obtain one.
+    pub fn bearer_token(mut self, token: &str) -> Self {
+        self.bearer_token = Some(Secret::new(token));
+        self
+    }
+
+    /// Authenticates with an OAuth 2.0 user token obtained through
+    /// [`OAuth2Client`]. The token is refreshed with `client` when it expires.
+    pub fn oauth2(mut self, client: OAuth2Client, token: OAuth2Token) -> Self {
+        self.oauth2 = Some(OAuth2Session::new(client, token));
+        self
+    }
+
+    /// Builds the client.
+    /// Uses the OAuth 2.0 user token or the bearer token if one was set, otherwise fails with
+    /// `TweetyError::MissingCredentials` if any of the four OAuth 1.0a
+    /// credentials is missing or empty.
+    pub fn build(mut self) -> Result<TweetyClient, TweetyError> {
+        let auth = match (self.oauth2.take(), self.bearer_token.take()) {
+            (Some(session), _) => Auth::OAuth2(session),
+            (None, Some(token)) => Auth::Bearer {
+                token: required(Some(token))?,
+            },
+            (None, None) => Auth::OAuth1(Credentials {
+                consumer_key: required(self.consumer_key.take())?,
+                consumer_key_secret: required(self.consumer_key_secret.take())?,
+                access_token: required(self.access_token.take())?,
+                access_token_secret: required(self.access_token_secret.take())?,
+            }),
+        };
+
+        self.build_with(auth)
+    }
+
+    /// Builds an OAuth 2.0 App-only client: exchanges the consumer key and
+    /// secret for a bearer token with `POST /oauth2/token`.
+    /// Access tokens set on the builder are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tweety_rs::TweetyClient;
+    ///
+    /// # async fn example() -> Result<(), tweety_rs::api::error::TweetyError> {
+    /// let client = TweetyClient::builder()
+    ///     .consumer_key("your_consumer_key")
+    ///     .consumer_key_secret("your_consumer_key_secret")
+    ///     .build_app_only()
+    ///     .await?;
+    ///
+    /// let results = client.full_archive_search("from:rustlang", None).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn build_app_only(mut self) -> Result<TweetyClient, TweetyError> {
+        let consumer_key = required(self.consumer_key.take())?;
+        let consumer_key_secret = required(self.consumer_key_secret.take())?;
+
+        let mut client = self.build_with(Auth::Bearer {
+            token: Secret::default(),
+        })?;
+        let token = client
+            .request_bearer_token(consumer_key.expose(), consumer_key_secret.expose())
+            .await?;
+        client.auth = Auth::Bearer {
+            token: token.access_token,
+        };
+
+        Ok(client)
+    }
+
+    fn build_with(self, auth: Auth) -> Result<TweetyClient, TweetyError> {
+        let http = match self.http_client {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::Client::builder().user_agent(
+                    self.user_agent
+                        .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string()),
+                );
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(proxy) = self.proxy {
+                    builder = builder.proxy(proxy);
+                }
+                builder.build()?
+            }
+        };
+
+        let base_url = |value: Option<String>, default: &str| {
+            value
+                .as_deref()
+                .unwrap_or(default)
+                .trim_end_matches('/')
+                .to_string()
+        };
+
+        Ok(TweetyClient {
+            auth,
+            api_base_url: base_url(self.api_base_url, DEFAULT_API_BASE_URL),
+            upload_base_url: base_url(self.upload_base_url, DEFAULT_UPLOAD_BASE_URL),
+            stream_base_url: base_url(self.stream_base_url, DEFAULT_STREAM_BASE_URL),
+            http,
+            rate_limit_policy: self.rate_limit_policy,
+            retry_policy: self.retry_policy.unwrap_or_else(RetryPolicy::none),
+            rate_limits: Default::default(),
+            cassette: self.cassette.map(Arc::new),
+        })
+    }
+}
+
+fn required(value: Option<Secret>) -> Result<Secret, TweetyError> {
+    match value {
+        Some(value) if !value.is_empty() => Ok(value),
+        _ => Err(TweetyError::MissingCredentials),
+    }
+}
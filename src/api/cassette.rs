@@ -0,0 +1,475 @@
+//! Record-and-replay of HTTP interactions, to run tests against the real API once and
+//! offline afterwards.
+//!
+//! A [`Cassette`] is a JSON file of request/response pairs. While recording, the client sends
+//! its requests as usual and writes each of them, with its response, to the file. While
+//! replaying, it never touches the network: every request is answered with the response
+//! recorded for a matching request, in the order they were recorded.
+//!
+//! ```rust,no_run
+//! use tweety_rs::api::cassette::{Cassette, CassetteMode};
+//! use tweety_rs::types::id::UserId;
+//! use tweety_rs::TweetyClient;
+//!
+//! # async fn example() -> Result<(), tweety_rs::api::error::TweetyError> {
+//! // Records `tests/cassettes/get_user.json` on the first run, replays it on the next ones.
+//! let cassette = Cassette::open("tests/cassettes/get_user.json", CassetteMode::Once)?;
+//! let client = TweetyClient::builder()
+//!     .consumer_key("your_consumer_key")
+//!     .consumer_key_secret("your_consumer_key_secret")
+//!     .access_token("your_access_token")
+//!     .access_token_secret("your_access_token_secret")
+//!     .cassette(cassette)
+//!     .build()?;
+//!
+//! let user = client.get_user_by_id(UserId::new(2244994945), None).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Credentials are scrubbed from the recorded `Authorization` headers, and tokens
+//! (`access_token`, `refresh_token`, `oauth_token` and `oauth_token_secret`) from the
+//! request and response bodies, JSON or form-encoded, so cassettes can be committed.
+//! Requests match on their method, URL (in any query parameter order), body and
+//! authentication scheme; the OAuth 1.0a nonce and timestamp are ignored, as are the scrubbed
+//! values, so a cassette replays with any credentials.
+
+use crate::api::client::RawResponse;
+use crate::api::error::TweetyError;
+use crate::api::rate_limit::RateLimit;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+use url::Url;
+
+/// Replaces the scrubbed values in a recorded request.
+pub const REDACTED: &str = "[REDACTED]";
+/// Replaces the tokens in recorded bodies. It is a valid form value and token,
+/// so recorded token requests and responses replay as they were sent.
+pub const REDACTED_TOKEN: &str = "REDACTED_TOKEN";
+
+/// OAuth 1.0a parameters that are credentials, or derived from them.
+const SECRET_OAUTH_PARAMS: [&str; 3] = ["oauth_consumer_key", "oauth_token", "oauth_signature"];
+/// Fields of request and response bodies that are tokens.
+const SECRET_BODY_FIELDS: [&str; 4] = [
+    "access_token",
+    "refresh_token",
+    "oauth_token",
+    "oauth_token_secret",
+];
+/// OAuth 1.0a parameters that change with every request.
+const VOLATILE_OAUTH_PARAMS: [&str; 2] = ["oauth_nonce", "oauth_timestamp"];
+/// Response headers that are never written to a cassette.
+const SKIPPED_RESPONSE_HEADERS: [&str; 1] = ["set-cookie"];
+
+/// Whether a [`Cassette`] records or replays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// Sends every request and replaces the cassette with them.
+    Record,
+    /// Never sends a request: each one must match a recorded one.
+    Replay,
+    /// Replays the cassette if the file exists, records it otherwise.
+    Once,
+}
+
+/// A request and the response it got.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    /// Header names are lowercase. The credentials of `authorization` are scrubbed.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// JSON bodies are kept as JSON, other bodies as a string. Tokens are scrubbed.
+    #[serde(default)]
+    pub body: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status: u16,
+    /// Header names are lowercase.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// JSON bodies are kept as JSON, other bodies as a string, and an empty body as `null`.
+    /// Tokens are scrubbed.
+    #[serde(default)]
+    pub body: Value,
+}
+
+/// The file format.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+/// Recorded interactions, see the [module documentation](self).
+#[derive(Debug)]
+pub struct Cassette {
+    path: PathBuf,
+    recording: bool,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    interactions: Vec<Interaction>,
+    /// Whether each interaction was replayed already.
+    replayed: Vec<bool>,
+}
+
+impl Cassette {
+    /// Opens the cassette at `path`. Fails in [`CassetteMode::Replay`] if the
+    /// file is missing or invalid, and in [`CassetteMode::Once`] if it is invalid.
+    /// Nothing is written until the first request is recorded.
+    pub fn open<P: AsRef<Path>>(path: P, mode: CassetteMode) -> Result<Self, TweetyError> {
+        let path = path.as_ref().to_path_buf();
+        let recording = match mode {
+            CassetteMode::Record => true,
+            CassetteMode::Replay => false,
+            CassetteMode::Once => !path.exists(),
+        };
+
+        let interactions = if recording {
+            Vec::new()
+        } else {
+            let json = std::fs::read_to_string(&path)
+                .map_err(|err| TweetyError::FileIOError(format!("{}: {}", path.display(), err)))?;
+            serde_json::from_str::<CassetteFile>(&json)
+                .map_err(|err| TweetyError::JsonParseError(format!("{}: {}", path.display(), err)))?
+                .interactions
+        };
+
+        Ok(Cassette {
+            path,
+            recording,
+            state: Mutex::new(State {
+                replayed: vec![false; interactions.len()],
+                interactions,
+            }),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Whether requests are sent and recorded, rather than replayed.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// The interactions recorded so far, or loaded from the file.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.state().interactions.clone()
+    }
+
+    /// Number of loaded interactions that were not replayed yet.
+    pub fn unplayed(&self) -> usize {
+        self.state()
+            .replayed
+            .iter()
+            .filter(|replayed| !**replayed)
+            .count()
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Sends and records `request`, or replays its response.
+    /// Like `TweetyClient::dispatch`, network errors are returned in the inner `Result`.
+    pub(crate) async fn execute(
+        &self,
+        http: &reqwest::Client,
+        request: reqwest::Request,
+    ) -> Result<Result<RawResponse, reqwest::Error>, TweetyError> {
+        let recorded = RecordedRequest::from_request(&request);
+        if !self.recording {
+            return self.replay(&recorded).map(Ok);
+        }
+
+        let response = match http.execute(request).await {
+            Ok(response) => response,
+            Err(err) => return Ok(Err(err)),
+        };
+        let headers = response.headers().clone();
+        let response = match RawResponse::try_read(response).await {
+            Ok(response) => response,
+            Err(err) => return Ok(Err(err)),
+        };
+
+        self.record(Interaction {
+            request: recorded,
+            response: RecordedResponse::new(response.status, &headers, &response.body),
+        })?;
+        Ok(Ok(response))
+    }
+
+    /// Answers with the first interaction not replayed yet whose request matches.
+    fn replay(&self, request: &RecordedRequest) -> Result<RawResponse, TweetyError> {
+        let mut state = self.state();
+        let State {
+            interactions,
+            replayed,
+        } = &mut *state;
+
+        let index = interactions
+            .iter()
+            .zip(replayed.iter())
+            .position(|(interaction, replayed)| !replayed && interaction.request.matches(request))
+            .ok_or_else(|| {
+                let body = request
+                    .body
+                    .as_ref()
+                    .map(|body| format!(" with body {}", body))
+                    .unwrap_or_default();
+                TweetyError::CassetteMismatch(format!(
+                    "no interaction left in {} for {} {}{}",
+                    self.path.display(),
+                    request.method,
+                    request.url,
+                    body
+                ))
+            })?;
+
+        replayed[index] = true;
+        Ok(interactions[index].response.to_raw())
+    }
+
+    /// Appends `interaction` and rewrites the file, so the cassette is
+    /// complete even if the test fails later on.
+    fn record(&self, interaction: Interaction) -> Result<(), TweetyError> {
+        let mut state = self.state();
+        state.interactions.push(interaction);
+        state.replayed.push(true);
+
+        let file = CassetteFile {
+            interactions: state.interactions.clone(),
+        };
+        let json = serde_json::to_string_pretty(&file)
+            .map_err(|err| TweetyError::JsonParseError(err.to_string()))?;
+
+        let io_error = |err: std::io::Error| {
+            TweetyError::FileIOError(format!("{}: {}", self.path.display(), err))
+        };
+        if let Some(parent) = self
+            .path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let mut tmp = self.path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, &self.path))
+            .map_err(io_error)
+    }
+}
+
+impl RecordedRequest {
+    fn from_request(request: &reqwest::Request) -> Self {
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                if *name == AUTHORIZATION {
+                    (name.to_string(), scrub_authorization(&value))
+                } else {
+                    (name.to_string(), value)
+                }
+            })
+            .collect();
+        let body = request
+            .body()
+            .and_then(reqwest::Body::as_bytes)
+            .filter(|body| !body.is_empty())
+            .map(|body| scrub_body(body_to_value(body)));
+
+        RecordedRequest {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers,
+            body,
+        }
+    }
+
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        let authorization = |request: &RecordedRequest| {
+            request
+                .headers
+                .get(AUTHORIZATION.as_str())
+                .map(|value| without_volatile_params(value))
+        };
+
+        self.method.eq_ignore_ascii_case(&other.method)
+            && same_url(&self.url, &other.url)
+            && self.body == other.body
+            && authorization(self) == authorization(other)
+    }
+}
+
+impl RecordedResponse {
+    fn new(status: StatusCode, headers: &HeaderMap, body: &str) -> Self {
+        let mut recorded = BTreeMap::<String, String>::new();
+        for (name, value) in headers {
+            if SKIPPED_RESPONSE_HEADERS.contains(&name.as_str()) {
+                continue;
+            }
+            let value = String::from_utf8_lossy(value.as_bytes());
+            recorded
+                .entry(name.to_string())
+                .and_modify(|values| {
+                    values.push_str(", ");
+                    values.push_str(&value);
+                })
+                .or_insert_with(|| value.into_owned());
+        }
+
+        RecordedResponse {
+            status: status.as_u16(),
+            headers: recorded,
+            body: if body.is_empty() {
+                Value::Null
+            } else {
+                scrub_body(body_to_value(body.as_bytes()))
+            },
+        }
+    }
+
+    fn to_raw(&self) -> RawResponse {
+        let headers: HeaderMap = self
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_str(value).ok()?,
+                ))
+            })
+            .collect();
+
+        RawResponse {
+            status: StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK),
+            rate_limit: RateLimit::from_headers(&headers),
+            body: match &self.body {
+                Value::Null => String::new(),
+                Value::String(body) => body.clone(),
+                body => body.to_string(),
+            },
+        }
+    }
+}
+
+fn body_to_value(body: &[u8]) -> Value {
+    serde_json::from_slice(body)
+        .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned()))
+}
+
+/// Replaces the [`SECRET_BODY_FIELDS`] of a JSON body, at any depth, or of a
+/// form-encoded one with [`REDACTED_TOKEN`].
+fn scrub_body(body: Value) -> Value {
+    match body {
+        Value::String(form) => Value::String(scrub_form(&form)),
+        json => scrub_json(json),
+    }
+}
+
+fn scrub_json(value: Value) -> Value {
+    match value {
+        Value::Object(fields) => fields
+            .into_iter()
+            .map(|(name, value)| {
+                if SECRET_BODY_FIELDS.contains(&name.as_str()) {
+                    (name, Value::String(REDACTED_TOKEN.to_string()))
+                } else {
+                    (name, scrub_json(value))
+                }
+            })
+            .collect(),
+        Value::Array(values) => values.into_iter().map(scrub_json).collect(),
+        value => value,
+    }
+}
+
+fn scrub_form(form: &str) -> String {
+    form.split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if SECRET_BODY_FIELDS.contains(&name) => {
+                format!("{}={}", name, REDACTED_TOKEN)
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// Replaces the credentials of an `Authorization` header with [`REDACTED`],
+/// keeping the scheme and, for OAuth 1.0a, the other parameters.
+fn scrub_authorization(value: &str) -> String {
+    if let Some(params) = value.strip_prefix("OAuth ") {
+        let params: Vec<String> = params
+            .split(',')
+            .map(|param| {
+                let param = param.trim();
+                match param.split_once('=') {
+                    Some((name, _)) if SECRET_OAUTH_PARAMS.contains(&name) => {
+                        format!("{}=\"{}\"", name, REDACTED)
+                    }
+                    _ => param.to_string(),
+                }
+            })
+            .collect();
+        return format!("OAuth {}", params.join(","));
+    }
+
+    match value.split_once(' ') {
+        Some((scheme, _)) => format!("{} {}", scheme, REDACTED),
+        None => REDACTED.to_string(),
+    }
+}
+
+fn without_volatile_params(authorization: &str) -> String {
+    match authorization.strip_prefix("OAuth ") {
+        Some(params) => params
+            .split(',')
+            .map(str::trim)
+            .filter(|param| {
+                let name = param.split('=').next().unwrap_or_default();
+                !VOLATILE_OAUTH_PARAMS.contains(&name)
+            })
+            .collect::<Vec<_>>()
+            .join(","),
+        None => authorization.to_string(),
+    }
+}
+
+/// Compares two URLs, ignoring the order of their query parameters.
+fn same_url(a: &str, b: &str) -> bool {
+    match (Url::parse(a), Url::parse(b)) {
+        (Ok(a), Ok(b)) => {
+            let query = |url: &Url| {
+                let mut pairs: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+                pairs.sort();
+                pairs
+            };
+            a[..url::Position::AfterPath] == b[..url::Position::AfterPath] && query(&a) == query(&b)
+        }
+        _ => a == b,
+    }
+}
@@ -1,19 +1,67 @@
-use crate::api::error::TweetyError;
-use reqwest::Method;
-use reqwest_oauth1::{self, OAuthClientProvider};
-use serde::{Deserialize, Serialize};
+use crate::api::auth::Auth;
+use crate::api::builder::TweetyClientBuilder;
+use crate::api::cassette::Cassette;
+use crate::api::credentials::Credentials;
+use crate::api::error::{TweetyError, TwitterError};
+use crate::api::oauth2::OAuth2Token;
+use crate::api::rate_limit::{RateLimit, RateLimitPolicy};
+use crate::api::retry::RetryPolicy;
+use crate::types::response::Response;
+use reqwest::header::{HeaderMap, CONTENT_TYPE};
+use reqwest::{Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use url::Url;
 
-#[derive(Debug, Deserialize, Serialize)]
+/// HTTP methods accepted by [`TweetyClient::send_request_with`].
+const SUPPORTED_METHODS: [Method; 5] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::PATCH,
+    Method::DELETE,
+];
+
+/// Production base URL for the v2 (and v1.1) REST endpoints.
+pub const DEFAULT_API_BASE_URL: &str = "https://api.x.com";
+/// Production base URL for the v1.1 media upload endpoints.
+pub const DEFAULT_UPLOAD_BASE_URL: &str = "https://upload.twitter.com";
+/// Production base URL for the streaming endpoints.
+pub const DEFAULT_STREAM_BASE_URL: &str = "https://api.x.com";
+
+/// Credentials are redacted in both the `Debug` and the `Serialize` output,
+/// see [`crate::api::credentials::Secret`]. As the credentials can't be read
+/// back, the client can't be deserialized: persist its [`Credentials`] and
+/// build it again from them instead.
+#[derive(Debug, Serialize)]
 pub struct TweetyClient {
-    pub(crate) consumer_key: String,
-    pub(crate) access_token: String,
-    pub(crate) consumer_key_secret: String,
-    pub(crate) access_token_secret: String,
+    #[serde(serialize_with = "Auth::serialize_redacted")]
+    pub(crate) auth: Auth,
+    pub(crate) api_base_url: String,
+    pub(crate) upload_base_url: String,
+    pub(crate) stream_base_url: String,
+    #[serde(skip)]
+    pub(crate) http: reqwest::Client,
+    #[serde(skip)]
+    pub(crate) rate_limit_policy: RateLimitPolicy,
+    #[serde(skip)]
+    pub(crate) retry_policy: RetryPolicy,
+    #[serde(skip)]
+    pub(crate) rate_limits: Arc<Mutex<HashMap<String, RateLimit>>>,
+    #[serde(skip)]
+    pub(crate) cassette: Option<Arc<Cassette>>,
 }
 
 impl TweetyClient {
+    /// Returns a [`TweetyClientBuilder`] to configure credentials, base URLs
+    /// and the underlying HTTP client.
+    pub fn builder() -> TweetyClientBuilder {
+        TweetyClientBuilder::new()
+    }
+
     /// Creates a new `TweetyClient` instance with the given credentials.
     ///
     /// # Parameters
@@ -34,6 +82,10 @@ impl TweetyClient {
     ///     "your_access_token_secret",
     /// );
     /// ```
+    #[deprecated(
+        since = "0.1.5",
+        note = "the positional arguments are easy to mix up, use `TweetyClient::builder()` instead"
+    )]
     pub fn new(
         consumer_key: &str,
         access_token: &str,
@@ -41,18 +93,99 @@ impl TweetyClient {
         access_token_secret: &str,
     ) -> Self {
         TweetyClient {
-            consumer_key: consumer_key.to_string(),
-            access_token: access_token.to_string(),
-            consumer_key_secret: consumer_key_secret.to_string(),
-            access_token_secret: access_token_secret.to_string(),
+            auth: Auth::OAuth1(Credentials::new(
+                consumer_key,
+                consumer_key_secret,
+                access_token,
+                access_token_secret,
+            )),
+            api_base_url: DEFAULT_API_BASE_URL.to_string(),
+            upload_base_url: DEFAULT_UPLOAD_BASE_URL.to_string(),
+            stream_base_url: DEFAULT_STREAM_BASE_URL.to_string(),
+            http: reqwest::Client::new(),
+            rate_limit_policy: RateLimitPolicy::default(),
+            retry_policy: RetryPolicy::none(),
+            rate_limits: Arc::default(),
+            cassette: None,
         }
     }
+
+    /// Overrides the base URL used for the REST endpoints, e.g. to point the
+    /// client at a local mock server. Defaults to [`DEFAULT_API_BASE_URL`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tweety_rs::api::client::TweetyClient;
+    /// let client = TweetyClient::builder()
+    ///     .consumer_key("your_consumer_key")
+    ///     .consumer_key_secret("your_consumer_key_secret")
+    ///     .access_token("your_access_token")
+    ///     .access_token_secret("your_access_token_secret")
+    ///     .build()
+    ///     .unwrap()
+    ///     .with_api_base_url("http://127.0.0.1:8080");
+    ///
+    /// assert_eq!(client.api_base_url(), "http://127.0.0.1:8080");
+    /// ```
+    pub fn with_api_base_url(mut self, base_url: &str) -> Self {
+        self.api_base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Overrides the base URL used for media uploads.
+    /// Defaults to [`DEFAULT_UPLOAD_BASE_URL`].
+    pub fn with_upload_base_url(mut self, base_url: &str) -> Self {
+        self.upload_base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Overrides the base URL used for the streaming endpoints.
+    /// Defaults to [`DEFAULT_STREAM_BASE_URL`].
+    pub fn with_stream_base_url(mut self, base_url: &str) -> Self {
+        self.stream_base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn api_base_url(&self) -> &str {
+        &self.api_base_url
+    }
+
+    pub fn upload_base_url(&self) -> &str {
+        &self.upload_base_url
+    }
+
+    pub fn stream_base_url(&self) -> &str {
+        &self.stream_base_url
+    }
+
+    /// How this client authenticates its requests.
+    pub fn auth(&self) -> &Auth {
+        &self.auth
+    }
+
+    /// The current OAuth 2.0 user token, if the client uses one.
+    /// It changes whenever the token is refreshed, persist it to avoid
+    /// going through the authorization flow again.
+    pub fn oauth2_token(&self) -> Option<OAuth2Token> {
+        match &self.auth {
+            Auth::OAuth2(session) => Some(session.token()),
+            _ => None,
+        }
+    }
+
+    /// The cassette requests are recorded to or replayed from, if any.
+    /// See [`crate::api::cassette`].
+    pub fn cassette(&self) -> Option<&Cassette> {
+        self.cassette.as_deref()
+    }
+
     pub fn is_initialized(&self) -> bool {
-        !self.consumer_key.is_empty()
-            && !self.access_token.is_empty()
-            && !self.consumer_key_secret.is_empty()
-            && !self.access_token_secret.is_empty()
+        self.auth.is_initialized()
     }
+
+    /// Sends a request without extra query parameters or headers.
+    /// See [`TweetyClient::send_request_with`].
     pub(crate) async fn send_request<T>(
         &self,
         url: &str,
@@ -60,77 +193,287 @@ impl TweetyClient {
         body: Option<T>,
     ) -> Result<Value, TweetyError>
     where
-        T: Serialize + Deserialize<'static>,
+        T: Serialize,
+    {
+        self.send_request_with(url, method, &[], HeaderMap::new(), body)
+            .await
+    }
+
+    /// Sends a request and parses the response into `R`, keeping the raw JSON.
+    pub(crate) async fn request<R, T>(
+        &self,
+        url: &str,
+        method: Method,
+        body: Option<T>,
+    ) -> Result<Response<R>, TweetyError>
+    where
+        R: DeserializeOwned,
+        T: Serialize,
+    {
+        let value = self.send_request(url, method, body).await?;
+        Response::from_value(value)
+    }
+
+    /// Signs and sends a request to `url`.
+    ///
+    /// * `query` - Extra query parameters, appended to the ones already in `url`.
+    /// * `headers` - Extra headers. `Content-Type: application/json` is added when a body is set.
+    /// * `body` - Serialized as JSON.
+    ///
+    /// Only GET, POST, PUT, PATCH and DELETE are supported, any other method
+    /// fails with `TweetyError::UnsupportedMethod`. A successful response with
+    /// an empty body is returned as `Value::Null`.
+    pub(crate) async fn send_request_with<T>(
+        &self,
+        url: &str,
+        method: Method,
+        query: &[(&str, &str)],
+        headers: HeaderMap,
+        body: Option<T>,
+    ) -> Result<Value, TweetyError>
+    where
+        T: Serialize,
     {
         if !self.is_initialized() {
             return Err(TweetyError::MissingCredentials);
         };
 
-        let parsed_url = match Url::parse(url) {
-            Ok(url) => url,
-            Err(err) => {
-                println!("{}", err);
-                return Err(TweetyError::UrlParseError(err));
+        if !SUPPORTED_METHODS.contains(&method) {
+            return Err(TweetyError::UnsupportedMethod(method));
+        }
+
+        let parsed_url = Url::parse(url).map_err(TweetyError::UrlParseError)?;
+        let json_body = match body {
+            Some(body) => Some(
+                serde_json::to_string(&body)
+                    .map_err(|err| TweetyError::JsonParseError(err.to_string()))?,
+            ),
+            None => None,
+        };
+        let bucket = rate_limit_bucket(&method, &parsed_url);
+
+        self.auth.refresh_if_expired().await?;
+
+        let mut attempt = 1;
+        let mut waited_for_rate_limit = false;
+        let response = loop {
+            let response = match self
+                .dispatch(&parsed_url, &method, query, &headers, json_body.as_deref())
+                .await?
+            {
+                Ok(response) => response,
+                Err(err) => {
+                    if self.retry_policy.should_retry_error(&method, &err, attempt) {
+                        tokio::time::sleep(self.retry_policy.backoff(attempt)).await;
+                        attempt += 1;
+                        continue;
+                    }
+                    return Err(TweetyError::NetworkError(err.to_string()));
+                }
+            };
+            self.record_rate_limit(&bucket, response.rate_limit);
+
+            if response.status == StatusCode::TOO_MANY_REQUESTS && !waited_for_rate_limit {
+                if let (RateLimitPolicy::WaitAndRetry { max_wait }, Some(rate_limit)) =
+                    (self.rate_limit_policy, response.rate_limit)
+                {
+                    let wait = rate_limit.reset_in();
+                    if wait <= max_wait {
+                        tokio::time::sleep(wait).await;
+                        waited_for_rate_limit = true;
+                        continue;
+                    }
+                }
+            }
+
+            if self
+                .retry_policy
+                .should_retry_status(&method, response.status, attempt)
+            {
+                tokio::time::sleep(self.retry_policy.backoff(attempt)).await;
+                attempt += 1;
+                continue;
             }
+
+            break response;
         };
 
-        let secrets = reqwest_oauth1::Secrets::new(&self.consumer_key, &self.consumer_key_secret)
-            .token(&self.access_token, &self.access_token_secret);
+        response.into_result()
+    }
 
-        let client = reqwest::Client::new();
-        let mut json_body = String::new();
+    /// Signs and sends a single attempt of a request.
+    async fn dispatch(
+        &self,
+        url: &Url,
+        method: &Method,
+        query: &[(&str, &str)],
+        headers: &HeaderMap,
+        body: Option<&str>,
+    ) -> Result<Result<RawResponse, reqwest::Error>, TweetyError> {
+        let mut request = self
+            .auth
+            .authorize(&self.http, method.clone(), url.clone(), query)?
+            .headers(headers.clone());
 
-        if body.is_some() {
-            json_body = serde_json::to_string(&body).unwrap();
+        if let Some(body) = body {
+            request = request
+                .header(CONTENT_TYPE, "application/json")
+                .body(body.to_string());
         }
 
-        let response = if method == "POST" {
-            client
-                .oauth1(secrets)
-                .post(&parsed_url.to_string())
-                .header("Content-Type", "application/json")
-                .body(json_body)
-                .send()
-        } else if method == "GET" {
-            client.oauth1(secrets).get(&parsed_url.to_string()).send()
-        } else if method == "DELETE" {
-            client
-                .oauth1(secrets)
-                .delete(&parsed_url.to_string())
-                .send()
-        } else if method == "PUT" {
-            client.oauth1(secrets).put(&parsed_url.to_string()).send()
-        } else {
-            //TODO : a good way to handle this without panicking
-            panic!("Invalid method");
+        self.execute(request).await
+    }
+
+    /// Sends a request and reads its response, or replays it from the cassette.
+    /// Network errors are returned in the inner `Result`, so they can be retried.
+    pub(crate) async fn execute(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<Result<RawResponse, reqwest::Error>, TweetyError> {
+        let (http, request) = request.build_split();
+        let request = match request {
+            Ok(request) => request,
+            Err(err) => return Ok(Err(err)),
         };
 
-        match response.await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    let api_response = response
-                        .json::<Value>()
-                        .await
-                        .map_err(|err| TweetyError::JsonParseError(err.to_string()))?;
+        match &self.cassette {
+            Some(cassette) => cassette.execute(&http, request).await,
+            None => Ok(match http.execute(request).await {
+                Ok(response) => RawResponse::try_read(response).await,
+                Err(err) => Err(err),
+            }),
+        }
+    }
 
-                    return Ok(api_response);
-                }
-                let status = response.status();
+    fn record_rate_limit(&self, bucket: &str, rate_limit: Option<RateLimit>) {
+        if let Some(rate_limit) = rate_limit {
+            if let Ok(mut rate_limits) = self.rate_limits.lock() {
+                rate_limits.insert(bucket.to_string(), rate_limit);
+            }
+        }
+    }
 
-                let status_text = response
-                    .json::<Value>()
-                    .await
-                    .map_err(|err| TweetyError::JsonParseError(err.to_string()))?;
+    /// Returns the last rate-limit state seen for an endpoint.
+    ///
+    /// `endpoint` is the request path, either as documented
+    /// (`/2/users/:id/followers`) or as sent (`/2/users/2244994945/followers`).
+    /// Numeric and `:placeholder` path segments are all treated as `:id`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use reqwest::Method;
+    /// use tweety_rs::TweetyClient;
+    ///
+    /// let client = TweetyClient::builder()
+    ///     .consumer_key("your_consumer_key")
+    ///     .consumer_key_secret("your_consumer_key_secret")
+    ///     .access_token("your_access_token")
+    ///     .access_token_secret("your_access_token_secret")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// // Nothing has been requested yet.
+    /// assert!(client.rate_limit(Method::GET, "/2/users/:id/followers").is_none());
+    /// ```
+    pub fn rate_limit(&self, method: Method, endpoint: &str) -> Option<RateLimit> {
+        let bucket = format!("{} {}", method, normalize_path(endpoint));
+        self.rate_limits.lock().ok()?.get(&bucket).copied()
+    }
+
+    /// Returns the last rate-limit state of every endpoint requested so far,
+    /// keyed by `"METHOD /path/:id"`.
+    pub fn rate_limits(&self) -> HashMap<String, RateLimit> {
+        self.rate_limits
+            .lock()
+            .map(|rate_limits| rate_limits.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Status, rate-limit headers and body of a response that was fully read.
+pub(crate) struct RawResponse {
+    pub(crate) status: StatusCode,
+    pub(crate) rate_limit: Option<RateLimit>,
+    pub(crate) body: String,
+}
+
+impl RawResponse {
+    pub(crate) async fn try_read(response: reqwest::Response) -> Result<Self, reqwest::Error> {
+        let status = response.status();
+        let rate_limit = RateLimit::from_headers(response.headers());
+        let body = response.text().await?;
+
+        Ok(RawResponse {
+            status,
+            rate_limit,
+            body,
+        })
+    }
 
-                Err(TweetyError::ApiError(format!(
-                    "HTTP {}: {}",
-                    status, status_text
-                )))
+    pub(crate) async fn read(response: reqwest::Response) -> Result<Self, TweetyError> {
+        Self::try_read(response)
+            .await
+            .map_err(|err| TweetyError::NetworkError(err.to_string()))
+    }
+
+    /// Parses the body as JSON on success, into a `TwitterError` otherwise.
+    /// An empty successful body is returned as `Value::Null`.
+    ///
+    /// A successful body with partial errors is returned as is, the typed
+    /// responses keep them in their `errors` field. When *every* requested
+    /// resource failed, the body only has `errors` and is turned into a `TwitterError`.
+    pub(crate) fn into_result(self) -> Result<Value, TweetyError> {
+        if self.status.is_success() {
+            if self.body.trim().is_empty() {
+                return Ok(Value::Null);
             }
-            Err(err) => {
-                println!("Error while sending request: {}", err);
-                Err(TweetyError::NetworkError(err.to_string()))
+            let value = serde_json::from_str::<Value>(&self.body)
+                .map_err(|err| TweetyError::JsonParseError(err.to_string()))?;
+            if only_errors(&value) {
+                return Err(TwitterError::from_response(
+                    self.status.as_u16(),
+                    &self.body,
+                    self.rate_limit,
+                )
+                .into());
             }
+            return Ok(value);
         }
+
+        Err(TwitterError::from_response(self.status.as_u16(), &self.body, self.rate_limit).into())
     }
 }
+
+fn only_errors(value: &Value) -> bool {
+    let has_errors = value
+        .get("errors")
+        .and_then(Value::as_array)
+        .is_some_and(|errors| !errors.is_empty());
+
+    has_errors && value.get("data").is_none() && value.get("meta").is_none()
+}
+
+fn rate_limit_bucket(method: &Method, url: &Url) -> String {
+    format!("{} {}", method, normalize_path(url.path()))
+}
+
+fn normalize_path(path: &str) -> String {
+    path.trim_end_matches('/')
+        .split('/')
+        .enumerate()
+        .map(|(index, segment)| {
+            // Segment 1 is the API version (`2`, `1.1`), never an id.
+            let is_id = segment.starts_with(':')
+                || (index > 1
+                    && !segment.is_empty()
+                    && segment.bytes().all(|b| b.is_ascii_digit()));
+            if is_id {
+                ":id"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
@@ -0,0 +1,197 @@
+//! OAuth 1.0a credentials, loaded from the environment or from a profiles file.
+//!
+//! Secrets are wrapped in [`Secret`], which never prints its value, so a `{:?}`
+//! of a [`TweetyClient`](crate::TweetyClient) doesn't leak them. See [`Secret`]
+//! for how they are serialized.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use tweety_rs::api::credentials::Credentials;
+//! use tweety_rs::TweetyClient;
+//!
+//! # fn example() -> Result<(), tweety_rs::api::error::TweetyError> {
+//! // Reads CONSUMER_API_KEY, CONSUMER_API_SECRET, ACCESS_TOKEN and
+//! // ACCESS_TOKEN_SECRET, from a `.env` file if there is one.
+//! let client = TweetyClient::builder()
+//!     .credentials(Credentials::from_env()?)
+//!     .build()?;
+//!
+//! // Or pick one account from a file holding several.
+//! let bot = TweetyClient::builder()
+//!     .credentials(Credentials::from_profile("accounts.toml", "bot")?)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::error::TweetyError;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+/// Environment variable holding the consumer key (API key).
+pub const CONSUMER_KEY_VAR: &str = "CONSUMER_API_KEY";
+/// Environment variable holding the consumer key secret (API secret key).
+pub const CONSUMER_KEY_SECRET_VAR: &str = "CONSUMER_API_SECRET";
+/// Environment variable holding the access token.
+pub const ACCESS_TOKEN_VAR: &str = "ACCESS_TOKEN";
+/// Environment variable holding the access token secret.
+pub const ACCESS_TOKEN_SECRET_VAR: &str = "ACCESS_TOKEN_SECRET";
+
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+/// A credential whose value is only reachable through [`Secret::expose`].
+///
+/// `Debug` and `Display` output `[REDACTED]`, so secrets stay out of logs.
+/// `Serialize` writes the actual value, so the types holding secrets, such as
+/// [`Credentials`], [`AccessToken`](crate::api::oauth1::AccessToken) and
+/// [`OAuth2Token`](crate::api::oauth2::OAuth2Token), can be persisted and
+/// deserialized again: treat their serialized form as a secret too. The one
+/// exception is [`TweetyClient`](crate::TweetyClient), whose `Serialize` output
+/// is meant for inspection and redacts its credentials.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: &str) -> Self {
+        Secret(value.to_string())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Secret(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Secret::new(value)
+    }
+}
+
+impl PartialEq<str> for Secret {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+/// The four OAuth 1.0a User Context credentials.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Credentials {
+    pub consumer_key: Secret,
+    pub consumer_key_secret: Secret,
+    pub access_token: Secret,
+    pub access_token_secret: Secret,
+}
+
+impl Credentials {
+    pub fn new(
+        consumer_key: &str,
+        consumer_key_secret: &str,
+        access_token: &str,
+        access_token_secret: &str,
+    ) -> Self {
+        Credentials {
+            consumer_key: Secret::new(consumer_key),
+            consumer_key_secret: Secret::new(consumer_key_secret),
+            access_token: Secret::new(access_token),
+            access_token_secret: Secret::new(access_token_secret),
+        }
+    }
+
+    /// Reads [`CONSUMER_KEY_VAR`], [`CONSUMER_KEY_SECRET_VAR`], [`ACCESS_TOKEN_VAR`]
+    /// and [`ACCESS_TOKEN_SECRET_VAR`]. Variables missing from the process
+    /// environment are looked up in a `.env` file in the current directory or
+    /// one of its parents.
+    pub fn from_env() -> Result<Self, TweetyError> {
+        // A missing .env file is fine, the variables may be set directly.
+        let _ = dotenvy::dotenv();
+
+        Ok(Credentials {
+            consumer_key: env_var(CONSUMER_KEY_VAR)?,
+            consumer_key_secret: env_var(CONSUMER_KEY_SECRET_VAR)?,
+            access_token: env_var(ACCESS_TOKEN_VAR)?,
+            access_token_secret: env_var(ACCESS_TOKEN_SECRET_VAR)?,
+        })
+    }
+
+    /// Loads every profile of a config file. Files ending in `.json` are parsed
+    /// as JSON, anything else as TOML. Each top-level table is a profile:
+    ///
+    /// ```toml
+    /// [personal]
+    /// consumer_key = "..."
+    /// consumer_key_secret = "..."
+    /// access_token = "..."
+    /// access_token_secret = "..."
+    ///
+    /// [bot]
+    /// # ...
+    /// ```
+    pub fn profiles<P: AsRef<Path>>(path: P) -> Result<BTreeMap<String, Credentials>, TweetyError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| TweetyError::FileIOError(format!("{}: {}", path.display(), err)))?;
+
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+        if is_json {
+            serde_json::from_str(&contents)
+                .map_err(|err| TweetyError::ConfigError(format!("{}: {}", path.display(), err)))
+        } else {
+            toml::from_str(&contents)
+                .map_err(|err| TweetyError::ConfigError(format!("{}: {}", path.display(), err)))
+        }
+    }
+
+    /// Loads the profile called `name` from a config file, see [`Credentials::profiles`].
+    pub fn from_profile<P: AsRef<Path>>(path: P, name: &str) -> Result<Self, TweetyError> {
+        let path = path.as_ref();
+        Self::profiles(path)?.remove(name).ok_or_else(|| {
+            TweetyError::ConfigError(format!(
+                "profile `{}` not found in {}",
+                name,
+                path.display()
+            ))
+        })
+    }
+
+    pub fn is_complete(&self) -> bool {
+        !self.consumer_key.is_empty()
+            && !self.consumer_key_secret.is_empty()
+            && !self.access_token.is_empty()
+            && !self.access_token_secret.is_empty()
+    }
+}
+
+fn env_var(name: &str) -> Result<Secret, TweetyError> {
+    match std::env::var(name) {
+        Ok(value) if !value.is_empty() => Ok(Secret(value)),
+        _ => Err(TweetyError::ConfigError(format!("{} is not set", name))),
+    }
+}
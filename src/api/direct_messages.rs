@@ -1,209 +1,78 @@
-use super::error::TweetyError;
+use super::error::{ErrorDetail, TweetyError};
+use super::fields::{query_enum, Fields, QueryString};
+use super::pagination::{Paginator, TokenParam};
+use crate::types::direct_message::DmEvent;
+use crate::types::id::{DmConversationId, UserId};
+use crate::types::response::{Includes, Meta, Response};
 use crate::TweetyClient;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum DMEventField {
-    Id,
-    Text,
-    EventType,
-    CreatedAt,
-    DmConversationId,
-    SenderId,
-    ParticipantIds,
-    ReferencedTweets,
-    Attachments,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum EventType {
-    MessageCreate,
-    ParticipantsJoin,
-    ParticipantsLeave,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum Expansion {
-    AttachmentsMediaKeys,
-    ReferencedTweetsId,
-    SenderId,
-    ParticipantIds,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum MediaField {
-    DurationMs,
-    Height,
-    MediaKey,
-    PreviewImageUrl,
-    Type,
-    Url,
-    Width,
-    PublicMetrics,
-    AltText,
-    Variants,
-}
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum TweetField {
-    Attachments,
-    AuthorId,
-    ContextAnnotations,
-    ConversationId,
-    CreatedAt,
-    EditControls,
-    Entities,
-    Geo,
-    Id,
-    InReplyToUserId,
-    Lang,
-    PublicMetrics,
-    PossiblySensitive,
-    ReferencedTweets,
-    ReplySettings,
-    Source,
-    Text,
-    Withheld,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum UserField {
-    CreatedAt,
-    Description,
-    Entities,
-    Id,
-    Location,
-    MostRecentTweetId,
-    Name,
-    PinnedTweetId,
-    ProfileImageUrl,
-    Protected,
-    PublicMetrics,
-    Url,
-    Username,
-    Verified,
-    Withheld,
+query_enum! {
+    pub enum EventType {
+        MessageCreate => "MessageCreate",
+        ParticipantsJoin => "ParticipantsJoin",
+        ParticipantsLeave => "ParticipantsLeave",
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// [Docs](https://developer.x.com/en/docs/x-api/direct-messages/lookup/api-reference/get-dm_events)
+#[derive(Debug, Clone, Default)]
 pub struct QueryParams {
-    #[serde(rename = "dm_event.fields")]
-    pub dm_event_fields: Option<Vec<DMEventField>>,
-
-    #[serde(rename = "event_types")]
     pub event_types: Option<Vec<EventType>>,
 
-    #[serde(rename = "expansions")]
-    pub expansions: Option<Vec<Expansion>>,
-
     /// The maximum number of results to be returned in a page. Must be between 1 and 100. The default is 100.
-    #[serde(rename = "max_results")]
     pub max_results: Option<u32>,
 
-    #[serde(rename = "media.fields")]
-    pub media_fields: Option<Vec<MediaField>>,
-
     /// Contains either the next_token or previous_token value.
-    #[serde(rename = "pagination_token")]
     pub pagination_token: Option<String>,
 
-    #[serde(rename = "tweet.fields")]
-    pub tweet_fields: Option<Vec<TweetField>>,
-
-    #[serde(rename = "user.fields")]
-    pub user_fields: Option<Vec<UserField>>,
+    /// `dm_event.fields`, `expansions`, `media.fields`, `tweet.fields` and `user.fields`.
+    pub fields: Fields,
 }
 
-/// [Docs](https://developer.x.com/en/docs/x-api/direct-messages/lookup/api-reference/get-dm_events)
-///
 impl QueryParams {
     pub fn to_query_string(&self) -> String {
-        let mut params = vec![];
-
-        if let Some(ref fields) = self.dm_event_fields {
-            let fields_str = fields
-                .iter()
-                .map(|f| serde_json::to_string(f).unwrap())
-                .collect::<Vec<_>>()
-                .join(",");
-            params.push(format!("dm_event.fields={}", fields_str));
-        }
+        self.query().to_string()
+    }
 
+    fn query(&self) -> QueryString {
+        let mut query = QueryString::new();
         if let Some(ref types) = self.event_types {
-            let types_str = types
-                .iter()
-                .map(|t| serde_json::to_string(t).unwrap())
-                .collect::<Vec<_>>()
-                .join(",");
-            params.push(format!("event_types={}", types_str));
-        }
-
-        if let Some(ref exps) = self.expansions {
-            let exps_str = exps
-                .iter()
-                .map(|e| serde_json::to_string(e).unwrap())
-                .collect::<Vec<_>>()
-                .join(",");
-            params.push(format!("expansions={}", exps_str));
-        }
-
-        if let Some(max_results) = self.max_results {
-            params.push(format!("max_results={}", max_results));
+            query.push_list("event_types", types.iter().map(EventType::as_str));
         }
-
-        if let Some(ref media_fields) = self.media_fields {
-            let media_str = media_fields
-                .iter()
-                .map(|m| serde_json::to_string(m).unwrap())
-                .collect::<Vec<_>>()
-                .join(",");
-            params.push(format!("media.fields={}", media_str));
-        }
-
-        if let Some(ref token) = self.pagination_token {
-            params.push(format!("pagination_token={}", token));
-        }
-
-        if let Some(ref tweet_fields) = self.tweet_fields {
-            let tweet_str = tweet_fields
-                .iter()
-                .map(|t| serde_json::to_string(t).unwrap())
-                .collect::<Vec<_>>()
-                .join(",");
-            params.push(format!("tweet.fields={}", tweet_str));
-        }
-
-        if let Some(ref user_fields) = self.user_fields {
-            let user_str = user_fields
-                .iter()
-                .map(|u| serde_json::to_string(u).unwrap())
-                .collect::<Vec<_>>()
-                .join(",");
-            params.push(format!("user.fields={}", user_str));
-        }
-
-        params.join("&")
+        query
+            .push_opt("max_results", self.max_results)
+            .push_opt("pagination_token", self.pagination_token.as_ref());
+        self.fields.append_to(&mut query);
+        query
     }
 }
 
+/// Response of the Direct Message lookup endpoints.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DmEventsResponse {
+    #[serde(default)]
+    pub data: Vec<DmEvent>,
+    pub includes: Option<Includes>,
+    pub meta: Option<Meta>,
+    /// Resources that could not be returned, see [`ErrorDetail`].
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
+}
+
 impl TweetyClient {
     /// Returns a list of Direct Messages for the authenticated user, both sent and received. Direct
     /// Message events are returned in reverse chronological order.
     /// Supports retrieving events from the previous 30 days.
     /// Authentication methods supported by this endpoint
-    // OAuth 2.0 Authorization Code with PKCE
-    pub async fn get_direct_messages(&self, params: QueryParams) -> Result<Value, TweetyError> {
-        let url = format!("https://api.x.com/2/dm_events?{}", params.to_query_string());
-        println!("Url: {}", url);
-        self.send_request::<()>(&url, Method::GET, None).await
+    /// OAuth 2.0 Authorization Code with PKCE, see [`crate::api::oauth2`]
+    pub async fn get_direct_messages(
+        &self,
+        params: QueryParams,
+    ) -> Result<Response<DmEventsResponse>, TweetyError> {
+        let url = self.dm_events_url(&params);
+        self.request::<_, ()>(&url, Method::GET, None).await
     }
     /// GET /2/dm_conversations/with/:participant_id/dm_events
     //Returns a list of Direct Messages (DM) events within a 1-1 conversation
@@ -211,30 +80,78 @@ impl TweetyClient {
     /// Messages are returned in reverse chronological order.
     pub async fn get_dm_events_with_participant(
         &self,
-        participant_id: &str,
+        participant_id: UserId,
         params: QueryParams,
-    ) -> Result<Value, TweetyError> {
-        let url = format!(
-            "https://api.x.com/2/dm_conversations/with/{}/dm_events?{}",
-            participant_id,
-            params.to_query_string()
-        );
-
-        self.send_request::<()>(&url, Method::GET, None).await
+    ) -> Result<Response<DmEventsResponse>, TweetyError> {
+        let url = self.participant_dm_events_url(participant_id, &params);
+
+        self.request::<_, ()>(&url, Method::GET, None).await
     }
     /// Returns a list of Direct Messages within a conversation specified in the dm_conversation_id path parameter.
     /// Messages are returned in reverse chronological order.
     pub async fn get_dm_events_in_conversation(
         &self,
-        dm_conversation_id: &str,
+        dm_conversation_id: &DmConversationId,
+        params: QueryParams,
+    ) -> Result<Response<DmEventsResponse>, TweetyError> {
+        let url = self.conversation_dm_events_url(dm_conversation_id, &params);
+
+        self.request::<_, ()>(&url, Method::GET, None).await
+    }
+
+    /// Pages through all the Direct Message events of the authenticated user.
+    /// See [`crate::api::pagination`].
+    pub fn paginate_direct_messages(&self, params: QueryParams) -> Paginator<'_, DmEvent> {
+        let url = self.dm_events_url(&params);
+
+        Paginator::new(self, &url, TokenParam::PaginationToken)
+    }
+
+    /// Pages through all the Direct Message events of a 1-1 conversation.
+    /// See [`crate::api::pagination`].
+    pub fn paginate_dm_events_with_participant(
+        &self,
+        participant_id: UserId,
         params: QueryParams,
-    ) -> Result<Value, TweetyError> {
-        let url = format!(
-            "https://api.x.com/2/dm_conversations/{}/dm_events?{}",
-            dm_conversation_id,
-            params.to_query_string()
-        );
-
-        self.send_request::<()>(&url, Method::GET, None).await
+    ) -> Paginator<'_, DmEvent> {
+        let url = self.participant_dm_events_url(participant_id, &params);
+
+        Paginator::new(self, &url, TokenParam::PaginationToken)
+    }
+
+    /// Pages through all the Direct Message events of a conversation.
+    /// See [`crate::api::pagination`].
+    pub fn paginate_dm_events_in_conversation(
+        &self,
+        dm_conversation_id: &DmConversationId,
+        params: QueryParams,
+    ) -> Paginator<'_, DmEvent> {
+        let url = self.conversation_dm_events_url(dm_conversation_id, &params);
+
+        Paginator::new(self, &url, TokenParam::PaginationToken)
+    }
+
+    fn dm_events_url(&self, params: &QueryParams) -> String {
+        params
+            .query()
+            .append_to_url(&format!("{}/2/dm_events", self.api_base_url))
+    }
+
+    fn participant_dm_events_url(&self, participant_id: UserId, params: &QueryParams) -> String {
+        params.query().append_to_url(&format!(
+            "{}/2/dm_conversations/with/{}/dm_events",
+            self.api_base_url, participant_id
+        ))
+    }
+
+    fn conversation_dm_events_url(
+        &self,
+        dm_conversation_id: &DmConversationId,
+        params: &QueryParams,
+    ) -> String {
+        params.query().append_to_url(&format!(
+            "{}/2/dm_conversations/{}/dm_events",
+            self.api_base_url, dm_conversation_id
+        ))
     }
 }
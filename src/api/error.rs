@@ -1,3 +1,8 @@
+use crate::api::rate_limit::RateLimit;
+use crate::api::uploads::MediaCategory;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
 use thiserror::Error;
 use url::ParseError;
 
@@ -12,13 +17,88 @@ pub enum TweetyError {
     #[error("Request Error :{0}")]
     RequestError(reqwest::Error),
     #[error("API responded with an error: {0}")]
-    ApiError(String),
+    ApiError(Box<TwitterError>),
     #[error("Failed to parse JSON response: {0}")]
     JsonParseError(String),
     #[error("Parse Error: {0}")]
     UrlParseError(ParseError),
     #[error("Twitter consumer credentials are missing. Please set the 'consumer_key', 'consumer_secret', 'access_token', and 'access_token_secret' in your configuration:")]
     MissingCredentials,
+    #[error("Unsupported HTTP method: {0}")]
+    UnsupportedMethod(reqwest::Method),
+    #[error("OAuth flow failed: {0}")]
+    OAuthError(String),
+    #[error("Invalid configuration: {0}")]
+    ConfigError(String),
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+    #[error("Request not found in cassette: {0}")]
+    CassetteMismatch(String),
+    #[error("Media processing failed: {0}")]
+    MediaProcessingFailed(String),
+    #[error("Invalid media: {0}")]
+    InvalidMedia(MediaError),
+}
+
+/// A media rejected before upload, see `api::media_validation::validate_media`.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum MediaError {
+    #[error("the media is empty")]
+    Empty,
+    #[error("the media is {detected}, not {declared}")]
+    TypeMismatch {
+        declared: String,
+        detected: &'static str,
+    },
+    #[error("{media_type} can't be uploaded as {category}")]
+    UnsupportedType {
+        media_type: String,
+        category: MediaCategory,
+    },
+    #[error("{size} bytes is over the {limit} bytes limit of {category}")]
+    TooLarge {
+        size: u64,
+        limit: u64,
+        category: MediaCategory,
+    },
+    #[error("{width}x{height} is over the {max_width}x{max_height} limit of {category}")]
+    DimensionsTooLarge {
+        width: u32,
+        height: u32,
+        max_width: u32,
+        max_height: u32,
+        category: MediaCategory,
+    },
+    #[error("{width}x{height} is under the {min_width}x{min_height} minimum of {category}")]
+    DimensionsTooSmall {
+        width: u32,
+        height: u32,
+        min_width: u32,
+        min_height: u32,
+        category: MediaCategory,
+    },
+    /// Returned by `TweetyClient::upload_file`, which only sends images.
+    #[error("{media_type} has to be uploaded as {category} with `TweetyClient::upload_media`")]
+    ChunkedUploadRequired {
+        media_type: String,
+        category: MediaCategory,
+    },
+}
+
+impl TweetyError {
+    /// The structured API error, if the request reached Twitter and was rejected.
+    pub fn api_error(&self) -> Option<&TwitterError> {
+        match self {
+            TweetyError::ApiError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<MediaError> for TweetyError {
+    fn from(err: MediaError) -> TweetyError {
+        TweetyError::InvalidMedia(err)
+    }
 }
 
 impl From<reqwest::Error> for TweetyError {
@@ -26,3 +106,212 @@ impl From<reqwest::Error> for TweetyError {
         TweetyError::RequestError(err)
     }
 }
+
+/// An error response from the API.
+///
+/// v2 endpoints answer with an `application/problem+json` body
+/// (`type`, `title`, `detail`, `status`), v1.1 endpoints with an `errors` array.
+/// Both are parsed into this struct together with the HTTP status and the
+/// rate-limit headers of the response.
+/// [Docs](https://developer.x.com/en/support/x-api/error-troubleshooting)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TwitterError {
+    /// HTTP status code of the response.
+    #[serde(default)]
+    pub status: u16,
+    /// Problem type URI, e.g. `https://api.twitter.com/2/problems/resource-not-found`.
+    #[serde(rename = "type")]
+    pub error_type: Option<String>,
+    pub title: Option<String>,
+    pub detail: Option<String>,
+    /// Per-field or per-resource errors.
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
+    /// Rate-limit headers sent along with the error.
+    #[serde(skip)]
+    pub rate_limit: Option<RateLimit>,
+}
+
+/// One entry of the `errors` array of a response.
+///
+/// Besides error responses, v2 lookups answer HTTP 200 with an `errors` array when
+/// only some of the requested resources could be returned (deleted, suspended,
+/// protected...). Those partial errors are kept in the `errors` field of the typed
+/// responses, next to the resources in `data`.
+/// [Docs](https://developer.x.com/en/support/x-api/error-troubleshooting#partial-errors)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ErrorDetail {
+    /// v1.1 error code, e.g. `187` for a duplicate status.
+    pub code: Option<i64>,
+    pub message: Option<String>,
+    #[serde(rename = "type")]
+    pub error_type: Option<String>,
+    pub title: Option<String>,
+    pub detail: Option<String>,
+    pub parameter: Option<String>,
+    pub parameters: Option<Value>,
+    pub value: Option<Value>,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<String>,
+}
+
+impl ErrorDetail {
+    pub fn problem_type(&self) -> ProblemType {
+        self.error_type
+            .as_deref()
+            .map(ProblemType::from_uri)
+            .unwrap_or(ProblemType::Other)
+    }
+}
+
+/// The v2 problem types, the last segment of the `type` URI of an error.
+/// [Docs](https://developer.x.com/en/support/x-api/error-troubleshooting#error-types)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProblemType {
+    /// The resource does not exist, e.g. a deleted Tweet.
+    ResourceNotFound,
+    /// The resource exists but cannot be returned, e.g. a suspended user.
+    ResourceUnavailable,
+    /// The resource is not visible to the authenticated user, e.g. a protected account.
+    NotAuthorizedForResource,
+    /// The resource cannot be returned by this endpoint.
+    DisallowedResource,
+    /// A parameter is missing or invalid.
+    InvalidRequest,
+    /// The client app is not allowed to use this endpoint.
+    ClientForbidden,
+    /// The endpoint does not support the authentication method used.
+    UnsupportedAuthentication,
+    /// The monthly usage cap of the project was reached.
+    UsageCapped,
+    Other,
+}
+
+impl ProblemType {
+    /// Parses a problem type URI, e.g. `https://api.twitter.com/2/problems/resource-not-found`.
+    pub fn from_uri(uri: &str) -> Self {
+        match uri.rsplit('/').next().unwrap_or_default() {
+            "resource-not-found" => ProblemType::ResourceNotFound,
+            "resource-unavailable" => ProblemType::ResourceUnavailable,
+            "not-authorized-for-resource" => ProblemType::NotAuthorizedForResource,
+            "disallowed-resource" => ProblemType::DisallowedResource,
+            "invalid-request" => ProblemType::InvalidRequest,
+            "client-forbidden" => ProblemType::ClientForbidden,
+            "unsupported-authentication" => ProblemType::UnsupportedAuthentication,
+            "usage-capped" => ProblemType::UsageCapped,
+            _ => ProblemType::Other,
+        }
+    }
+}
+
+/// Broad classes of API errors worth handling separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    /// The Tweet or DM has the same content as a recent one.
+    DuplicateContent,
+    /// Missing, invalid or insufficient credentials (HTTP 401/403).
+    NotAuthorized,
+    /// Too many requests in the current window (HTTP 429).
+    RateLimited,
+    /// The resource does not exist or is no longer available (HTTP 404).
+    NotFound,
+    Other,
+}
+
+const DUPLICATE_STATUS_CODE: i64 = 187;
+
+impl TwitterError {
+    /// Builds the error from a non-success response body.
+    /// Bodies that are not JSON (e.g. an HTML 503 page) end up in `detail`.
+    pub(crate) fn from_response(status: u16, body: &str, rate_limit: Option<RateLimit>) -> Self {
+        let mut error =
+            serde_json::from_str::<TwitterError>(body).unwrap_or_else(|_| TwitterError {
+                detail: (!body.trim().is_empty()).then(|| body.to_string()),
+                ..Default::default()
+            });
+        error.status = status;
+        error.rate_limit = rate_limit;
+        error
+    }
+
+    /// The problem type of the error, or of its first `errors` entry when
+    /// the response has no top-level `type`.
+    pub fn problem_type(&self) -> ProblemType {
+        match &self.error_type {
+            Some(uri) => ProblemType::from_uri(uri),
+            None => self
+                .errors
+                .first()
+                .map(ErrorDetail::problem_type)
+                .unwrap_or(ProblemType::Other),
+        }
+    }
+
+    pub fn kind(&self) -> ApiErrorKind {
+        let mentions_duplicate = |text: &Option<String>| {
+            text.as_deref()
+                .is_some_and(|text| text.to_lowercase().contains("duplicate"))
+        };
+        let problem_type = self.problem_type();
+
+        if mentions_duplicate(&self.detail)
+            || self.errors.iter().any(|err| {
+                err.code == Some(DUPLICATE_STATUS_CODE) || mentions_duplicate(&err.message)
+            })
+        {
+            ApiErrorKind::DuplicateContent
+        } else if self.status == 429 || problem_type == ProblemType::UsageCapped {
+            ApiErrorKind::RateLimited
+        } else if self.status == 404 || problem_type == ProblemType::ResourceNotFound {
+            ApiErrorKind::NotFound
+        } else if self.status == 401
+            || self.status == 403
+            || problem_type == ProblemType::NotAuthorizedForResource
+            || problem_type == ProblemType::UnsupportedAuthentication
+        {
+            ApiErrorKind::NotAuthorized
+        } else {
+            ApiErrorKind::Other
+        }
+    }
+
+    pub fn is_duplicate_content(&self) -> bool {
+        self.kind() == ApiErrorKind::DuplicateContent
+    }
+
+    pub fn is_not_authorized(&self) -> bool {
+        self.kind() == ApiErrorKind::NotAuthorized
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.kind() == ApiErrorKind::RateLimited
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.kind() == ApiErrorKind::NotFound
+    }
+}
+
+impl fmt::Display for TwitterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HTTP {}", self.status)?;
+        if let Some(title) = &self.title {
+            write!(f, " {}", title)?;
+        }
+        if let Some(detail) = &self.detail {
+            write!(f, ": {}", detail)?;
+        }
+        for err in &self.errors {
+            if let Some(message) = err.message.as_ref().or(err.detail.as_ref()) {
+                write!(f, "; {}", message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<TwitterError> for TweetyError {
+    fn from(err: TwitterError) -> TweetyError {
+        TweetyError::ApiError(Box::new(err))
+    }
+}
@@ -0,0 +1,359 @@
+//! Field and expansion selection shared by every endpoint.
+//!
+//! The v2 endpoints only return a few default fields per object; everything else has to be
+//! requested through `expansions` and the `<object>.fields` query parameters. [`Fields`] holds
+//! that selection and encodes it into a percent-escaped query string:
+//!
+//! ```rust
+//! use tweety_rs::api::fields::{Expansion, Fields, TweetField, UserField};
+//!
+//! let fields = Fields::new()
+//!     .expansions([Expansion::AuthorId])
+//!     .tweet_fields([TweetField::CreatedAt, TweetField::PublicMetrics])
+//!     .user_fields([UserField::Username]);
+//!
+//! assert_eq!(
+//!     fields.to_query_string(),
+//!     "expansions=author_id&tweet.fields=created_at,public_metrics&user.fields=username"
+//! );
+//! ```
+//!
+//! [Docs](https://developer.x.com/en/docs/x-api/fields)
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use url::form_urlencoded::byte_serialize;
+
+/// Declares a query parameter enum whose variants map to the strings the API expects.
+macro_rules! query_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($variant:ident => $value:literal,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+        pub enum $name {
+            $(
+                #[serde(rename = $value)]
+                $variant,
+            )+
+        }
+
+        impl $name {
+            /// The value sent to the API.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $value,)+
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+pub(crate) use query_enum;
+
+query_enum! {
+    /// Values of the `expansions` parameter.
+    /// [Docs](https://developer.x.com/en/docs/x-api/expansions)
+    pub enum Expansion {
+        AttachmentsPollIds => "attachments.poll_ids",
+        AttachmentsMediaKeys => "attachments.media_keys",
+        AuthorId => "author_id",
+        EditHistoryTweetIds => "edit_history_tweet_ids",
+        EntitiesMentionsUsername => "entities.mentions.username",
+        GeoPlaceId => "geo.place_id",
+        InReplyToUserId => "in_reply_to_user_id",
+        ReferencedTweetsId => "referenced_tweets.id",
+        ReferencedTweetsIdAuthorId => "referenced_tweets.id.author_id",
+        PinnedTweetId => "pinned_tweet_id",
+        SenderId => "sender_id",
+        ParticipantIds => "participant_ids",
+    }
+}
+
+query_enum! {
+    /// Values of the `tweet.fields` parameter.
+    pub enum TweetField {
+        Attachments => "attachments",
+        AuthorId => "author_id",
+        ContextAnnotations => "context_annotations",
+        ConversationId => "conversation_id",
+        CreatedAt => "created_at",
+        EditControls => "edit_controls",
+        EditHistoryTweetIds => "edit_history_tweet_ids",
+        Entities => "entities",
+        Geo => "geo",
+        Id => "id",
+        InReplyToUserId => "in_reply_to_user_id",
+        Lang => "lang",
+        NonPublicMetrics => "non_public_metrics",
+        NoteTweet => "note_tweet",
+        PublicMetrics => "public_metrics",
+        OrganicMetrics => "organic_metrics",
+        PromotedMetrics => "promoted_metrics",
+        PossiblySensitive => "possibly_sensitive",
+        ReferencedTweets => "referenced_tweets",
+        ReplySettings => "reply_settings",
+        Source => "source",
+        Text => "text",
+        Withheld => "withheld",
+    }
+}
+
+query_enum! {
+    /// Values of the `user.fields` parameter.
+    pub enum UserField {
+        CreatedAt => "created_at",
+        Description => "description",
+        Entities => "entities",
+        Id => "id",
+        Location => "location",
+        MostRecentTweetId => "most_recent_tweet_id",
+        Name => "name",
+        PinnedTweetId => "pinned_tweet_id",
+        ProfileImageUrl => "profile_image_url",
+        Protected => "protected",
+        PublicMetrics => "public_metrics",
+        Url => "url",
+        Username => "username",
+        Verified => "verified",
+        VerifiedType => "verified_type",
+        Withheld => "withheld",
+    }
+}
+
+query_enum! {
+    /// Values of the `media.fields` parameter.
+    pub enum MediaField {
+        DurationMs => "duration_ms",
+        Height => "height",
+        MediaKey => "media_key",
+        PreviewImageUrl => "preview_image_url",
+        Type => "type",
+        Url => "url",
+        Width => "width",
+        PublicMetrics => "public_metrics",
+        NonPublicMetrics => "non_public_metrics",
+        OrganicMetrics => "organic_metrics",
+        PromotedMetrics => "promoted_metrics",
+        AltText => "alt_text",
+        Variants => "variants",
+    }
+}
+
+query_enum! {
+    /// Values of the `place.fields` parameter.
+    pub enum PlaceField {
+        ContainedWithin => "contained_within",
+        Country => "country",
+        CountryCode => "country_code",
+        FullName => "full_name",
+        Geo => "geo",
+        Id => "id",
+        Name => "name",
+        PlaceType => "place_type",
+    }
+}
+
+query_enum! {
+    /// Values of the `poll.fields` parameter.
+    pub enum PollField {
+        DurationMinutes => "duration_minutes",
+        EndDatetime => "end_datetime",
+        Id => "id",
+        Options => "options",
+        VotingStatus => "voting_status",
+    }
+}
+
+query_enum! {
+    /// Values of the `dm_event.fields` parameter.
+    pub enum DmEventField {
+        Id => "id",
+        Text => "text",
+        EventType => "event_type",
+        CreatedAt => "created_at",
+        DmConversationId => "dm_conversation_id",
+        SenderId => "sender_id",
+        ParticipantIds => "participant_ids",
+        ReferencedTweets => "referenced_tweets",
+        Attachments => "attachments",
+    }
+}
+
+/// The expansions and fields to request. Empty lists are left out of the query.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fields {
+    pub expansions: Vec<Expansion>,
+    pub tweet_fields: Vec<TweetField>,
+    pub user_fields: Vec<UserField>,
+    pub media_fields: Vec<MediaField>,
+    pub place_fields: Vec<PlaceField>,
+    pub poll_fields: Vec<PollField>,
+    pub dm_event_fields: Vec<DmEventField>,
+}
+
+impl Fields {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn expansions(mut self, values: impl IntoIterator<Item = Expansion>) -> Self {
+        extend_unique(&mut self.expansions, values);
+        self
+    }
+
+    pub fn tweet_fields(mut self, values: impl IntoIterator<Item = TweetField>) -> Self {
+        extend_unique(&mut self.tweet_fields, values);
+        self
+    }
+
+    pub fn user_fields(mut self, values: impl IntoIterator<Item = UserField>) -> Self {
+        extend_unique(&mut self.user_fields, values);
+        self
+    }
+
+    pub fn media_fields(mut self, values: impl IntoIterator<Item = MediaField>) -> Self {
+        extend_unique(&mut self.media_fields, values);
+        self
+    }
+
+    pub fn place_fields(mut self, values: impl IntoIterator<Item = PlaceField>) -> Self {
+        extend_unique(&mut self.place_fields, values);
+        self
+    }
+
+    pub fn poll_fields(mut self, values: impl IntoIterator<Item = PollField>) -> Self {
+        extend_unique(&mut self.poll_fields, values);
+        self
+    }
+
+    pub fn dm_event_fields(mut self, values: impl IntoIterator<Item = DmEventField>) -> Self {
+        extend_unique(&mut self.dm_event_fields, values);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expansions.is_empty()
+            && self.tweet_fields.is_empty()
+            && self.user_fields.is_empty()
+            && self.media_fields.is_empty()
+            && self.place_fields.is_empty()
+            && self.poll_fields.is_empty()
+            && self.dm_event_fields.is_empty()
+    }
+
+    /// Encodes the selection, e.g. `expansions=author_id&tweet.fields=created_at,lang`.
+    pub fn to_query_string(&self) -> String {
+        let mut query = QueryString::new();
+        self.append_to(&mut query);
+        query.to_string()
+    }
+
+    pub(crate) fn append_to(&self, query: &mut QueryString) {
+        query.push_list("expansions", self.expansions.iter().map(Expansion::as_str));
+        query.push_list(
+            "dm_event.fields",
+            self.dm_event_fields.iter().map(DmEventField::as_str),
+        );
+        query.push_list(
+            "media.fields",
+            self.media_fields.iter().map(MediaField::as_str),
+        );
+        query.push_list(
+            "place.fields",
+            self.place_fields.iter().map(PlaceField::as_str),
+        );
+        query.push_list(
+            "poll.fields",
+            self.poll_fields.iter().map(PollField::as_str),
+        );
+        query.push_list(
+            "tweet.fields",
+            self.tweet_fields.iter().map(TweetField::as_str),
+        );
+        query.push_list(
+            "user.fields",
+            self.user_fields.iter().map(UserField::as_str),
+        );
+    }
+}
+
+fn extend_unique<T: PartialEq>(list: &mut Vec<T>, values: impl IntoIterator<Item = T>) {
+    for value in values {
+        if !list.contains(&value) {
+            list.push(value);
+        }
+    }
+}
+
+/// The query string encoder used by every endpoint. Keys and values are percent-escaped,
+/// list values are joined with unescaped commas.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct QueryString {
+    pairs: Vec<String>,
+}
+
+impl QueryString {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn push(&mut self, key: &str, value: impl fmt::Display) -> &mut Self {
+        self.pairs
+            .push(format!("{}={}", escape(key), escape(&value.to_string())));
+        self
+    }
+
+    pub(crate) fn push_opt<T: fmt::Display>(&mut self, key: &str, value: Option<T>) -> &mut Self {
+        if let Some(value) = value {
+            self.push(key, value);
+        }
+        self
+    }
+
+    /// Pushes a comma separated list, skipped when there are no values.
+    pub(crate) fn push_list<'a>(
+        &mut self,
+        key: &str,
+        values: impl IntoIterator<Item = &'a str>,
+    ) -> &mut Self {
+        let values = values.into_iter().map(escape).collect::<Vec<_>>();
+        if !values.is_empty() {
+            self.pairs
+                .push(format!("{}={}", escape(key), values.join(",")));
+        }
+        self
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Appends the query to `url`, which must not have a query yet.
+    pub(crate) fn append_to_url(&self, url: &str) -> String {
+        if self.is_empty() {
+            url.to_string()
+        } else {
+            format!("{}?{}", url, self)
+        }
+    }
+}
+
+impl fmt::Display for QueryString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pairs.join("&"))
+    }
+}
+
+fn escape(value: &str) -> String {
+    byte_serialize(value.as_bytes()).collect()
+}
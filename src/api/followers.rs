@@ -1,25 +1,31 @@
-use super::{error::TweetyError, user::UserQueryParams};
+use super::error::{ErrorDetail, TweetyError};
+use super::user::UserQueryParams;
 use crate::api::client::TweetyClient;
+use crate::api::pagination::{Paginator, TokenParam};
+use crate::types::hydrated::Hydrated;
+use crate::types::id::UserId;
+use crate::types::response::{Includes, Meta};
+use crate::types::user::User;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UserFollowersResponse {
-    pub data: Vec<Follower>,
-    pub meta: MetaData,
+    #[serde(default)]
+    pub data: Vec<User>,
+    pub includes: Option<Includes>,
+    pub meta: Meta,
+    /// Resources that could not be returned, see [`ErrorDetail`].
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Follower {
-    pub id: String,
-    pub name: String,
-    pub username: String,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct MetaData {
-    pub result_count: u32,
-    pub next_token: String,
+impl UserFollowersResponse {
+    /// Indexes `includes` to follow the pinned Tweet of every user.
+    /// See [`crate::types::hydrated`].
+    pub fn hydrate(self) -> Hydrated<User> {
+        Hydrated::new(self.data, self.includes, Some(self.meta)).with_errors(self.errors)
+    }
 }
 
 impl TweetyClient {
@@ -27,21 +33,36 @@ impl TweetyClient {
     /// [Docs](https://developer.x.com/en/docs/x-api/users/follows/api-reference/get-users-id-followers)
     pub async fn get_user_followers(
         &self,
-        user_id: &str,
+        user_id: UserId,
         params: Option<UserQueryParams>,
     ) -> Result<UserFollowersResponse, TweetyError> {
-        let mut url = format!("https://api.x.com/2/users/{}/followers?", user_id);
-
-        if let Some(param_str) = params {
-            let query_string = param_str.construct_query_string();
-            url.push_str(&query_string);
-        }
+        let url = self.user_followers_url(user_id, params);
         match self.send_request::<()>(&url, Method::GET, None).await {
             Ok(value) => match serde_json::from_value::<UserFollowersResponse>(value) {
                 Ok(data) => Ok(data),
                 Err(err) => Err(TweetyError::JsonParseError(err.to_string())),
             },
-            Err(err) => Err(TweetyError::ApiError(err.to_string())),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Pages through all the followers of the specified user ID.
+    /// See [`crate::api::pagination`].
+    pub fn paginate_user_followers(
+        &self,
+        user_id: UserId,
+        params: Option<UserQueryParams>,
+    ) -> Paginator<'_, User> {
+        let url = self.user_followers_url(user_id, params);
+        Paginator::new(self, &url, TokenParam::PaginationToken)
+    }
+
+    fn user_followers_url(&self, user_id: UserId, params: Option<UserQueryParams>) -> String {
+        let url = format!("{}/2/users/{}/followers", self.api_base_url, user_id);
+
+        match params {
+            Some(params) if !params.is_empty() => format!("{}?{}", url, params.to_query_string()),
+            _ => url,
         }
     }
 }
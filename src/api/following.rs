@@ -1,12 +1,19 @@
 use super::user::UserQueryParams;
 use crate::api::client::TweetyClient;
-use crate::api::error::TweetyError;
+use crate::api::error::{ErrorDetail, TweetyError};
+use crate::api::pagination::{Paginator, TokenParam};
+use crate::types::hydrated::Hydrated;
+use crate::types::id::UserId;
+use crate::types::response::{Includes, Meta};
+use crate::types::user::User;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FollowResponse {
     pub data: FollowData,
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -17,12 +24,14 @@ pub struct FollowData {
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct FollowBody {
-    pub target_user_id: String,
+    pub target_user_id: UserId,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UnfollowResponse {
     pub data: UnfollowData,
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -30,58 +39,23 @@ pub struct UnfollowData {
     pub following: bool,
 }
 
-// USER FOLLOWING STRUCT
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UserFollowingResponse {
+    #[serde(default)]
     pub data: Vec<User>,
-    pub includes: Includes,
+    pub includes: Option<Includes>,
+    pub meta: Option<Meta>,
+    /// Resources that could not be returned, see [`ErrorDetail`].
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
-pub struct User {
-    pub pinned_tweet_id: Option<String>,
-    pub id: String,
-    pub username: String,
-    pub name: String,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Includes {
-    pub tweets: Vec<Tweet>,
-    pub meta: MetaData,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Tweet {
-    pub context_annotations: Option<Vec<ContextAnnotation>>,
-    pub id: String,
-    pub text: String,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct ContextAnnotation {
-    pub domain: Domain,
-    pub entity: Entity,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Domain {
-    pub id: String,
-    pub name: String,
-    pub description: Option<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Entity {
-    pub id: String,
-    pub name: String,
-    pub description: Option<String>,
-}
-
-#[derive(Serialize, Deserialize, Debug)]
-pub struct MetaData {
-    pub result_count: u32,
-    pub next_token: String,
+impl UserFollowingResponse {
+    /// Indexes `includes` to follow the pinned Tweet of every user.
+    /// See [`crate::types::hydrated`].
+    pub fn hydrate(self) -> Hydrated<User> {
+        Hydrated::new(self.data, self.includes, self.meta).with_errors(self.errors)
+    }
 }
 
 impl TweetyClient {
@@ -90,21 +64,19 @@ impl TweetyClient {
     /// [Docs](https://developer.x.com/en/docs/x-api/users/follows/api-reference/post-users-source_user_id-following)
     pub async fn follow_user(
         &self,
-        user_id: &str,
-        target_user_id: &str,
+        user_id: UserId,
+        target_user_id: UserId,
     ) -> Result<FollowResponse, TweetyError> {
-        let url = format!("https://api.x.com/2/users/:{}/following", user_id);
+        let url = format!("{}/2/users/{}/following", self.api_base_url, user_id);
 
-        let json_body = FollowBody {
-            target_user_id: target_user_id.to_string(),
-        };
+        let json_body = FollowBody { target_user_id };
 
         match self.send_request(&url, Method::POST, Some(json_body)).await {
             Ok(value) => match serde_json::from_value::<FollowResponse>(value) {
                 Ok(data) => Ok(data),
                 Err(err) => Err(TweetyError::JsonParseError(err.to_string())),
             },
-            Err(err) => Err(TweetyError::ApiError(err.to_string())),
+            Err(err) => Err(err),
         }
     }
     /// Allows a user ID to unfollow another user.
@@ -113,12 +85,12 @@ impl TweetyClient {
     /// [Docs](https://developer.x.com/en/docs/x-api/users/follows/api-reference/delete-users-source_id-following)
     pub async fn unfollow_user(
         &self,
-        source_userid: &str,
-        target_userid: &str,
+        source_userid: UserId,
+        target_userid: UserId,
     ) -> Result<UnfollowResponse, TweetyError> {
         let url = format!(
-            "https://api.x.com/2/users/:{}/following/:{}",
-            source_userid, target_userid
+            "{}/2/users/{}/following/{}",
+            self.api_base_url, source_userid, target_userid
         );
 
         match self.send_request::<()>(&url, Method::DELETE, None).await {
@@ -126,7 +98,7 @@ impl TweetyClient {
                 Ok(data) => Ok(data),
                 Err(err) => Err(TweetyError::JsonParseError(err.to_string())),
             },
-            Err(err) => Err(TweetyError::ApiError(err.to_string())),
+            Err(err) => Err(err),
         }
     }
     /// GET /2/users/:id/following
@@ -134,22 +106,37 @@ impl TweetyClient {
     /// [Docs](https://developer.x.com/en/docs/x-api/users/follows/api-reference/get-users-id-following#tab0)
     pub async fn get_users_following(
         &self,
-        user_id: &str,
+        user_id: UserId,
         query: Option<UserQueryParams>,
     ) -> Result<UserFollowingResponse, TweetyError> {
-        let mut base_url = format!("https://api.x.com/2/users/{}/following", user_id);
-
-        if let Some(query) = query {
-            let query_string = query.construct_query_string();
-            base_url.push_str(&query_string);
-        }
+        let base_url = self.users_following_url(user_id, query);
 
         match self.send_request::<()>(&base_url, Method::GET, None).await {
             Ok(value) => match serde_json::from_value::<UserFollowingResponse>(value) {
                 Ok(data) => Ok(data),
                 Err(err) => Err(TweetyError::JsonParseError(err.to_string())),
             },
-            Err(err) => Err(TweetyError::ApiError(err.to_string())),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Pages through all the users the specified user ID is following.
+    /// See [`crate::api::pagination`].
+    pub fn paginate_users_following(
+        &self,
+        user_id: UserId,
+        query: Option<UserQueryParams>,
+    ) -> Paginator<'_, User> {
+        let url = self.users_following_url(user_id, query);
+        Paginator::new(self, &url, TokenParam::PaginationToken)
+    }
+
+    fn users_following_url(&self, user_id: UserId, query: Option<UserQueryParams>) -> String {
+        let base_url = format!("{}/2/users/{}/following", self.api_base_url, user_id);
+
+        match query {
+            Some(query) if !query.is_empty() => format!("{}?{}", base_url, query.to_query_string()),
+            _ => base_url,
         }
     }
 }
@@ -1,24 +1,38 @@
 use crate::api::client::TweetyClient;
 use crate::api::error::TweetyError;
+use crate::types::id::TweetId;
+use crate::types::response::Response;
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HideTweet {
     hidden: bool,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HideReplyResponse {
+    pub data: HideReplyData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HideReplyData {
+    /// Indicates if the Tweet was successfully hidden or unhidden.
+    pub hidden: bool,
+}
 /// REFERENCE LINK
 /// Hide replies
 /// PUT /2/tweets/:id/hidden
 /// Hides or unhides a reply to a Tweet.
 /// Endpoint URL
 /// https://api.x.com/2/tweets/:id/hidden
-/// hidden	boolean	Indicates if the Tweet was successfully hidden or unhidden.
 impl TweetyClient {
-    pub async fn hide_tweet(self, tweet_id: &str) -> Result<Value, TweetyError> {
-        let url = format!("https://api.x.com/2/tweets/{}/hidden", tweet_id);
+    pub async fn hide_tweet(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<HideReplyResponse>, TweetyError> {
+        let url = format!("{}/2/tweets/{}/hidden", self.api_base_url, tweet_id);
         let json_body = HideTweet { hidden: true };
-        self.send_request(&url, Method::PUT, Some(json_body)).await
+        self.request(&url, Method::PUT, Some(json_body)).await
     }
 }
@@ -1,50 +1,96 @@
 use crate::api::client::TweetyClient;
 use crate::api::error::TweetyError;
+use crate::api::pagination::{Paginator, TokenParam};
+use crate::api::tweet::TargetTweet;
+use crate::types::id::{ParseIdError, TweetId, UserId};
+use crate::types::response::Response;
+use crate::types::tweet::{Tweet, TweetsResponse};
+use crate::types::user::ApiResponse;
 use reqwest::Method;
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LikeResponse {
+    pub data: LikeData,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LikeData {
+    pub liked: bool,
+}
 
 impl TweetyClient {
-    /// DELETE https://api.x.com/2/tweets/:id/likes/:tweet_id
+    /// DELETE https://api.x.com/2/users/:id/likes/:tweet_id
     /// (unlike a Post)
     /// https://developer.x.com/en/docs/x-api/tweets/likes/migrate/manage-likes-standard-to-twitter-api-v2
-    pub async fn unlike_tweet(&self, user_id: u64, tweet_id: u64) -> Result<Value, TweetyError> {
+    pub async fn unlike_tweet(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<LikeResponse>, TweetyError> {
         let url = format!(
-            "https://api.x.com/2/tweets/:{}/likes/:{}",
-            user_id, tweet_id
+            "{}/2/users/{}/likes/{}",
+            self.api_base_url, user_id, tweet_id
         );
 
-        self.send_request::<()>(&url, Method::DELETE, None).await
+        self.request::<_, ()>(&url, Method::DELETE, None).await
     }
     /// Users who have liked a Post
     /// https://developer.x.com/en/docs/x-api/tweets/likes/api-reference
-    pub async fn get_users_who_liked_a_post(&self, post_id: &str) -> Result<Value, TweetyError> {
-        let url = format!("https://api.x.com/2/tweets/{}/liking_users", post_id);
+    pub async fn get_users_who_liked_a_post(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        let url = format!("{}/2/tweets/{}/liking_users", self.api_base_url, tweet_id);
 
-        self.send_request::<()>(&url, Method::GET, None).await
+        self.request::<_, ()>(&url, Method::GET, None).await
     }
 
     /// Posts liked by a user
     /// https://developer.x.com/en/docs/x-api/tweets/likes/api-reference
-    pub async fn get_posts_liked_by_a_user(&self, user_id: &str) -> Result<Value, TweetyError> {
-        let url = format!("https://api.x.com/2/users/{}/liked_tweets", user_id);
+    pub async fn get_posts_liked_by_a_user(
+        &self,
+        user_id: UserId,
+    ) -> Result<Response<TweetsResponse>, TweetyError> {
+        let url = format!("{}/2/users/{}/liked_tweets", self.api_base_url, user_id);
+
+        self.request::<_, ()>(&url, Method::GET, None).await
+    }
+
+    /// Pages through all the Posts liked by a user.
+    /// See [`crate::api::pagination`].
+    pub fn paginate_posts_liked_by_a_user(&self, user_id: UserId) -> Paginator<'_, Tweet> {
+        let url = format!("{}/2/users/{}/liked_tweets", self.api_base_url, user_id);
 
-        self.send_request::<()>(&url, Method::GET, None).await
+        Paginator::new(self, &url, TokenParam::PaginationToken)
     }
-    /// MANAGE LIKES
+
+    // MANAGE LIKES
 
     /// Allows a user ID to like a Post
-    /// https://developer.x.com/en/docs/x-api/tweets/likes/api-reference
-    pub async fn like_a_post(&self, user_id: &str) -> Result<Value, TweetyError> {
-        let url = format!("https://api.x.com/2/users/{}/likes", user_id);
+    /// [Docs](https://developer.x.com/en/docs/x-api/tweets/likes/api-reference/post-users-id-likes)
+    pub async fn like_a_post(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<LikeResponse>, TweetyError> {
+        let url = format!("{}/2/users/{}/likes", self.api_base_url, user_id);
 
-        self.send_request::<()>(&url, Method::POST, None).await
+        self.request(&url, Method::POST, Some(TargetTweet { tweet_id }))
+            .await
     }
 
     /// Allows a user ID to unlike a Post
     /// https://developer.x.com/en/docs/x-api/tweets/likes/api-reference
-    pub async fn unlike_a_post(&self, user_id: &str, tweet_id: &str) -> Result<Value, TweetyError> {
-        let url = format!("https://api.x.com/2/users/{}/likes/{}", user_id, tweet_id);
-
-        self.send_request::<()>(&url, Method::DELETE, None).await
+    #[deprecated(note = "use `unlike_tweet`, which takes typed ids")]
+    pub async fn unlike_a_post(
+        &self,
+        user_id: &str,
+        tweet_id: &str,
+    ) -> Result<Response<LikeResponse>, TweetyError> {
+        let invalid = |err: ParseIdError| TweetyError::InvalidParameter(err.to_string());
+        let user_id = user_id.parse().map_err(invalid)?;
+        let tweet_id = tweet_id.parse().map_err(invalid)?;
+        self.unlike_tweet(user_id, tweet_id).await
     }
 }
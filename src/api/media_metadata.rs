@@ -0,0 +1,270 @@
+use crate::api::client::TweetyClient;
+use crate::api::error::TweetyError;
+use crate::api::fields::query_enum;
+use crate::types::id::MediaId;
+use reqwest::Method;
+use serde::Serialize;
+use std::fmt;
+
+/// Alt text of an image or GIF, read by screen readers. Up to 1000 characters.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AltText(String);
+
+impl AltText {
+    /// Longest alt text accepted by the API, in characters.
+    pub const MAX_CHARS: usize = 1000;
+
+    /// Fails with `TweetyError::InvalidParameter` if `text` is blank or longer than
+    /// [`AltText::MAX_CHARS`] characters.
+    pub fn new(text: impl Into<String>) -> Result<Self, TweetyError> {
+        let text = text.into();
+        if text.trim().is_empty() {
+            return Err(TweetyError::InvalidParameter(
+                "alt text is empty".to_string(),
+            ));
+        }
+        let chars = text.chars().count();
+        if chars > Self::MAX_CHARS {
+            return Err(TweetyError::InvalidParameter(format!(
+                "alt text is {} characters long, the limit is {}",
+                chars,
+                Self::MAX_CHARS
+            )));
+        }
+        Ok(AltText(text))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for AltText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+query_enum! {
+    /// Content warnings shown over a media until it is clicked.
+    pub enum SensitiveMediaWarning {
+        AdultContent => "adult_content",
+        GraphicViolence => "graphic_violence",
+        Other => "other",
+    }
+}
+
+/// Parameters of [`TweetyClient::create_media_metadata`]. At least one has to be set.
+#[derive(Debug, Clone, Default)]
+pub struct MediaMetadata {
+    pub alt_text: Option<AltText>,
+    pub sensitive_media_warning: Vec<SensitiveMediaWarning>,
+}
+
+/// BCP 47 language code of subtitles, such as `en` or `es`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct LanguageCode(String);
+
+impl LanguageCode {
+    /// Fails with `TweetyError::InvalidParameter` unless `code` is two or three ASCII letters.
+    pub fn new(code: impl Into<String>) -> Result<Self, TweetyError> {
+        let code = code.into();
+        if !(2..=3).contains(&code.len()) || !code.bytes().all(|byte| byte.is_ascii_alphabetic()) {
+            return Err(TweetyError::InvalidParameter(format!(
+                "{:?} is not a language code such as \"en\"",
+                code
+            )));
+        }
+        Ok(LanguageCode(code))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for LanguageCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A subtitles file attached to a video.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subtitles {
+    /// Media id of the SRT file, uploaded with the `subtitles` media category.
+    pub media_id: MediaId,
+    pub language_code: LanguageCode,
+    /// Name of the language shown in the player, e.g. `English`.
+    pub display_name: String,
+}
+
+#[derive(Serialize)]
+struct MetadataBody<'a> {
+    media_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alt_text: Option<AltTextBody<'a>>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    sensitive_media_warning: &'a [SensitiveMediaWarning],
+}
+
+#[derive(Serialize)]
+struct AltTextBody<'a> {
+    text: &'a str,
+}
+
+#[derive(Serialize)]
+struct SubtitlesBody<'a> {
+    media_id: String,
+    media_category: &'static str,
+    subtitle_info: SubtitleInfo<'a>,
+}
+
+#[derive(Serialize)]
+struct SubtitleInfo<'a> {
+    subtitles: Vec<SubtitleEntry<'a>>,
+}
+
+#[derive(Serialize)]
+struct SubtitleEntry<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    media_id: Option<String>,
+    language_code: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_name: Option<&'a str>,
+}
+
+/// The subtitle endpoints only take Tweet videos, with this spelling.
+const SUBTITLES_MEDIA_CATEGORY: &str = "TweetVideo";
+
+/// REFERENCE LINK
+/// Media metadata and subtitles
+/// POST media/metadata/create.json
+/// POST media/subtitles/create.json
+/// POST media/subtitles/delete.json
+/// Endpoint URL
+/// <https://upload.twitter.com/1.1/media/metadata/create.json>
+impl TweetyClient {
+    /// Sets the alt text and content warnings of an uploaded media, before it is
+    /// attached to a Tweet.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use tweety_rs::api::media_metadata::{AltText, MediaMetadata};
+    /// use tweety_rs::TweetyClient;
+    ///
+    /// # async fn example(client: TweetyClient) -> Result<(), tweety_rs::api::error::TweetyError> {
+    /// let media_id = client.upload_file(Path::new("ferris.png")).await?;
+    /// let metadata = MediaMetadata {
+    ///     alt_text: Some(AltText::new("Ferris the crab waving a claw")?),
+    ///     ..Default::default()
+    /// };
+    /// client.create_media_metadata(media_id, &metadata).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Docs](https://developer.x.com/en/docs/twitter-api/v1/media/upload-media/api-reference/post-media-metadata-create)
+    pub async fn create_media_metadata(
+        &self,
+        media_id: MediaId,
+        metadata: &MediaMetadata,
+    ) -> Result<(), TweetyError> {
+        if metadata.alt_text.is_none() && metadata.sensitive_media_warning.is_empty() {
+            return Err(TweetyError::InvalidParameter(
+                "set the alt text or a sensitive media warning".to_string(),
+            ));
+        }
+
+        let url = format!("{}/1.1/media/metadata/create.json", self.upload_base_url);
+        let body = MetadataBody {
+            media_id: media_id.to_string(),
+            alt_text: metadata.alt_text.as_ref().map(|alt_text| AltTextBody {
+                text: alt_text.as_str(),
+            }),
+            sensitive_media_warning: &metadata.sensitive_media_warning,
+        };
+        self.send_request(&url, Method::POST, Some(body)).await?;
+        Ok(())
+    }
+
+    /// Attaches subtitles to an uploaded video, one file per language.
+    ///
+    /// [Docs](https://developer.x.com/en/docs/twitter-api/v1/media/upload-media/api-reference/post-media-subtitles-create)
+    pub async fn create_media_subtitles(
+        &self,
+        video_id: MediaId,
+        subtitles: &[Subtitles],
+    ) -> Result<(), TweetyError> {
+        if subtitles.is_empty() {
+            return Err(TweetyError::InvalidParameter(
+                "no subtitles to create".to_string(),
+            ));
+        }
+        if let Some(empty) = subtitles
+            .iter()
+            .find(|subtitles| subtitles.display_name.trim().is_empty())
+        {
+            return Err(TweetyError::InvalidParameter(format!(
+                "the {} subtitles have no display name",
+                empty.language_code
+            )));
+        }
+
+        let entries = subtitles
+            .iter()
+            .map(|subtitles| SubtitleEntry {
+                media_id: Some(subtitles.media_id.to_string()),
+                language_code: subtitles.language_code.as_str(),
+                display_name: Some(&subtitles.display_name),
+            })
+            .collect();
+        self.send_subtitles("create", video_id, entries).await
+    }
+
+    /// Removes the subtitles of these languages from a video.
+    ///
+    /// [Docs](https://developer.x.com/en/docs/twitter-api/v1/media/upload-media/api-reference/post-media-subtitles-delete)
+    pub async fn delete_media_subtitles(
+        &self,
+        video_id: MediaId,
+        language_codes: &[LanguageCode],
+    ) -> Result<(), TweetyError> {
+        if language_codes.is_empty() {
+            return Err(TweetyError::InvalidParameter(
+                "no subtitles to delete".to_string(),
+            ));
+        }
+
+        let entries = language_codes
+            .iter()
+            .map(|language_code| SubtitleEntry {
+                media_id: None,
+                language_code: language_code.as_str(),
+                display_name: None,
+            })
+            .collect();
+        self.send_subtitles("delete", video_id, entries).await
+    }
+
+    async fn send_subtitles(
+        &self,
+        command: &str,
+        video_id: MediaId,
+        subtitles: Vec<SubtitleEntry<'_>>,
+    ) -> Result<(), TweetyError> {
+        let url = format!(
+            "{}/1.1/media/subtitles/{}.json",
+            self.upload_base_url, command
+        );
+        let body = SubtitlesBody {
+            media_id: video_id.to_string(),
+            media_category: SUBTITLES_MEDIA_CATEGORY,
+            subtitle_info: SubtitleInfo { subtitles },
+        };
+        self.send_request(&url, Method::POST, Some(body)).await?;
+        Ok(())
+    }
+}
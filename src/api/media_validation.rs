@@ -0,0 +1,243 @@
+//! Media type detection and the upload limits of each media category, checked
+//! before anything is sent.
+//!
+//! The uploads run [`validate_media`] on the first bytes of the media. It can
+//! be skipped with `UploadParams::skip_validation` if the API raises a limit.
+//! [Docs](https://developer.x.com/en/docs/twitter-api/v1/media/upload-media/uploading-media/media-best-practices)
+
+use crate::api::error::MediaError;
+use crate::api::uploads::MediaCategory;
+
+/// How many bytes of a media are enough for [`sniff_media_type`] and [`image_dimensions`].
+pub const HEADER_LEN: usize = 64 * 1024;
+
+const MB: u64 = 1024 * 1024;
+
+const IMAGE_TYPES: &[&str] = &["image/png", "image/jpeg", "image/webp", "image/gif"];
+const GIF_TYPES: &[&str] = &["image/gif"];
+const VIDEO_TYPES: &[&str] = &["video/mp4", "video/quicktime"];
+const SUBTITLES_TYPES: &[&str] = &["application/x-subrip"];
+
+/// What the API accepts for a media category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MediaLimits {
+    pub media_types: &'static [&'static str],
+    pub max_bytes: Option<u64>,
+    /// Width and height, only checked for images and GIFs.
+    pub min_dimensions: Option<(u32, u32)>,
+    pub max_dimensions: Option<(u32, u32)>,
+}
+
+impl MediaLimits {
+    pub fn for_category(category: MediaCategory) -> Self {
+        match category {
+            MediaCategory::TweetImage | MediaCategory::DmImage => MediaLimits {
+                media_types: IMAGE_TYPES,
+                max_bytes: Some(5 * MB),
+                min_dimensions: Some((4, 4)),
+                max_dimensions: Some((8192, 8192)),
+            },
+            MediaCategory::TweetGif | MediaCategory::DmGif => MediaLimits {
+                media_types: GIF_TYPES,
+                max_bytes: Some(15 * MB),
+                min_dimensions: Some((4, 4)),
+                max_dimensions: Some((1280, 1080)),
+            },
+            MediaCategory::TweetVideo | MediaCategory::DmVideo => MediaLimits {
+                media_types: VIDEO_TYPES,
+                max_bytes: Some(512 * MB),
+                min_dimensions: None,
+                max_dimensions: None,
+            },
+            MediaCategory::Subtitles => MediaLimits {
+                media_types: SUBTITLES_TYPES,
+                max_bytes: None,
+                min_dimensions: None,
+                max_dimensions: None,
+            },
+        }
+    }
+}
+
+/// Major brands of the `ftyp` box of an MP4 file.
+const MP4_BRANDS: [&[u8]; 13] = [
+    b"isom", b"iso2", b"iso3", b"iso4", b"iso5", b"iso6", b"mp41", b"mp42", b"avc1", b"M4V ",
+    b"M4VP", b"dash", b"msnv",
+];
+
+/// The MIME type given by the magic bytes at the start of a media:
+/// PNG, JPEG, GIF, WebP, MP4 or QuickTime.
+pub fn sniff_media_type(header: &[u8]) -> Option<&'static str> {
+    if header.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("image/png")
+    } else if header.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if header.starts_with(b"GIF87a") || header.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if header.len() >= 12 && &header[..4] == b"RIFF" && &header[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else if header.len() >= 12 && &header[4..8] == b"ftyp" {
+        // ISO base media files share the `ftyp` box, its major brand tells
+        // MP4 from QuickTime and from HEIC/HEIF/AVIF images.
+        match &header[8..12] {
+            b"qt  " => Some("video/quicktime"),
+            brand if MP4_BRANDS.contains(&brand) => Some("video/mp4"),
+            _ => None,
+        }
+    } else {
+        None
+    }
+}
+
+/// Width and height of a PNG, JPEG, GIF or WebP image, read from its header.
+pub fn image_dimensions(header: &[u8]) -> Option<(u32, u32)> {
+    match sniff_media_type(header)? {
+        "image/png" if &header.get(12..16)? == b"IHDR" => {
+            Some((be32(header, 16)?, be32(header, 20)?))
+        }
+        "image/gif" => Some((le16(header, 6)?.into(), le16(header, 8)?.into())),
+        "image/jpeg" => jpeg_dimensions(header),
+        "image/webp" => webp_dimensions(header),
+        _ => None,
+    }
+}
+
+/// Checks a media against the limits of its category, from its first bytes
+/// (at least [`HEADER_LEN`] of them, or all of them) and its size.
+///
+/// Fails with the first limit it breaks: a detected type other than `media_type`,
+/// a type not accepted by the category, the size, then the dimensions.
+pub fn validate_media(
+    header: &[u8],
+    total_bytes: u64,
+    media_type: &str,
+    category: MediaCategory,
+) -> Result<(), MediaError> {
+    if total_bytes == 0 {
+        return Err(MediaError::Empty);
+    }
+    let media_type = canonical_media_type(media_type);
+    if let Some(detected) = sniff_media_type(header) {
+        if !detected.eq_ignore_ascii_case(&media_type) {
+            return Err(MediaError::TypeMismatch {
+                declared: media_type,
+                detected,
+            });
+        }
+    }
+
+    let limits = MediaLimits::for_category(category);
+    if !limits
+        .media_types
+        .iter()
+        .any(|accepted| accepted.eq_ignore_ascii_case(&media_type))
+    {
+        return Err(MediaError::UnsupportedType {
+            media_type,
+            category,
+        });
+    }
+    if let Some(limit) = limits.max_bytes.filter(|limit| total_bytes > *limit) {
+        return Err(MediaError::TooLarge {
+            size: total_bytes,
+            limit,
+            category,
+        });
+    }
+
+    let Some((width, height)) = image_dimensions(header) else {
+        return Ok(());
+    };
+    if let Some((max_width, max_height)) = limits.max_dimensions {
+        if width > max_width || height > max_height {
+            return Err(MediaError::DimensionsTooLarge {
+                width,
+                height,
+                max_width,
+                max_height,
+                category,
+            });
+        }
+    }
+    if let Some((min_width, min_height)) = limits.min_dimensions {
+        if width < min_width || height < min_height {
+            return Err(MediaError::DimensionsTooSmall {
+                width,
+                height,
+                min_width,
+                min_height,
+                category,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Lowercased, with the common aliases replaced.
+fn canonical_media_type(media_type: &str) -> String {
+    let media_type = media_type.trim().to_ascii_lowercase();
+    match media_type.as_str() {
+        "image/jpg" | "image/pjpeg" => "image/jpeg".to_string(),
+        "text/srt" | "application/srt" => "application/x-subrip".to_string(),
+        _ => media_type,
+    }
+}
+
+/// The size of the first frame: walks the segments up to a start-of-frame marker.
+fn jpeg_dimensions(header: &[u8]) -> Option<(u32, u32)> {
+    let mut offset = 2;
+    loop {
+        if *header.get(offset)? != 0xFF {
+            return None;
+        }
+        let marker = *header.get(offset + 1)?;
+        match marker {
+            0xFF => offset += 1,
+            0x01 | 0xD0..=0xD7 => offset += 2,
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                let height = be16(header, offset + 5)?;
+                let width = be16(header, offset + 7)?;
+                return Some((width.into(), height.into()));
+            }
+            _ => offset += 2 + usize::from(be16(header, offset + 2)?),
+        }
+    }
+}
+
+fn webp_dimensions(header: &[u8]) -> Option<(u32, u32)> {
+    match header.get(12..16)? {
+        b"VP8 " => Some((
+            u32::from(le16(header, 26)? & 0x3FFF),
+            u32::from(le16(header, 28)? & 0x3FFF),
+        )),
+        b"VP8L" => {
+            let bits = u32::from_le_bytes(header.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        b"VP8X" => Some((le24(header, 24)? + 1, le24(header, 27)? + 1)),
+        _ => None,
+    }
+}
+
+fn be16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn be32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn le16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn le24(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 3)?;
+    Some(u32::from(bytes[0]) | u32::from(bytes[1]) << 8 | u32::from(bytes[2]) << 16)
+}
@@ -1,15 +1,28 @@
+pub mod auth;
 pub mod bookmark;
+pub mod builder;
+pub mod cassette;
 pub mod client;
+pub mod credentials;
 pub mod direct_messages;
 pub mod error;
 pub mod favourites;
+pub mod fields;
 pub mod followers;
 pub mod following;
 pub mod hide_replies;
 pub mod like;
+pub mod media_metadata;
+pub mod media_validation;
 pub mod mentions;
+pub mod oauth1;
+pub mod oauth2;
+pub mod pagination;
+pub mod rate_limit;
+pub mod retry;
 pub mod retweets;
 pub mod search;
 pub mod tweet;
+pub mod twitter_api;
 pub mod uploads;
 pub mod user;
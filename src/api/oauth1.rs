@@ -0,0 +1,239 @@
+//! Three-legged OAuth 1.0a flow (a.k.a. "Sign in with X" / PIN-based authorization).
+//!
+//! Obtains an access token and secret for any account that authorizes the app,
+//! not only for the app owner's account shown in the developer portal.
+//! [Docs](https://developer.x.com/en/docs/authentication/oauth-1-0a/obtaining-user-access-tokens)
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use tweety_rs::api::oauth1::OAuth1Client;
+//! use tweety_rs::TweetyClient;
+//!
+//! # async fn example() -> Result<(), tweety_rs::api::error::TweetyError> {
+//! let oauth1 = OAuth1Client::new("your_consumer_key", "your_consumer_key_secret");
+//!
+//! // "oob" selects the PIN-based flow, pass your callback URL otherwise.
+//! let request_token = oauth1.request_token("oob").await?;
+//! println!("Authorize the app at: {}", oauth1.authorize_url(&request_token)?);
+//!
+//! let pin = "1234567"; // read it from the user
+//! let access_token = oauth1.access_token(&request_token, pin).await?;
+//!
+//! let client = TweetyClient::builder()
+//!     .consumer_key("your_consumer_key")
+//!     .consumer_key_secret("your_consumer_key_secret")
+//!     .access_token(access_token.oauth_token.expose())
+//!     .access_token_secret(access_token.oauth_token_secret.expose())
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::client::{RawResponse, DEFAULT_API_BASE_URL};
+use crate::api::credentials::Secret;
+use crate::api::error::TweetyError;
+use reqwest_oauth1::OAuthClientProvider;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Temporary token returned by `POST /oauth/request_token`.
+/// `oauth_token` is not secret, it ends up in the authorization URL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestToken {
+    pub oauth_token: String,
+    pub oauth_token_secret: Secret,
+    pub oauth_callback_confirmed: bool,
+}
+
+/// User access token returned by `POST /oauth/access_token`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessToken {
+    pub oauth_token: Secret,
+    pub oauth_token_secret: Secret,
+    pub user_id: Option<String>,
+    pub screen_name: Option<String>,
+}
+
+/// Response of `POST /1.1/oauth/invalidate_token`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvalidatedAccessToken {
+    pub access_token: Secret,
+}
+
+/// An app's consumer credentials, used to walk users through the OAuth 1.0a flow.
+#[derive(Debug, Clone)]
+pub struct OAuth1Client {
+    consumer_key: Secret,
+    consumer_key_secret: Secret,
+    api_base_url: String,
+    http: reqwest::Client,
+}
+
+impl OAuth1Client {
+    pub fn new(consumer_key: &str, consumer_key_secret: &str) -> Self {
+        OAuth1Client {
+            consumer_key: Secret::new(consumer_key),
+            consumer_key_secret: Secret::new(consumer_key_secret),
+            api_base_url: DEFAULT_API_BASE_URL.to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Defaults to [`DEFAULT_API_BASE_URL`].
+    pub fn api_base_url(mut self, base_url: &str) -> Self {
+        self.api_base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http = client;
+        self
+    }
+
+    /// Step 1: obtains a request token.
+    /// `callback` is the URL the user is redirected to, or `oob` for the PIN-based flow.
+    /// [Docs](https://developer.x.com/en/docs/authentication/api-reference/request_token)
+    pub async fn request_token(&self, callback: &str) -> Result<RequestToken, TweetyError> {
+        let url = format!("{}/oauth/request_token", self.api_base_url);
+        let secrets = reqwest_oauth1::Secrets::new(
+            self.consumer_key.expose(),
+            self.consumer_key_secret.expose(),
+        );
+
+        let request = self
+            .http
+            .clone()
+            .oauth1(secrets)
+            .post(&url)
+            .query(&[("oauth_callback", callback)])
+            .generate_signature()
+            .map_err(|_| TweetyError::AuthError)?;
+
+        let fields = self.send_form(request).await?;
+        Ok(RequestToken {
+            oauth_token: required_field(&fields, "oauth_token")?,
+            oauth_token_secret: required_field(&fields, "oauth_token_secret")?.into(),
+            oauth_callback_confirmed: field(&fields, "oauth_callback_confirmed").as_deref()
+                == Some("true"),
+        })
+    }
+
+    /// Step 2: the page where the user authorizes the app.
+    /// [Docs](https://developer.x.com/en/docs/authentication/api-reference/authorize)
+    /// Fails with `TweetyError::UrlParseError` if the API base URL is malformed.
+    pub fn authorize_url(&self, request_token: &RequestToken) -> Result<Url, TweetyError> {
+        self.user_url("oauth/authorize", request_token)
+    }
+
+    /// Like [`OAuth1Client::authorize_url`], but skips the prompt for users who
+    /// already authorized the app ("Sign in with X").
+    /// [Docs](https://developer.x.com/en/docs/authentication/api-reference/authenticate)
+    pub fn authenticate_url(&self, request_token: &RequestToken) -> Result<Url, TweetyError> {
+        self.user_url("oauth/authenticate", request_token)
+    }
+
+    /// Step 3: exchanges the request token and the `oauth_verifier` (the PIN, or
+    /// the query parameter of the callback) for the user's access token.
+    /// [Docs](https://developer.x.com/en/docs/authentication/api-reference/access_token)
+    pub async fn access_token(
+        &self,
+        request_token: &RequestToken,
+        verifier: &str,
+    ) -> Result<AccessToken, TweetyError> {
+        let url = format!("{}/oauth/access_token", self.api_base_url);
+        let secrets = reqwest_oauth1::Secrets::new(
+            self.consumer_key.expose(),
+            self.consumer_key_secret.expose(),
+        )
+        .token(
+            &request_token.oauth_token,
+            request_token.oauth_token_secret.expose(),
+        );
+
+        let request = self
+            .http
+            .clone()
+            .oauth1(secrets)
+            .post(&url)
+            .query(&[("oauth_verifier", verifier)])
+            .generate_signature()
+            .map_err(|_| TweetyError::AuthError)?;
+
+        let fields = self.send_form(request).await?;
+        Ok(AccessToken {
+            oauth_token: required_field(&fields, "oauth_token")?.into(),
+            oauth_token_secret: required_field(&fields, "oauth_token_secret")?.into(),
+            user_id: field(&fields, "user_id"),
+            screen_name: field(&fields, "screen_name"),
+        })
+    }
+
+    /// Revokes a user's access token.
+    /// [Docs](https://developer.x.com/en/docs/authentication/api-reference/invalidate_access_token)
+    pub async fn invalidate_token(
+        &self,
+        access_token: &str,
+        access_token_secret: &str,
+    ) -> Result<InvalidatedAccessToken, TweetyError> {
+        let url = format!("{}/1.1/oauth/invalidate_token", self.api_base_url);
+        let secrets = reqwest_oauth1::Secrets::new(
+            self.consumer_key.expose(),
+            self.consumer_key_secret.expose(),
+        )
+        .token(access_token, access_token_secret);
+
+        let response = self
+            .http
+            .clone()
+            .oauth1(secrets)
+            .post(&url)
+            .send()
+            .await
+            .map_err(|err| TweetyError::NetworkError(err.to_string()))?;
+
+        let value = RawResponse::read(response).await?.into_result()?;
+        serde_json::from_value::<InvalidatedAccessToken>(value)
+            .map_err(|err| TweetyError::JsonParseError(err.to_string()))
+    }
+
+    fn user_url(&self, path: &str, request_token: &RequestToken) -> Result<Url, TweetyError> {
+        let mut url = Url::parse(&format!("{}/{}", self.api_base_url, path))
+            .map_err(TweetyError::UrlParseError)?;
+        url.query_pairs_mut()
+            .append_pair("oauth_token", &request_token.oauth_token);
+        Ok(url)
+    }
+
+    /// The token endpoints answer with a form-encoded body instead of JSON.
+    async fn send_form(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<Vec<(String, String)>, TweetyError> {
+        let response = request
+            .send()
+            .await
+            .map_err(|err| TweetyError::NetworkError(err.to_string()))?;
+        let response = RawResponse::read(response).await?;
+
+        if !response.status.is_success() {
+            return Err(response.into_result().unwrap_err());
+        }
+
+        Ok(url::form_urlencoded::parse(response.body.as_bytes())
+            .into_owned()
+            .collect())
+    }
+}
+
+fn field(fields: &[(String, String)], name: &str) -> Option<String> {
+    fields
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.clone())
+}
+
+fn required_field(fields: &[(String, String)], name: &str) -> Result<String, TweetyError> {
+    field(fields, name)
+        .ok_or_else(|| TweetyError::OAuthError(format!("`{}` missing from the response", name)))
+}
@@ -0,0 +1,504 @@
+//! OAuth 2.0 Authorization Code Flow with PKCE.
+//!
+//! Lets a user grant the app access to their account, for endpoints that only
+//! accept OAuth 2.0 user context (e.g. direct messages or bookmarks).
+//! [Docs](https://developer.x.com/en/docs/authentication/oauth-2-0/authorization-code)
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use tweety_rs::api::oauth2::{listen_for_code, OAuth2Client, Scope};
+//! use tweety_rs::TweetyClient;
+//!
+//! # async fn example() -> Result<(), tweety_rs::api::error::TweetyError> {
+//! let oauth2 = OAuth2Client::new("your_client_id", "http://127.0.0.1:8976/callback")
+//!     .scopes(vec![Scope::TweetRead, Scope::UsersRead, Scope::DmRead, Scope::OfflineAccess]);
+//!
+//! let request = oauth2.authorization_request()?;
+//! println!("Open this URL in your browser: {}", request.url);
+//!
+//! let code = listen_for_code("127.0.0.1:8976", &request.state).await?;
+//! let token = oauth2.exchange_code(&code, &request.pkce.verifier).await?;
+//!
+//! // The token is refreshed automatically once it expires.
+//! let client = TweetyClient::builder().oauth2(oauth2, token).build()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::client::{RawResponse, DEFAULT_API_BASE_URL};
+use crate::api::credentials::Secret;
+use crate::api::error::TweetyError;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use url::Url;
+
+/// Where users are sent to grant access.
+pub const DEFAULT_AUTHORIZE_URL: &str = "https://x.com/i/oauth2/authorize";
+
+/// Tokens are refreshed this many seconds before they actually expire.
+const EXPIRY_LEEWAY_SECS: u64 = 30;
+
+/// Permissions requested from the user.
+/// [Docs](https://developer.x.com/en/docs/authentication/oauth-2-0/authorization-code)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Scope {
+    #[serde(rename = "tweet.read")]
+    TweetRead,
+    #[serde(rename = "tweet.write")]
+    TweetWrite,
+    #[serde(rename = "tweet.moderate.write")]
+    TweetModerateWrite,
+    #[serde(rename = "users.read")]
+    UsersRead,
+    #[serde(rename = "follows.read")]
+    FollowsRead,
+    #[serde(rename = "follows.write")]
+    FollowsWrite,
+    /// Required to receive a refresh token.
+    #[serde(rename = "offline.access")]
+    OfflineAccess,
+    #[serde(rename = "space.read")]
+    SpaceRead,
+    #[serde(rename = "mute.read")]
+    MuteRead,
+    #[serde(rename = "mute.write")]
+    MuteWrite,
+    #[serde(rename = "like.read")]
+    LikeRead,
+    #[serde(rename = "like.write")]
+    LikeWrite,
+    #[serde(rename = "list.read")]
+    ListRead,
+    #[serde(rename = "list.write")]
+    ListWrite,
+    #[serde(rename = "block.read")]
+    BlockRead,
+    #[serde(rename = "block.write")]
+    BlockWrite,
+    #[serde(rename = "bookmark.read")]
+    BookmarkRead,
+    #[serde(rename = "bookmark.write")]
+    BookmarkWrite,
+    #[serde(rename = "dm.read")]
+    DmRead,
+    #[serde(rename = "dm.write")]
+    DmWrite,
+    #[serde(rename = "media.write")]
+    MediaWrite,
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::TweetRead => "tweet.read",
+            Scope::TweetWrite => "tweet.write",
+            Scope::TweetModerateWrite => "tweet.moderate.write",
+            Scope::UsersRead => "users.read",
+            Scope::FollowsRead => "follows.read",
+            Scope::FollowsWrite => "follows.write",
+            Scope::OfflineAccess => "offline.access",
+            Scope::SpaceRead => "space.read",
+            Scope::MuteRead => "mute.read",
+            Scope::MuteWrite => "mute.write",
+            Scope::LikeRead => "like.read",
+            Scope::LikeWrite => "like.write",
+            Scope::ListRead => "list.read",
+            Scope::ListWrite => "list.write",
+            Scope::BlockRead => "block.read",
+            Scope::BlockWrite => "block.write",
+            Scope::BookmarkRead => "bookmark.read",
+            Scope::BookmarkWrite => "bookmark.write",
+            Scope::DmRead => "dm.read",
+            Scope::DmWrite => "dm.write",
+            Scope::MediaWrite => "media.write",
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A PKCE code verifier and its S256 challenge.
+#[derive(Debug, Clone)]
+pub struct PkceChallenge {
+    /// Kept secret until the code is exchanged.
+    pub verifier: String,
+    /// `BASE64URL(SHA256(verifier))`, sent in the authorize URL.
+    pub challenge: String,
+}
+
+impl PkceChallenge {
+    /// Generates a random 64 character verifier.
+    pub fn new() -> Self {
+        Self::from_verifier(&random_string(64))
+    }
+
+    pub fn from_verifier(verifier: &str) -> Self {
+        let digest = Sha256::digest(verifier.as_bytes());
+        PkceChallenge {
+            verifier: verifier.to_string(),
+            challenge: URL_SAFE_NO_PAD.encode(digest),
+        }
+    }
+}
+
+impl Default for PkceChallenge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Everything needed to send the user to the authorize page and to finish the
+/// flow once they come back.
+#[derive(Debug, Clone)]
+pub struct AuthorizationRequest {
+    pub url: Url,
+    /// Must match the `state` the user is redirected back with.
+    pub state: String,
+    pub pkce: PkceChallenge,
+}
+
+/// Response of `POST /2/oauth2/token`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuth2Token {
+    pub token_type: String,
+    pub access_token: Secret,
+    /// Only returned when the `offline.access` scope was granted.
+    pub refresh_token: Option<Secret>,
+    /// Lifetime of the access token in seconds, as returned by the API.
+    pub expires_in: Option<u64>,
+    /// Space separated list of granted scopes.
+    pub scope: Option<String>,
+    /// When the access token expires, in seconds since the Unix epoch.
+    /// Computed from `expires_in` when the token is received.
+    #[serde(default)]
+    pub expires_at: Option<u64>,
+}
+
+impl OAuth2Token {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| now_secs() + EXPIRY_LEEWAY_SECS >= expires_at)
+    }
+}
+
+/// An OAuth 2.0 app (client) registered in the developer portal.
+#[derive(Debug, Clone)]
+pub struct OAuth2Client {
+    client_id: String,
+    client_secret: Option<Secret>,
+    redirect_uri: String,
+    scopes: Vec<Scope>,
+    authorize_url: String,
+    api_base_url: String,
+    http: reqwest::Client,
+}
+
+impl OAuth2Client {
+    /// `redirect_uri` must be one of the callback URLs configured for the app.
+    pub fn new(client_id: &str, redirect_uri: &str) -> Self {
+        OAuth2Client {
+            client_id: client_id.to_string(),
+            client_secret: None,
+            redirect_uri: redirect_uri.to_string(),
+            scopes: vec![Scope::TweetRead, Scope::UsersRead],
+            authorize_url: DEFAULT_AUTHORIZE_URL.to_string(),
+            api_base_url: DEFAULT_API_BASE_URL.to_string(),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Confidential clients authenticate token requests with their secret.
+    pub fn client_secret(mut self, client_secret: &str) -> Self {
+        self.client_secret = Some(Secret::new(client_secret));
+        self
+    }
+
+    /// Defaults to `tweet.read users.read`.
+    pub fn scopes(mut self, scopes: Vec<Scope>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Defaults to [`DEFAULT_AUTHORIZE_URL`].
+    pub fn authorize_url(mut self, authorize_url: &str) -> Self {
+        self.authorize_url = authorize_url.to_string();
+        self
+    }
+
+    /// Base URL of the token endpoints. Defaults to [`DEFAULT_API_BASE_URL`].
+    pub fn api_base_url(mut self, base_url: &str) -> Self {
+        self.api_base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http = client;
+        self
+    }
+
+    pub fn client_id(&self) -> &str {
+        &self.client_id
+    }
+
+    /// Builds the URL to send the user to, with a fresh `state` and PKCE challenge.
+    /// Fails with `TweetyError::UrlParseError` if the authorize URL is malformed.
+    pub fn authorization_request(&self) -> Result<AuthorizationRequest, TweetyError> {
+        let state = random_string(32);
+        let pkce = PkceChallenge::new();
+        let scope = self
+            .scopes
+            .iter()
+            .map(Scope::as_str)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut url = Url::parse(&self.authorize_url).map_err(TweetyError::UrlParseError)?;
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", &self.redirect_uri)
+            .append_pair("scope", &scope)
+            .append_pair("state", &state)
+            .append_pair("code_challenge", &pkce.challenge)
+            .append_pair("code_challenge_method", "S256");
+
+        Ok(AuthorizationRequest { url, state, pkce })
+    }
+
+    /// Exchanges the code the user was redirected back with for tokens.
+    pub async fn exchange_code(
+        &self,
+        code: &str,
+        code_verifier: &str,
+    ) -> Result<OAuth2Token, TweetyError> {
+        self.token_request(&[
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &self.redirect_uri),
+            ("code_verifier", code_verifier),
+            ("client_id", &self.client_id),
+        ])
+        .await
+    }
+
+    /// Gets a new access token. Refresh tokens are single-use, the returned
+    /// token carries the next one.
+    pub async fn refresh_token(&self, refresh_token: &str) -> Result<OAuth2Token, TweetyError> {
+        self.token_request(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token),
+            ("client_id", &self.client_id),
+        ])
+        .await
+    }
+
+    /// Revokes an access or refresh token.
+    pub async fn revoke_token(&self, token: &str) -> Result<(), TweetyError> {
+        let url = format!("{}/2/oauth2/revoke", self.api_base_url);
+        let request = self
+            .authenticate(self.http.post(&url))
+            .form(&[("token", token), ("client_id", &self.client_id)]);
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| TweetyError::NetworkError(err.to_string()))?;
+        RawResponse::read(response).await?.into_result()?;
+
+        Ok(())
+    }
+
+    async fn token_request(&self, form: &[(&str, &str)]) -> Result<OAuth2Token, TweetyError> {
+        let url = format!("{}/2/oauth2/token", self.api_base_url);
+        let request = self.authenticate(self.http.post(&url)).form(form);
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| TweetyError::NetworkError(err.to_string()))?;
+        let value = RawResponse::read(response).await?.into_result()?;
+
+        let mut token = serde_json::from_value::<OAuth2Token>(value)
+            .map_err(|err| TweetyError::JsonParseError(err.to_string()))?;
+        token.expires_at = token.expires_in.map(|expires_in| now_secs() + expires_in);
+
+        Ok(token)
+    }
+
+    fn authenticate(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.client_secret {
+            Some(secret) => request.basic_auth(&self.client_id, Some(secret.expose())),
+            None => request,
+        }
+    }
+}
+
+/// An OAuth 2.0 user token that refreshes itself when it expires.
+/// Clones share the same token.
+#[derive(Debug, Clone)]
+pub struct OAuth2Session {
+    client: OAuth2Client,
+    token: Arc<RwLock<OAuth2Token>>,
+    refresh_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl OAuth2Session {
+    pub fn new(client: OAuth2Client, token: OAuth2Token) -> Self {
+        OAuth2Session {
+            client,
+            token: Arc::new(RwLock::new(token)),
+            refresh_lock: Arc::default(),
+        }
+    }
+
+    /// The current token. Store it after a refresh to survive restarts.
+    pub fn token(&self) -> OAuth2Token {
+        self.token
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    pub(crate) fn access_token(&self) -> Secret {
+        self.token
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .access_token
+            .clone()
+    }
+
+    /// Refreshes the token if it expired. Fails with `TweetyError::OAuthError` if
+    /// there is no refresh token to do so: it is only granted with
+    /// [`Scope::OfflineAccess`].
+    pub async fn refresh_if_expired(&self) -> Result<(), TweetyError> {
+        if !self.token().is_expired() {
+            return Ok(());
+        }
+
+        // Only one refresh at a time: refresh tokens are single-use.
+        let _guard = self.refresh_lock.lock().await;
+        let current = self.token();
+        if !current.is_expired() {
+            return Ok(());
+        }
+        let Some(refresh_token) = current.refresh_token else {
+            return Err(TweetyError::OAuthError(
+                "the access token expired and there is no refresh token, authorize again"
+                    .to_string(),
+            ));
+        };
+
+        let refreshed = self.client.refresh_token(refresh_token.expose()).await?;
+        *self.token.write().unwrap_or_else(|err| err.into_inner()) = refreshed;
+
+        Ok(())
+    }
+}
+
+/// Waits for the browser to be redirected to a loopback `redirect_uri`
+/// (e.g. `http://127.0.0.1:8976/callback`) and returns the authorization code.
+///
+/// Requests without `code`, `state` or `error`, such as the browser asking for a
+/// favicon, get a 404 and the listener keeps waiting.
+///
+/// Fails with `TweetyError::OAuthError` if the user denied access or the
+/// `state` doesn't match `expected_state`.
+pub async fn listen_for_code<A: ToSocketAddrs>(
+    addr: A,
+    expected_state: &str,
+) -> Result<String, TweetyError> {
+    let io_error = |err: std::io::Error| TweetyError::NetworkError(err.to_string());
+
+    let listener = TcpListener::bind(addr).await.map_err(io_error)?;
+    loop {
+        let (mut stream, _) = listener.accept().await.map_err(io_error)?;
+
+        let mut buffer = vec![0u8; 8192];
+        let Ok(read) = stream.read(&mut buffer).await else {
+            continue;
+        };
+        let request = String::from_utf8_lossy(&buffer[..read]);
+        let target = request
+            .lines()
+            .next()
+            .and_then(|line| line.split(' ').nth(1))
+            .unwrap_or("/");
+
+        let Ok(url) = Url::parse(&format!("http://localhost{}", target)) else {
+            respond(&mut stream, "404 Not Found", "Not found.").await;
+            continue;
+        };
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.to_string())
+        };
+        if ["code", "state", "error"]
+            .iter()
+            .all(|name| param(name).is_none())
+        {
+            respond(&mut stream, "404 Not Found", "Not found.").await;
+            continue;
+        }
+
+        let result = if let Some(error) = param("error") {
+            Err(TweetyError::OAuthError(format!(
+                "authorization denied: {}",
+                error
+            )))
+        } else if param("state").as_deref() != Some(expected_state) {
+            Err(TweetyError::OAuthError("state mismatch".to_string()))
+        } else {
+            param("code").ok_or_else(|| TweetyError::OAuthError("missing code".to_string()))
+        };
+
+        let message = match &result {
+            Ok(_) => "Authorization complete, you can close this window.",
+            Err(_) => "Authorization failed, you can close this window.",
+        };
+        respond(&mut stream, "200 OK", message).await;
+
+        return result;
+    }
+}
+
+/// Answers a request to [`listen_for_code`] with a plain text `message`.
+async fn respond(stream: &mut TcpStream, status: &str, message: &str) {
+    let response = format!(
+        "HTTP/1.1 {}\r\ncontent-type: text/plain\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+        status,
+        message.len(),
+        message
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+fn random_string(len: usize) -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
@@ -0,0 +1,358 @@
+//! Cursor-based pagination as an async [`Stream`](futures::Stream).
+//!
+//! Endpoints that return results in pages have a `paginate_*` counterpart
+//! returning a [`Paginator`], which follows `meta.next_token` until the last page
+//! or until an optional item cap is reached.
+//! [Docs](https://developer.x.com/en/docs/x-api/pagination)
+//!
+//! Every [`Page`] carries a [`Checkpoint`] that can be saved to disk and later
+//! passed to [`Paginator::resume`] to continue where the iteration stopped.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use tweety_rs::types::id::UserId;
+//! use tweety_rs::TweetyClient;
+//!
+//! # async fn example(client: TweetyClient) -> Result<(), tweety_rs::api::error::TweetyError> {
+//! let mut followers = client
+//!     .paginate_user_followers(UserId::new(2244994945), None)
+//!     .max_items(5000)
+//!     .items();
+//!
+//! while let Some(follower) = followers.next().await {
+//!     println!("{}", follower?.username);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Example: resumable export
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use std::path::Path;
+//! use tweety_rs::types::user::User;
+//! use tweety_rs::api::pagination::{Checkpoint, Paginator};
+//! use tweety_rs::types::id::UserId;
+//! use tweety_rs::TweetyClient;
+//!
+//! # async fn example(client: TweetyClient) -> Result<(), tweety_rs::api::error::TweetyError> {
+//! let path = Path::new("followers.checkpoint.json");
+//! let paginator = if path.exists() {
+//!     let checkpoint = Checkpoint::load(path)?;
+//!     tokio::time::sleep(checkpoint.ready_in()).await;
+//!     Paginator::<User>::resume(&client, checkpoint)
+//! } else {
+//!     client.paginate_user_followers(UserId::new(2244994945), None)
+//! };
+//!
+//! let mut pages = paginator.pages();
+//! while let Some(page) = pages.next().await {
+//!     let page = page?;
+//!     // ... store page.data ...
+//!     page.checkpoint.save(path)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::api::client::TweetyClient;
+use crate::api::error::{ErrorDetail, TweetyError};
+use crate::api::rate_limit::RateLimit;
+use crate::types::hydrated::Hydrated;
+use crate::types::response::{Includes, Meta};
+use futures::stream::{self, BoxStream, StreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::time::Duration;
+use url::Url;
+
+/// Query parameter carrying the token of the next page. Most endpoints call it
+/// `pagination_token`, the search endpoints `next_token`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenParam {
+    PaginationToken,
+    NextToken,
+}
+
+impl TokenParam {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TokenParam::PaginationToken => "pagination_token",
+            TokenParam::NextToken => "next_token",
+        }
+    }
+}
+
+/// One page of results.
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// Items of the `data` array, empty when the page has none.
+    pub data: Vec<T>,
+    /// The `includes` object, if the request asked for expansions.
+    pub includes: Option<Includes>,
+    /// The `meta` object, if any.
+    pub meta: Option<Meta>,
+    /// Token of the next page, `None` on the last page.
+    pub next_token: Option<String>,
+    /// Resources of the page that could not be returned, see [`ErrorDetail`].
+    pub errors: Vec<ErrorDetail>,
+    /// Where to resume once this page has been processed.
+    pub checkpoint: Checkpoint,
+}
+
+impl<T: DeserializeOwned> Page<T> {
+    fn from_value(mut value: Value, checkpoint: Checkpoint) -> Result<Self, TweetyError> {
+        let data = match value.get_mut("data").map(Value::take) {
+            Some(Value::Null) | None => Vec::new(),
+            Some(data) => serde_json::from_value::<Vec<T>>(data)
+                .map_err(|err| TweetyError::JsonParseError(err.to_string()))?,
+        };
+        let errors = parse_optional::<Vec<ErrorDetail>>(value.get_mut("errors").map(Value::take))?
+            .unwrap_or_default();
+        let includes = parse_optional::<Includes>(value.get_mut("includes").map(Value::take))?;
+        let meta = parse_optional::<Meta>(value.get_mut("meta").map(Value::take))?;
+        let next_token = meta.as_ref().and_then(|meta| meta.next_token.clone());
+
+        Ok(Page {
+            data,
+            includes,
+            meta,
+            next_token,
+            errors,
+            checkpoint,
+        })
+    }
+}
+
+impl<T> Page<T> {
+    /// Indexes `includes` to follow the expansions of the page's items.
+    /// See [`crate::types::hydrated`].
+    pub fn hydrate(self) -> Hydrated<T> {
+        Hydrated::new(self.data, self.includes, self.meta).with_errors(self.errors)
+    }
+}
+
+fn parse_optional<T: DeserializeOwned>(value: Option<Value>) -> Result<Option<T>, TweetyError> {
+    match value {
+        Some(Value::Null) | None => Ok(None),
+        Some(value) => serde_json::from_value(value)
+            .map(Some)
+            .map_err(|err| TweetyError::JsonParseError(err.to_string())),
+    }
+}
+
+/// The state of a pagination, serializable so a long iteration can survive restarts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// First page's URL, including the query parameters.
+    pub url: String,
+    pub token_param: TokenParam,
+    /// Token of the next page to fetch, `None` before the first page.
+    pub next_token: Option<String>,
+    /// Number of items returned so far.
+    pub items_seen: usize,
+    pub max_items: Option<usize>,
+    /// Whether the last page was reached (or `max_items`).
+    pub done: bool,
+    /// Rate-limit state of the endpoint after the last request.
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl Checkpoint {
+    /// Writes the checkpoint as JSON. The file is replaced atomically, so a
+    /// crash while saving leaves the previous checkpoint intact.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), TweetyError> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err| TweetyError::JsonParseError(err.to_string()))?;
+
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, json)
+            .and_then(|_| std::fs::rename(&tmp, path))
+            .map_err(|err| TweetyError::FileIOError(format!("{}: {}", path.display(), err)))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, TweetyError> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|err| TweetyError::FileIOError(format!("{}: {}", path.display(), err)))?;
+
+        serde_json::from_str(&json).map_err(|err| TweetyError::JsonParseError(err.to_string()))
+    }
+
+    /// How long to wait before resuming: the time until the rate-limit window
+    /// resets if it was exhausted, zero otherwise.
+    pub fn ready_in(&self) -> Duration {
+        match self.rate_limit {
+            Some(rate_limit) if rate_limit.is_exhausted() => rate_limit.reset_in(),
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// Walks the pages of a paginated endpoint. Nothing is requested until
+/// [`Paginator::pages`] or [`Paginator::items`] is polled.
+pub struct Paginator<'a, T> {
+    client: &'a TweetyClient,
+    state: Checkpoint,
+    /// Returned by the first poll instead of requesting anything, see [`Paginator::try_new`].
+    error: Option<TweetyError>,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Paginator<'a, T>
+where
+    T: DeserializeOwned + Send + 'a,
+{
+    /// `url` is the first page's URL, including any query parameters.
+    /// A token already in its query is where the pagination starts.
+    pub fn new(client: &'a TweetyClient, url: &str, token_param: TokenParam) -> Self {
+        let next_token = Url::parse(url).ok().and_then(|url| {
+            url.query_pairs()
+                .find(|(key, _)| key == token_param.as_str())
+                .map(|(_, value)| value.into_owned())
+        });
+
+        Self::resume(
+            client,
+            Checkpoint {
+                url: url.to_string(),
+                token_param,
+                next_token,
+                items_seen: 0,
+                max_items: None,
+                done: false,
+                rate_limit: None,
+            },
+        )
+    }
+
+    /// Like [`Paginator::new`], for a URL built from parameters that failed to
+    /// validate: the streams yield that error and end, without sending anything.
+    pub(crate) fn try_new(
+        client: &'a TweetyClient,
+        url: Result<String, TweetyError>,
+        token_param: TokenParam,
+    ) -> Self {
+        match url {
+            Ok(url) => Self::new(client, &url, token_param),
+            Err(err) => Paginator {
+                error: Some(err),
+                ..Self::new(client, "", token_param)
+            },
+        }
+    }
+
+    /// Continues a pagination from a [`Checkpoint`], e.g. one loaded from disk.
+    pub fn resume(client: &'a TweetyClient, checkpoint: Checkpoint) -> Self {
+        Paginator {
+            client,
+            state: checkpoint,
+            error: None,
+            _item: PhantomData,
+        }
+    }
+
+    /// Stops once this many items were returned, without requesting further pages.
+    pub fn max_items(mut self, max_items: usize) -> Self {
+        self.state.max_items = Some(max_items);
+        self
+    }
+
+    /// Starts from the page with this token instead of the first one.
+    pub fn start_token(mut self, token: &str) -> Self {
+        self.state.next_token = Some(token.to_string());
+        self
+    }
+
+    /// The current state, to resume from later.
+    pub fn checkpoint(&self) -> &Checkpoint {
+        &self.state
+    }
+
+    /// Yields every page in order. Ends after the first error.
+    pub fn pages(self) -> BoxStream<'a, Result<Page<T>, TweetyError>> {
+        stream::unfold(Some(self), |paginator| async move {
+            let mut paginator = paginator?;
+            if let Some(err) = paginator.error.take() {
+                return Some((Err(err), None));
+            }
+            if paginator.state.done || paginator.remaining() == Some(0) {
+                return None;
+            }
+
+            match paginator.fetch().await {
+                Ok(page) => Some((Ok(page), Some(paginator))),
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+        .boxed()
+    }
+
+    /// Yields the items of every page in order. Ends after the first error.
+    pub fn items(self) -> BoxStream<'a, Result<T, TweetyError>> {
+        self.pages()
+            .flat_map(|page| {
+                stream::iter(match page {
+                    Ok(page) => page.data.into_iter().map(Ok).collect(),
+                    Err(err) => vec![Err(err)],
+                })
+            })
+            .boxed()
+    }
+
+    fn remaining(&self) -> Option<usize> {
+        self.state
+            .max_items
+            .map(|max_items| max_items.saturating_sub(self.state.items_seen))
+    }
+
+    /// Fetches the next page and advances the state past it.
+    async fn fetch(&mut self) -> Result<Page<T>, TweetyError> {
+        let mut url = Url::parse(&self.state.url).map_err(TweetyError::UrlParseError)?;
+        let param = self.state.token_param.as_str();
+
+        // Drop a token passed in the original query, the current one replaces it.
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .into_owned()
+            .filter(|(key, _)| key != param)
+            .collect();
+        url.set_query(None);
+        if !pairs.is_empty() {
+            url.query_pairs_mut().extend_pairs(pairs);
+        }
+
+        let query = match &self.state.next_token {
+            Some(token) => vec![(param, token.as_str())],
+            None => vec![],
+        };
+        let value = self
+            .client
+            .send_request_with::<()>(url.as_str(), Method::GET, &query, HeaderMap::new(), None)
+            .await?;
+
+        let mut page = Page::<T>::from_value(value, self.state.clone())?;
+        if let Some(remaining) = self.remaining() {
+            page.data.truncate(remaining);
+        }
+
+        self.state.items_seen += page.data.len();
+        self.state.next_token = page.next_token.clone();
+        self.state.done = page.next_token.is_none() || self.remaining() == Some(0);
+        self.state.rate_limit = self.client.rate_limit(Method::GET, url.path());
+        page.checkpoint = self.state.clone();
+
+        Ok(page)
+    }
+}
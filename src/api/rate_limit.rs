@@ -0,0 +1,56 @@
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Rate-limit state reported by the `x-rate-limit-*` response headers.
+/// [Docs](https://developer.x.com/en/docs/x-api/rate-limits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Maximum number of requests allowed in the current window.
+    pub limit: u32,
+    /// Number of requests left in the current window.
+    pub remaining: u32,
+    /// When the window resets, in seconds since the Unix epoch.
+    pub reset: u64,
+}
+
+impl RateLimit {
+    /// Reads `x-rate-limit-limit`, `x-rate-limit-remaining` and `x-rate-limit-reset`.
+    /// Returns `None` unless all three are present and numeric.
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let header = |name: &str| headers.get(name)?.to_str().ok()?.trim().parse::<u64>().ok();
+
+        Some(RateLimit {
+            limit: header("x-rate-limit-limit")? as u32,
+            remaining: header("x-rate-limit-remaining")? as u32,
+            reset: header("x-rate-limit-reset")?,
+        })
+    }
+
+    /// The moment the current window resets.
+    pub fn reset_at(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.reset)
+    }
+
+    /// Time left until the window resets, zero if it already has.
+    pub fn reset_in(&self) -> Duration {
+        self.reset_at()
+            .duration_since(SystemTime::now())
+            .unwrap_or_default()
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+}
+
+/// What to do when a request is rejected with HTTP 429.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitPolicy {
+    /// Return the `TweetyError::ApiError` right away.
+    #[default]
+    Fail,
+    /// Sleep until the window resets (per `x-rate-limit-reset`) and retry once.
+    /// Fails as usual if the reset is further away than `max_wait`.
+    WaitAndRetry { max_wait: Duration },
+}
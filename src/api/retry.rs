@@ -0,0 +1,124 @@
+use reqwest::{Method, StatusCode};
+use std::time::Duration;
+
+/// Controls how `TweetyClient` retries requests that fail for transient reasons
+/// (timeouts, dropped connections, 5xx responses).
+///
+/// HTTP 429 is handled separately by [`RateLimitPolicy`](crate::api::rate_limit::RateLimitPolicy).
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+/// use tweety_rs::api::retry::RetryPolicy;
+/// use tweety_rs::TweetyClient;
+///
+/// let client = TweetyClient::builder()
+///     .consumer_key("your_consumer_key")
+///     .consumer_key_secret("your_consumer_key_secret")
+///     .access_token("your_access_token")
+///     .access_token_secret("your_access_token_secret")
+///     .retry_policy(RetryPolicy {
+///         max_attempts: 5,
+///         initial_backoff: Duration::from_secs(1),
+///         ..Default::default()
+///     })
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one. `1` disables retries.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between two attempts.
+    pub max_backoff: Duration,
+    /// Factor applied to the delay after every retry.
+    pub multiplier: f64,
+    /// Randomizes each delay between half and all of its value, so several
+    /// clients failing together don't retry in lockstep.
+    pub jitter: bool,
+    /// Response status codes that are retried.
+    pub retry_on_status: Vec<u16>,
+    /// Retry when the request timed out.
+    pub retry_on_timeout: bool,
+    /// Retry when the connection could not be established.
+    pub retry_on_connect: bool,
+    /// Also retry POST and PATCH requests. Off by default, since retrying e.g.
+    /// `post_tweet` after a timeout may post the same Tweet twice.
+    pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, 500ms doubling up to 30s with jitter, on 5xx responses,
+    /// timeouts and connection errors of idempotent requests.
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+            retry_on_status: vec![500, 502, 503, 504],
+            retry_on_timeout: true,
+            retry_on_connect: true,
+            retry_non_idempotent: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries. This is what `TweetyClient` uses unless
+    /// configured otherwise.
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Delay to wait after the given failed attempt (starting at 1).
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let delay = self
+            .initial_backoff
+            .mul_f64(self.multiplier.max(1.0).powi(exponent))
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let half = delay / 2;
+            half + half.mul_f64(rand::random::<f64>())
+        } else {
+            delay
+        }
+    }
+
+    pub(crate) fn allows(&self, method: &Method, attempt: u32) -> bool {
+        attempt < self.max_attempts && (self.retry_non_idempotent || is_idempotent(method))
+    }
+
+    pub(crate) fn should_retry_status(
+        &self,
+        method: &Method,
+        status: StatusCode,
+        attempt: u32,
+    ) -> bool {
+        self.allows(method, attempt) && self.retry_on_status.contains(&status.as_u16())
+    }
+
+    pub(crate) fn should_retry_error(
+        &self,
+        method: &Method,
+        err: &reqwest::Error,
+        attempt: u32,
+    ) -> bool {
+        self.allows(method, attempt)
+            && ((self.retry_on_timeout && err.is_timeout())
+                || (self.retry_on_connect && err.is_connect()))
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    !matches!(*method, Method::POST | Method::PATCH)
+}
@@ -1,9 +1,10 @@
 use crate::api::client::TweetyClient;
-use crate::api::error::TweetyError;
-use crate::types::tweet::PostTweetParams;
+use crate::api::error::{ErrorDetail, TweetyError};
+use crate::types::id::{MediaId, TweetId};
+use crate::types::response::Response;
+use crate::types::tweet::{PostTweetParams, Tweet, TweetResponse, TweetsResponse};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 use std::fmt;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -14,12 +15,20 @@ pub struct TweetRequest {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Media {
-    pub media_ids: Vec<String>,
+    pub media_ids: Vec<MediaId>,
+}
+
+/// Body of the endpoints acting on a Tweet on behalf of a user: retweet, like and bookmark.
+#[derive(Debug, Serialize)]
+pub(crate) struct TargetTweet {
+    pub(crate) tweet_id: TweetId,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DeleteResponse {
     pub data: DeleteData,
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -29,29 +38,27 @@ pub struct DeleteData {
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Ids {
-    Single(String),
-    Multiple(Vec<String>),
+    Single(TweetId),
+    Multiple(Vec<TweetId>),
 }
 
 impl fmt::Display for Ids {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Ids::Single(id) => write!(f, "{}", id),
-            Ids::Multiple(ids) => write!(f, "{}", ids.join(",")),
+            Ids::Multiple(ids) => {
+                let ids: Vec<String> = ids.iter().map(TweetId::to_string).collect();
+                write!(f, "{}", ids.join(","))
+            }
         }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PostTweetResponseData {
-    pub data: TweetResponse,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TweetResponse {
-    pub edit_history_tweet_ids: Vec<String>,
-    pub id: String,
-    pub text: String,
+    pub data: Tweet,
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
 }
 
 impl TweetyClient {
@@ -60,18 +67,21 @@ impl TweetyClient {
     /// GET /2/tweets
     /// Returns a variety of information about the Tweet specified by the requested ID or list of IDs.
     /// [Docs](https://developer.x.com/en/docs/x-api/tweets/lookup/api-reference/get-tweets)
-    pub async fn get_tweet(&self, tweet_id: Ids) -> Result<Value, TweetyError> {
-        let base_url = format!("https://api.x.com/2/tweets/?ids={}", tweet_id);
+    pub async fn get_tweet(&self, tweet_id: Ids) -> Result<Response<TweetsResponse>, TweetyError> {
+        let base_url = format!("{}/2/tweets/?ids={}", self.api_base_url, tweet_id);
 
-        self.send_request::<()>(&base_url, Method::GET, None).await
+        self.request::<_, ()>(&base_url, Method::GET, None).await
     }
     /// GET /2/tweets/:id
     /// Returns a variety of information about a single Tweet specified by the requested ID.
     /// [Docs](https://developer.x.com/en/docs/x-api/tweets/lookup/api-reference/get-tweets-id)
-    pub async fn get_tweet_info(&self, tweet_id: &str) -> Result<Value, TweetyError> {
-        let base_url = format!("https://api.x.com/2/tweets/{}", tweet_id);
+    pub async fn get_tweet_info(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<TweetResponse>, TweetyError> {
+        let base_url = format!("{}/2/tweets/{}", self.api_base_url, tweet_id);
 
-        self.send_request::<()>(&base_url, Method::GET, None).await
+        self.request::<_, ()>(&base_url, Method::GET, None).await
     }
 
     /// SEND tweet message, Media id is optional for attaching tweets with an image
@@ -82,7 +92,7 @@ impl TweetyClient {
         message: &str,
         body_params: Option<PostTweetParams>,
     ) -> Result<PostTweetResponseData, TweetyError> {
-        let base_url = "https://api.twitter.com/2/tweets";
+        let base_url = format!("{}/2/tweets", self.api_base_url);
 
         let json_body = if let Some(body) = body_params {
             body.to_json(message)
@@ -92,33 +102,36 @@ impl TweetyClient {
         };
 
         match self
-            .send_request(base_url, Method::POST, Some(json_body))
+            .send_request(&base_url, Method::POST, Some(json_body))
             .await
         {
             Ok(value) => match serde_json::from_value::<PostTweetResponseData>(value) {
                 Ok(res) => Ok(res),
                 Err(e) => Err(TweetyError::JsonParseError(e.to_string())),
             },
-            Err(err) => Err(TweetyError::ApiError(err.to_string())),
+            Err(err) => Err(err),
         }
     }
     /// UPDATE/EDIT TWEET
-    pub async fn edit_tweet(self, message: &str, media_id: &str) -> Result<Value, TweetyError> {
-        let base_url = format!("https://api.twitter.com/2/tweets/{}", media_id);
+    pub async fn edit_tweet(
+        &self,
+        message: &str,
+        tweet_id: TweetId,
+    ) -> Result<Response<PostTweetResponseData>, TweetyError> {
+        let base_url = format!("{}/2/tweets/{}", self.api_base_url, tweet_id);
 
         let body = serde_json::json!({
             "text": message,
         });
 
-        self.send_request(&base_url, Method::PATCH, Some(body))
-            .await
+        self.request(&base_url, Method::PATCH, Some(body)).await
     }
 
     /// DELETE TWEET
     /// Path parameter, pass The Tweet ID you are deleting.
     /// [Delete Docs](https://developer.x.com/en/docs/x-api/tweets/manage-tweets/api-reference/delete-tweets-id)
-    pub async fn delete_tweet(&self, tweet_id: &str) -> Result<DeleteResponse, TweetyError> {
-        let url = format!("https://api.x.com/2/tweets/{}", tweet_id);
+    pub async fn delete_tweet(&self, tweet_id: TweetId) -> Result<DeleteResponse, TweetyError> {
+        let url = format!("{}/2/tweets/{}", self.api_base_url, tweet_id);
 
         match self.send_request::<()>(&url, Method::DELETE, None).await {
             Ok(value) => match serde_json::from_value::<DeleteResponse>(value) {
@@ -0,0 +1,593 @@
+//! The API surface as a trait, so code built on top of the client can be tested without it.
+//!
+//! [`TweetyClient`] implements [`TwitterApi`] by calling its own methods. Write bot logic
+//! against the trait and pass the client in production, and the in-memory
+//! `testing::FakeTwitterApi` (behind the `testing` cargo feature) in tests:
+//!
+//! ```rust,no_run
+//! use tweety_rs::api::error::TweetyError;
+//! use tweety_rs::api::twitter_api::TwitterApi;
+//! use tweety_rs::types::id::UserId;
+//! use tweety_rs::TweetyClient;
+//!
+//! async fn retweet_mentions(api: &impl TwitterApi, user_id: UserId) -> Result<usize, TweetyError> {
+//!     let mentions = api.get_users_mentions(user_id, None).await?;
+//!     for mention in &mentions.data {
+//!         api.retweet(user_id, mention.id).await?;
+//!     }
+//!     Ok(mentions.data.len())
+//! }
+//!
+//! # async fn example(client: TweetyClient) -> Result<(), TweetyError> {
+//! retweet_mentions(&client, UserId::new(2244994945)).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The `paginate_*` methods are not part of the trait, their streams are tied to the client,
+//! nor are the uploads from a reader or a stream, which are generic.
+
+use crate::api::bookmark::{BookmarkParams, BookmarkResponse};
+use crate::api::client::TweetyClient;
+use crate::api::direct_messages::{self, DmEventsResponse};
+use crate::api::error::TweetyError;
+use crate::api::followers::UserFollowersResponse;
+use crate::api::following::{FollowResponse, UnfollowResponse, UserFollowingResponse};
+use crate::api::hide_replies::HideReplyResponse;
+use crate::api::like::LikeResponse;
+use crate::api::media_metadata::{LanguageCode, MediaMetadata, Subtitles};
+use crate::api::mentions::{self, MentionsResponse};
+use crate::api::retweets::{RetweetQueryParams, RetweetResponse};
+use crate::api::search::{self, FullArchiveSearchResponse, RecentSearchResponse};
+use crate::api::tweet::{DeleteResponse, Ids, PostTweetResponseData};
+use crate::api::uploads::{UploadParams, UploadedMedia};
+use crate::api::user::{UserInfo, UserQueryParams};
+use crate::types::id::{DmConversationId, MediaId, TweetId, UserId};
+use crate::types::response::Response;
+use crate::types::tweet::{PostTweetParams, TweetResponse, TweetsResponse};
+use crate::types::user::ApiResponse;
+use bytes::Bytes;
+use std::future::Future;
+use std::path::Path;
+
+/// Every endpoint of [`TweetyClient`]. Each method is documented on the client.
+pub trait TwitterApi {
+    /// See [`TweetyClient::get_tweet`].
+    fn get_tweet(
+        &self,
+        tweet_id: Ids,
+    ) -> impl Future<Output = Result<Response<TweetsResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_tweet_info`].
+    fn get_tweet_info(
+        &self,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<TweetResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::post_tweet`].
+    fn post_tweet(
+        &self,
+        message: &str,
+        body_params: Option<PostTweetParams>,
+    ) -> impl Future<Output = Result<PostTweetResponseData, TweetyError>> + Send;
+
+    /// See [`TweetyClient::edit_tweet`].
+    fn edit_tweet(
+        &self,
+        message: &str,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<PostTweetResponseData>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::delete_tweet`].
+    fn delete_tweet(
+        &self,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<DeleteResponse, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_user_by_id`].
+    fn get_user_by_id(
+        &self,
+        user_id: UserId,
+        params: Option<UserQueryParams>,
+    ) -> impl Future<Output = Result<Response<UserInfo>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_users`].
+    fn get_users(
+        &self,
+        ids: &[UserId],
+        params: Option<UserQueryParams>,
+    ) -> impl Future<Output = Result<Response<ApiResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_users_by_username`].
+    fn get_users_by_username(
+        &self,
+        username: &[&str],
+    ) -> impl Future<Output = Result<Response<UserInfo>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_users_by_usernames`].
+    fn get_users_by_usernames(
+        &self,
+        user_names: &[&str],
+        params: Option<UserQueryParams>,
+    ) -> impl Future<Output = Result<Response<ApiResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_user_me`].
+    fn get_user_me(
+        &self,
+        params: Option<UserQueryParams>,
+    ) -> impl Future<Output = Result<Response<UserInfo>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_user_followers`].
+    fn get_user_followers(
+        &self,
+        user_id: UserId,
+        params: Option<UserQueryParams>,
+    ) -> impl Future<Output = Result<UserFollowersResponse, TweetyError>> + Send;
+
+    /// See [`TweetyClient::follow_user`].
+    fn follow_user(
+        &self,
+        user_id: UserId,
+        target_user_id: UserId,
+    ) -> impl Future<Output = Result<FollowResponse, TweetyError>> + Send;
+
+    /// See [`TweetyClient::unfollow_user`].
+    fn unfollow_user(
+        &self,
+        source_userid: UserId,
+        target_userid: UserId,
+    ) -> impl Future<Output = Result<UnfollowResponse, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_users_following`].
+    fn get_users_following(
+        &self,
+        user_id: UserId,
+        query: Option<UserQueryParams>,
+    ) -> impl Future<Output = Result<UserFollowingResponse, TweetyError>> + Send;
+
+    /// See [`TweetyClient::unlike_tweet`].
+    fn unlike_tweet(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<LikeResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_users_who_liked_a_post`].
+    fn get_users_who_liked_a_post(
+        &self,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<ApiResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_posts_liked_by_a_user`].
+    fn get_posts_liked_by_a_user(
+        &self,
+        user_id: UserId,
+    ) -> impl Future<Output = Result<Response<TweetsResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::like_a_post`].
+    fn like_a_post(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<LikeResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::fetch_retweeters`].
+    fn fetch_retweeters(
+        &self,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<ApiResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::retweet`].
+    fn retweet(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<RetweetResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::unretweet`].
+    fn unretweet(
+        &self,
+        user_id: UserId,
+        source_tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<RetweetResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::fetch_retweets`].
+    fn fetch_retweets(
+        &self,
+        tweet_id: TweetId,
+        params: Option<RetweetQueryParams>,
+    ) -> impl Future<Output = Result<Response<TweetsResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_user_bookmark`].
+    fn get_user_bookmark(
+        &self,
+        user_id: UserId,
+        params: Option<BookmarkParams>,
+    ) -> impl Future<Output = Result<Response<TweetsResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::bookmark_post`].
+    fn bookmark_post(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<BookmarkResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::delete_bookmark`].
+    fn delete_bookmark(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<BookmarkResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::hide_tweet`].
+    fn hide_tweet(
+        &self,
+        tweet_id: TweetId,
+    ) -> impl Future<Output = Result<Response<HideReplyResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_users_mentions`].
+    fn get_users_mentions(
+        &self,
+        user_id: UserId,
+        query_params: Option<mentions::QueryParams>,
+    ) -> impl Future<Output = Result<Response<MentionsResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::recent_search`].
+    fn recent_search(
+        &self,
+        query: &str,
+        query_params: Option<search::QueryParams>,
+    ) -> impl Future<Output = Result<Response<RecentSearchResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::full_archive_search`].
+    fn full_archive_search(
+        &self,
+        query: &str,
+        query_params: Option<search::QueryParams>,
+    ) -> impl Future<Output = Result<Response<FullArchiveSearchResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_direct_messages`].
+    fn get_direct_messages(
+        &self,
+        params: direct_messages::QueryParams,
+    ) -> impl Future<Output = Result<Response<DmEventsResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_dm_events_with_participant`].
+    fn get_dm_events_with_participant(
+        &self,
+        participant_id: UserId,
+        params: direct_messages::QueryParams,
+    ) -> impl Future<Output = Result<Response<DmEventsResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::get_dm_events_in_conversation`].
+    fn get_dm_events_in_conversation(
+        &self,
+        dm_conversation_id: &DmConversationId,
+        params: direct_messages::QueryParams,
+    ) -> impl Future<Output = Result<Response<DmEventsResponse>, TweetyError>> + Send;
+
+    /// See [`TweetyClient::upload_file`].
+    fn upload_file(&self, path: &Path)
+        -> impl Future<Output = Result<MediaId, TweetyError>> + Send;
+
+    /// See [`TweetyClient::upload_media`].
+    fn upload_media(
+        &self,
+        path: &Path,
+        params: UploadParams,
+    ) -> impl Future<Output = Result<UploadedMedia, TweetyError>> + Send;
+
+    /// See [`TweetyClient::upload_media_bytes`].
+    fn upload_media_bytes(
+        &self,
+        bytes: Bytes,
+        params: UploadParams,
+    ) -> impl Future<Output = Result<UploadedMedia, TweetyError>> + Send;
+
+    /// See [`TweetyClient::create_media_metadata`].
+    fn create_media_metadata(
+        &self,
+        media_id: MediaId,
+        metadata: &MediaMetadata,
+    ) -> impl Future<Output = Result<(), TweetyError>> + Send;
+
+    /// See [`TweetyClient::create_media_subtitles`].
+    fn create_media_subtitles(
+        &self,
+        video_id: MediaId,
+        subtitles: &[Subtitles],
+    ) -> impl Future<Output = Result<(), TweetyError>> + Send;
+
+    /// See [`TweetyClient::delete_media_subtitles`].
+    fn delete_media_subtitles(
+        &self,
+        video_id: MediaId,
+        language_codes: &[LanguageCode],
+    ) -> impl Future<Output = Result<(), TweetyError>> + Send;
+}
+
+impl TwitterApi for TweetyClient {
+    async fn get_tweet(&self, tweet_id: Ids) -> Result<Response<TweetsResponse>, TweetyError> {
+        TweetyClient::get_tweet(self, tweet_id).await
+    }
+
+    async fn get_tweet_info(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<TweetResponse>, TweetyError> {
+        TweetyClient::get_tweet_info(self, tweet_id).await
+    }
+
+    async fn post_tweet(
+        &self,
+        message: &str,
+        body_params: Option<PostTweetParams>,
+    ) -> Result<PostTweetResponseData, TweetyError> {
+        TweetyClient::post_tweet(self, message, body_params).await
+    }
+
+    async fn edit_tweet(
+        &self,
+        message: &str,
+        tweet_id: TweetId,
+    ) -> Result<Response<PostTweetResponseData>, TweetyError> {
+        TweetyClient::edit_tweet(self, message, tweet_id).await
+    }
+
+    async fn delete_tweet(&self, tweet_id: TweetId) -> Result<DeleteResponse, TweetyError> {
+        TweetyClient::delete_tweet(self, tweet_id).await
+    }
+
+    async fn get_user_by_id(
+        &self,
+        user_id: UserId,
+        params: Option<UserQueryParams>,
+    ) -> Result<Response<UserInfo>, TweetyError> {
+        TweetyClient::get_user_by_id(self, user_id, params).await
+    }
+
+    async fn get_users(
+        &self,
+        ids: &[UserId],
+        params: Option<UserQueryParams>,
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        TweetyClient::get_users(self, ids, params).await
+    }
+
+    async fn get_users_by_username(
+        &self,
+        username: &[&str],
+    ) -> Result<Response<UserInfo>, TweetyError> {
+        TweetyClient::get_users_by_username(self, username).await
+    }
+
+    async fn get_users_by_usernames(
+        &self,
+        user_names: &[&str],
+        params: Option<UserQueryParams>,
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        TweetyClient::get_users_by_usernames(self, user_names, params).await
+    }
+
+    async fn get_user_me(
+        &self,
+        params: Option<UserQueryParams>,
+    ) -> Result<Response<UserInfo>, TweetyError> {
+        TweetyClient::get_user_me(self, params).await
+    }
+
+    async fn get_user_followers(
+        &self,
+        user_id: UserId,
+        params: Option<UserQueryParams>,
+    ) -> Result<UserFollowersResponse, TweetyError> {
+        TweetyClient::get_user_followers(self, user_id, params).await
+    }
+
+    async fn follow_user(
+        &self,
+        user_id: UserId,
+        target_user_id: UserId,
+    ) -> Result<FollowResponse, TweetyError> {
+        TweetyClient::follow_user(self, user_id, target_user_id).await
+    }
+
+    async fn unfollow_user(
+        &self,
+        source_userid: UserId,
+        target_userid: UserId,
+    ) -> Result<UnfollowResponse, TweetyError> {
+        TweetyClient::unfollow_user(self, source_userid, target_userid).await
+    }
+
+    async fn get_users_following(
+        &self,
+        user_id: UserId,
+        query: Option<UserQueryParams>,
+    ) -> Result<UserFollowingResponse, TweetyError> {
+        TweetyClient::get_users_following(self, user_id, query).await
+    }
+
+    async fn unlike_tweet(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<LikeResponse>, TweetyError> {
+        TweetyClient::unlike_tweet(self, user_id, tweet_id).await
+    }
+
+    async fn get_users_who_liked_a_post(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        TweetyClient::get_users_who_liked_a_post(self, tweet_id).await
+    }
+
+    async fn get_posts_liked_by_a_user(
+        &self,
+        user_id: UserId,
+    ) -> Result<Response<TweetsResponse>, TweetyError> {
+        TweetyClient::get_posts_liked_by_a_user(self, user_id).await
+    }
+
+    async fn like_a_post(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<LikeResponse>, TweetyError> {
+        TweetyClient::like_a_post(self, user_id, tweet_id).await
+    }
+
+    async fn fetch_retweeters(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        TweetyClient::fetch_retweeters(self, tweet_id).await
+    }
+
+    async fn retweet(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<RetweetResponse>, TweetyError> {
+        TweetyClient::retweet(self, user_id, tweet_id).await
+    }
+
+    async fn unretweet(
+        &self,
+        user_id: UserId,
+        source_tweet_id: TweetId,
+    ) -> Result<Response<RetweetResponse>, TweetyError> {
+        TweetyClient::unretweet(self, user_id, source_tweet_id).await
+    }
+
+    async fn fetch_retweets(
+        &self,
+        tweet_id: TweetId,
+        params: Option<RetweetQueryParams>,
+    ) -> Result<Response<TweetsResponse>, TweetyError> {
+        TweetyClient::fetch_retweets(self, tweet_id, params).await
+    }
+
+    async fn get_user_bookmark(
+        &self,
+        user_id: UserId,
+        params: Option<BookmarkParams>,
+    ) -> Result<Response<TweetsResponse>, TweetyError> {
+        TweetyClient::get_user_bookmark(self, user_id, params).await
+    }
+
+    async fn bookmark_post(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<BookmarkResponse>, TweetyError> {
+        TweetyClient::bookmark_post(self, user_id, tweet_id).await
+    }
+
+    async fn delete_bookmark(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<BookmarkResponse>, TweetyError> {
+        TweetyClient::delete_bookmark(self, user_id, tweet_id).await
+    }
+
+    async fn hide_tweet(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<HideReplyResponse>, TweetyError> {
+        TweetyClient::hide_tweet(self, tweet_id).await
+    }
+
+    async fn get_users_mentions(
+        &self,
+        user_id: UserId,
+        query_params: Option<mentions::QueryParams>,
+    ) -> Result<Response<MentionsResponse>, TweetyError> {
+        TweetyClient::get_users_mentions(self, user_id, query_params).await
+    }
+
+    async fn recent_search(
+        &self,
+        query: &str,
+        query_params: Option<search::QueryParams>,
+    ) -> Result<Response<RecentSearchResponse>, TweetyError> {
+        TweetyClient::recent_search(self, query, query_params).await
+    }
+
+    async fn full_archive_search(
+        &self,
+        query: &str,
+        query_params: Option<search::QueryParams>,
+    ) -> Result<Response<FullArchiveSearchResponse>, TweetyError> {
+        TweetyClient::full_archive_search(self, query, query_params).await
+    }
+
+    async fn get_direct_messages(
+        &self,
+        params: direct_messages::QueryParams,
+    ) -> Result<Response<DmEventsResponse>, TweetyError> {
+        TweetyClient::get_direct_messages(self, params).await
+    }
+
+    async fn get_dm_events_with_participant(
+        &self,
+        participant_id: UserId,
+        params: direct_messages::QueryParams,
+    ) -> Result<Response<DmEventsResponse>, TweetyError> {
+        TweetyClient::get_dm_events_with_participant(self, participant_id, params).await
+    }
+
+    async fn get_dm_events_in_conversation(
+        &self,
+        dm_conversation_id: &DmConversationId,
+        params: direct_messages::QueryParams,
+    ) -> Result<Response<DmEventsResponse>, TweetyError> {
+        TweetyClient::get_dm_events_in_conversation(self, dm_conversation_id, params).await
+    }
+
+    async fn upload_file(&self, path: &Path) -> Result<MediaId, TweetyError> {
+        TweetyClient::upload_file(self, path).await
+    }
+
+    async fn upload_media(
+        &self,
+        path: &Path,
+        params: UploadParams,
+    ) -> Result<UploadedMedia, TweetyError> {
+        TweetyClient::upload_media(self, path, params).await
+    }
+
+    async fn upload_media_bytes(
+        &self,
+        bytes: Bytes,
+        params: UploadParams,
+    ) -> Result<UploadedMedia, TweetyError> {
+        TweetyClient::upload_media_bytes(self, bytes, params).await
+    }
+
+    async fn create_media_metadata(
+        &self,
+        media_id: MediaId,
+        metadata: &MediaMetadata,
+    ) -> Result<(), TweetyError> {
+        TweetyClient::create_media_metadata(self, media_id, metadata).await
+    }
+
+    async fn create_media_subtitles(
+        &self,
+        video_id: MediaId,
+        subtitles: &[Subtitles],
+    ) -> Result<(), TweetyError> {
+        TweetyClient::create_media_subtitles(self, video_id, subtitles).await
+    }
+
+    async fn delete_media_subtitles(
+        &self,
+        video_id: MediaId,
+        language_codes: &[LanguageCode],
+    ) -> Result<(), TweetyError> {
+        TweetyClient::delete_media_subtitles(self, video_id, language_codes).await
+    }
+}
@@ -1,67 +1,661 @@
 use crate::api::client::TweetyClient;
-use crate::api::error::TweetyError;
-use reqwest::multipart;
-use reqwest_oauth1::OAuthClientProvider;
+use crate::api::error::{MediaError, TweetyError};
+use crate::api::fields::{query_enum, QueryString};
+use crate::api::media_validation::{sniff_media_type, validate_media};
+use crate::types::id::{MediaId, UserId};
+use bytes::{Bytes, BytesMut};
+use futures::stream::{self, Stream, StreamExt};
+use reqwest::header::HeaderMap;
+use reqwest::{multipart, Method};
 use serde::{Deserialize, Serialize};
-use std::{fs::File, io::Read, path::Path};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::sync::mpsc::UnboundedSender;
+use url::Url;
+
+/// Size of the segments sent by `APPEND`, the API accepts up to 5 MB.
+pub const CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Wait between two `STATUS` polls when the API does not say.
+const DEFAULT_CHECK_AFTER: Duration = Duration::from_secs(1);
+
+/// How long the uploads wait for a media to be processed when
+/// `UploadParams::max_processing_wait` is not set.
+pub const DEFAULT_MAX_PROCESSING_WAIT: Duration = Duration::from_secs(10 * 60);
+
+query_enum! {
+    /// Values of the `media_category` parameter: what the media will be attached to.
+    /// Videos and animated GIFs are only accepted with their category.
+    /// [Docs](https://developer.x.com/en/docs/twitter-api/v1/media/upload-media/uploading-media/media-best-practices)
+    pub enum MediaCategory {
+        TweetImage => "tweet_image",
+        TweetGif => "tweet_gif",
+        TweetVideo => "tweet_video",
+        DmImage => "dm_image",
+        DmGif => "dm_gif",
+        DmVideo => "dm_video",
+        Subtitles => "subtitles",
+    }
+}
+
+impl MediaCategory {
+    /// The Tweet category of a MIME type: `tweet_gif` for GIFs, `tweet_video` for videos,
+    /// `subtitles` for SRT files and `tweet_image` for anything else.
+    pub fn for_media_type(media_type: &str) -> Self {
+        if media_type.eq_ignore_ascii_case("application/x-subrip") {
+            MediaCategory::Subtitles
+        } else if media_type.eq_ignore_ascii_case("image/gif") {
+            MediaCategory::TweetGif
+        } else if media_type.starts_with("video/") {
+            MediaCategory::TweetVideo
+        } else {
+            MediaCategory::TweetImage
+        }
+    }
+}
+
+/// Parameters of [`TweetyClient::upload_media`] and the other chunked uploads.
+#[derive(Debug, Clone, Default)]
+pub struct UploadParams {
+    /// MIME type, e.g. `video/mp4`. Detected from the first bytes of the media when not set,
+    /// or else the file extension.
+    pub media_type: Option<String>,
+    /// Guessed from the media type when not set, see [`MediaCategory::for_media_type`].
+    pub media_category: Option<MediaCategory>,
+    /// Other users allowed to attach the media to their Tweets, up to 100.
+    pub additional_owners: Vec<UserId>,
+    /// Called after each segment sent.
+    pub progress: Option<ProgressCallback>,
+    /// Sends the media without checking it against the limits of its category first,
+    /// e.g. if the API has raised one.
+    pub skip_validation: bool,
+    /// How long to wait for the media to be processed after the upload,
+    /// [`DEFAULT_MAX_PROCESSING_WAIT`] when not set.
+    pub max_processing_wait: Option<Duration>,
+}
+
+impl UploadParams {
+    /// Calls `callback` with the progress of the upload after each segment sent.
+    pub fn on_progress(
+        mut self,
+        callback: impl Fn(UploadProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(ProgressCallback(Arc::new(callback)));
+        self
+    }
+
+    /// Sends the progress of the upload to `sender` after each segment sent.
+    /// The upload carries on if the receiver is dropped.
+    pub fn progress_channel(self, sender: UnboundedSender<UploadProgress>) -> Self {
+        self.on_progress(move |progress| {
+            let _ = sender.send(progress);
+        })
+    }
+}
+
+/// How much of a media has been sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    pub bytes_sent: u64,
+    pub total_bytes: u64,
+}
+
+impl UploadProgress {
+    /// Between 0 and 1.
+    pub fn fraction(&self) -> f64 {
+        if self.total_bytes == 0 {
+            1.0
+        } else {
+            self.bytes_sent as f64 / self.total_bytes as f64
+        }
+    }
+}
+
+/// A progress callback, set with [`UploadParams::on_progress`].
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<dyn Fn(UploadProgress) + Send + Sync>);
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ProgressCallback")
+    }
+}
+
+/// Response of the `INIT`, `FINALIZE` and `STATUS` commands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UploadedMedia {
+    pub media_id: MediaId,
+    pub media_key: Option<String>,
+    /// Size in bytes, set once finalized.
+    pub size: Option<u64>,
+    /// The media id has to be used in a Tweet before it expires.
+    pub expires_after_secs: Option<u64>,
+    /// Only set for media processed asynchronously (videos and GIFs).
+    pub processing_info: Option<ProcessingInfo>,
+}
+
+impl UploadedMedia {
+    /// Whether the media is still being processed and can't be attached yet.
+    pub fn is_processing(&self) -> bool {
+        self.processing_info.as_ref().is_some_and(|info| {
+            matches!(
+                info.state,
+                ProcessingState::Pending | ProcessingState::InProgress
+            )
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessingInfo {
+    pub state: ProcessingState,
+    /// Seconds to wait before polling `STATUS` again.
+    pub check_after_secs: Option<u64>,
+    pub progress_percent: Option<u8>,
+    /// Only set when the state is `failed`.
+    pub error: Option<ProcessingError>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingState {
+    Pending,
+    InProgress,
+    Failed,
+    Succeeded,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessingError {
+    pub code: Option<i64>,
+    pub name: Option<String>,
+    pub message: Option<String>,
+}
+
+/// MIME type of the file extensions accepted by the upload endpoints.
+pub(crate) fn media_type_from_extension(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "srt" => "application/x-subrip",
+        _ => return None,
+    })
+}
 
 #[derive(Serialize, Deserialize)]
 pub struct Media {
-    pub media_id: u64,
+    pub media_id: MediaId,
 }
 
 impl TweetyClient {
     /// Create a media from a file<br/>
     /// Will fail if `consumer_key`, `consumer_secret`, `access_token` and `access_token_secret` are not set
     /// Will take a path as a parameter and return the media id of the uploadeded file is sucess and TweetyError incase of failure
-    pub async fn upload_file(&self, path: &Path) -> Result<u64, TweetyError> {
+    ///
+    /// Only images up to 5 MB are accepted, checked before sending them (see
+    /// [`crate::api::media_validation`]). Videos, and GIFs over the image limits, fail with
+    /// `MediaError::ChunkedUploadRequired`: upload them with [`TweetyClient::upload_media`].
+    pub async fn upload_file(&self, path: &Path) -> Result<MediaId, TweetyError> {
         if !self.is_initialized() {
             return Err(TweetyError::MissingCredentials);
         }
 
-        let mut file = match File::open(path) {
-            Ok(value) => value,
-            Err(err) => {
-                println!("Error opening file: {:?}", err);
-                return Err(TweetyError::FileIOError(err.to_string()));
+        let buffer = tokio::fs::read(path)
+            .await
+            .map_err(|err| TweetyError::FileIOError(format!("{}: {}", path.display(), err)))?;
+        let media_type = sniff_media_type(&buffer)
+            .or_else(|| media_type_from_extension(path))
+            .ok_or_else(|| {
+                TweetyError::InvalidParameter(format!("unknown media type of {}", path.display()))
+            })?;
+        let total_bytes = buffer.len() as u64;
+        if let Err(err) =
+            validate_media(&buffer, total_bytes, media_type, MediaCategory::TweetImage)
+        {
+            let category = MediaCategory::for_media_type(media_type);
+            if category == MediaCategory::TweetImage {
+                return Err(err.into());
             }
-        };
-
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer).unwrap();
+            validate_media(&buffer, total_bytes, media_type, category)?;
+            return Err(MediaError::ChunkedUploadRequired {
+                media_type: media_type.to_string(),
+                category,
+            }
+            .into());
+        }
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "media".to_string());
 
         let part = multipart::Part::bytes(buffer)
-            .file_name(path.file_name().unwrap().to_string_lossy().to_string());
+            .file_name(file_name)
+            .mime_str(media_type)?;
 
         let form = multipart::Form::new().part("media", part);
 
-        let secrets = reqwest_oauth1::Secrets::new(&self.consumer_key, &self.consumer_key_secret)
-            .token(&self.access_token, &self.access_token_secret);
-        let client = reqwest::Client::new();
-
-        let response = client
-            .oauth1(secrets)
-            .post("https://upload.twitter.com/1.1/media/upload.json")
-            .multipart(form)
-            .send()
-            .await;
-
-        match response {
-            Ok(res) => {
-                if res.status().is_success() {
-                    let res = res.json::<Media>().await?;
-                    Ok(res.media_id)
+        let url = Url::parse(&self.media_upload_url()).map_err(TweetyError::UrlParseError)?;
+
+        self.auth.refresh_if_expired().await?;
+        let request = self
+            .auth
+            .authorize(&self.http, Method::POST, url, &[])?
+            .multipart(form);
+
+        let value = self
+            .execute(request)
+            .await?
+            .map_err(|err| TweetyError::NetworkError(err.to_string()))?
+            .into_result()?;
+        let media: Media = serde_json::from_value(value)
+            .map_err(|err| TweetyError::JsonParseError(err.to_string()))?;
+        Ok(media.media_id)
+    }
+}
+
+impl TweetyClient {
+    /// Uploads a media in chunks (`INIT`, `APPEND`, `FINALIZE`), then waits until it
+    /// is processed. Unlike [`TweetyClient::upload_file`], this accepts videos, animated
+    /// GIFs and files over 5 MB.
+    ///
+    /// Unless `params.media_type` is set, the media type is detected from the first bytes,
+    /// or else the file extension. The media is checked against the limits of its category
+    /// before anything is sent, see [`crate::api::media_validation`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::path::Path;
+    /// use tweety_rs::api::uploads::{MediaCategory, UploadParams};
+    /// use tweety_rs::TweetyClient;
+    ///
+    /// # async fn example(client: TweetyClient) -> Result<(), tweety_rs::api::error::TweetyError> {
+    /// let params = UploadParams {
+    ///     media_category: Some(MediaCategory::TweetVideo),
+    ///     ..Default::default()
+    /// };
+    /// let media = client.upload_media(Path::new("clip.mp4"), params).await?;
+    /// println!("uploaded {}", media.media_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [Docs](https://developer.x.com/en/docs/twitter-api/v1/media/upload-media/uploading-media/chunked-media-upload)
+    pub async fn upload_media(
+        &self,
+        path: &Path,
+        params: UploadParams,
+    ) -> Result<UploadedMedia, TweetyError> {
+        let io_error =
+            |err: std::io::Error| TweetyError::FileIOError(format!("{}: {}", path.display(), err));
+
+        let file = tokio::fs::File::open(path).await.map_err(io_error)?;
+        let total_bytes = file.metadata().await.map_err(io_error)?.len();
+
+        self.upload_segments(
+            reader_segments(file),
+            total_bytes,
+            media_type_from_extension(path),
+            &path.display().to_string(),
+            params,
+        )
+        .await
+    }
+
+    /// Same as [`TweetyClient::upload_media`], from a media already in memory.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use tweety_rs::api::uploads::UploadParams;
+    /// use tweety_rs::TweetyClient;
+    ///
+    /// # async fn example(client: TweetyClient, png: Vec<u8>) -> Result<(), tweety_rs::api::error::TweetyError> {
+    /// let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    /// let params = UploadParams::default().progress_channel(sender);
+    ///
+    /// let upload = client.upload_media_bytes(png, params);
+    /// let progress = async {
+    ///     while let Some(progress) = receiver.recv().await {
+    ///         println!("{:.0}%", progress.fraction() * 100.0);
+    ///     }
+    /// };
+    /// let (media, ()) = tokio::join!(upload, progress);
+    /// println!("uploaded {}", media?.media_id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn upload_media_bytes(
+        &self,
+        bytes: impl Into<Bytes>,
+        params: UploadParams,
+    ) -> Result<UploadedMedia, TweetyError> {
+        let bytes = bytes.into();
+        let total_bytes = bytes.len() as u64;
+        let segments: Vec<_> = (0..bytes.len())
+            .step_by(CHUNK_SIZE)
+            .map(|start| Ok(bytes.slice(start..bytes.len().min(start + CHUNK_SIZE))))
+            .collect();
+
+        self.upload_segments(
+            stream::iter(segments),
+            total_bytes,
+            None,
+            "the bytes",
+            params,
+        )
+        .await
+    }
+
+    /// Same as [`TweetyClient::upload_media`], reading `total_bytes` bytes from `reader`.
+    pub async fn upload_media_reader(
+        &self,
+        reader: impl AsyncRead + Send,
+        total_bytes: u64,
+        params: UploadParams,
+    ) -> Result<UploadedMedia, TweetyError> {
+        self.upload_segments(
+            reader_segments(reader),
+            total_bytes,
+            None,
+            "the reader",
+            params,
+        )
+        .await
+    }
+
+    /// Same as [`TweetyClient::upload_media`], from a stream of `total_bytes` bytes,
+    /// such as a `reqwest::Response::bytes_stream`.
+    pub async fn upload_media_stream<S, E>(
+        &self,
+        stream: S,
+        total_bytes: u64,
+        params: UploadParams,
+    ) -> Result<UploadedMedia, TweetyError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.upload_segments(
+            stream_segments(stream),
+            total_bytes,
+            None,
+            "the stream",
+            params,
+        )
+        .await
+    }
+
+    /// Checks the media from its first segment, then `INIT`, an `APPEND` per segment,
+    /// `FINALIZE` and [`TweetyClient::wait_for_processing`].
+    async fn upload_segments(
+        &self,
+        segments: impl Stream<Item = Result<Bytes, TweetyError>> + Send,
+        total_bytes: u64,
+        guessed_media_type: Option<&'static str>,
+        source: &str,
+        params: UploadParams,
+    ) -> Result<UploadedMedia, TweetyError> {
+        let mut segments = std::pin::pin!(segments);
+        let first_segment = segments.next().await.transpose()?;
+        let header = first_segment.as_deref().unwrap_or_default();
+
+        let media_type = params
+            .media_type
+            .clone()
+            .or_else(|| {
+                sniff_media_type(header)
+                    .or(guessed_media_type)
+                    .map(str::to_string)
+            })
+            .ok_or_else(|| {
+                TweetyError::InvalidParameter(format!(
+                    "unknown media type of {}, set UploadParams::media_type",
+                    source
+                ))
+            })?;
+        let media_category = params
+            .media_category
+            .unwrap_or_else(|| MediaCategory::for_media_type(&media_type));
+        if !params.skip_validation {
+            validate_media(header, total_bytes, &media_type, media_category)?;
+        }
+
+        let media = self
+            .media_upload_init(
+                total_bytes,
+                &media_type,
+                media_category,
+                &params.additional_owners,
+            )
+            .await?;
+
+        let mut segments = stream::iter(first_segment.map(Ok)).chain(segments);
+        let mut segment_index = 0;
+        let mut bytes_sent = 0;
+        while let Some(segment) = segments.next().await {
+            let segment = segment?;
+            bytes_sent += segment.len() as u64;
+            if bytes_sent > total_bytes {
+                break;
+            }
+            self.media_upload_append(media.media_id, segment_index, segment)
+                .await?;
+            segment_index += 1;
+            if let Some(progress) = &params.progress {
+                (progress.0)(UploadProgress {
+                    bytes_sent,
+                    total_bytes,
+                });
+            }
+        }
+        if bytes_sent != total_bytes {
+            return Err(TweetyError::InvalidParameter(format!(
+                "expected a media of {} bytes, got {}{}",
+                total_bytes,
+                if bytes_sent > total_bytes {
+                    "at least "
                 } else {
-                    let err = res.json().await;
-                    if let Ok(value) = err {
-                        return Ok(value);
+                    ""
+                },
+                bytes_sent
+            )));
+        }
+
+        let media = self.media_upload_finalize(media.media_id).await?;
+        let max_wait = params
+            .max_processing_wait
+            .unwrap_or(DEFAULT_MAX_PROCESSING_WAIT);
+        self.wait_for_processing(media, max_wait).await
+    }
+
+    /// `INIT` command: declares an upload and returns its media id.
+    pub async fn media_upload_init(
+        &self,
+        total_bytes: u64,
+        media_type: &str,
+        media_category: MediaCategory,
+        additional_owners: &[UserId],
+    ) -> Result<UploadedMedia, TweetyError> {
+        let owners: Vec<String> = additional_owners.iter().map(UserId::to_string).collect();
+        let mut query = QueryString::new();
+        query
+            .push("command", "INIT")
+            .push("total_bytes", total_bytes)
+            .push("media_type", media_type)
+            .push("media_category", media_category)
+            .push_list("additional_owners", owners.iter().map(String::as_str));
+
+        self.media_upload_command(Method::POST, query).await
+    }
+
+    /// `APPEND` command: sends the segment `segment_index` (starting at 0) of the media.
+    pub async fn media_upload_append(
+        &self,
+        media_id: MediaId,
+        segment_index: u32,
+        chunk: impl Into<Bytes>,
+    ) -> Result<(), TweetyError> {
+        let mut query = QueryString::new();
+        query
+            .push("command", "APPEND")
+            .push("media_id", media_id)
+            .push("segment_index", segment_index);
+        let url = query.append_to_url(&self.media_upload_url());
+        let url = Url::parse(&url).map_err(TweetyError::UrlParseError)?;
+
+        // Multipart bodies are not part of the OAuth 1.0a signature,
+        // only the query is.
+        let form =
+            multipart::Form::new().part("media", multipart::Part::bytes(Vec::from(chunk.into())));
+        self.auth.refresh_if_expired().await?;
+        let request = self
+            .auth
+            .authorize(&self.http, Method::POST, url, &[])?
+            .multipart(form);
+
+        self.execute(request)
+            .await?
+            .map_err(|err| TweetyError::NetworkError(err.to_string()))?
+            .into_result()?;
+        Ok(())
+    }
+
+    /// `FINALIZE` command: completes the upload. Videos and GIFs are then processed
+    /// asynchronously, see [`TweetyClient::wait_for_processing`].
+    pub async fn media_upload_finalize(
+        &self,
+        media_id: MediaId,
+    ) -> Result<UploadedMedia, TweetyError> {
+        let mut query = QueryString::new();
+        query.push("command", "FINALIZE").push("media_id", media_id);
+
+        self.media_upload_command(Method::POST, query).await
+    }
+
+    /// `STATUS` command: the processing state of a finalized upload.
+    pub async fn media_upload_status(
+        &self,
+        media_id: MediaId,
+    ) -> Result<UploadedMedia, TweetyError> {
+        let mut query = QueryString::new();
+        query.push("command", "STATUS").push("media_id", media_id);
+
+        self.media_upload_command(Method::GET, query).await
+    }
+
+    /// Polls `STATUS`, waiting `check_after_secs` between two polls, until the media
+    /// is processed. Fails with `TweetyError::MediaProcessingFailed` if processing fails,
+    /// or if the next poll would come after `max_wait`.
+    pub async fn wait_for_processing(
+        &self,
+        mut media: UploadedMedia,
+        max_wait: Duration,
+    ) -> Result<UploadedMedia, TweetyError> {
+        let started = tokio::time::Instant::now();
+        loop {
+            let Some(info) = &media.processing_info else {
+                return Ok(media);
+            };
+            match info.state {
+                ProcessingState::Succeeded => return Ok(media),
+                ProcessingState::Failed => {
+                    let reason = info
+                        .error
+                        .as_ref()
+                        .and_then(|error| error.message.clone().or_else(|| error.name.clone()))
+                        .unwrap_or_else(|| "unknown error".to_string());
+                    return Err(TweetyError::MediaProcessingFailed(format!(
+                        "media {}: {}",
+                        media.media_id, reason
+                    )));
+                }
+                ProcessingState::Pending | ProcessingState::InProgress => {
+                    let wait = info
+                        .check_after_secs
+                        .map(Duration::from_secs)
+                        .unwrap_or(DEFAULT_CHECK_AFTER);
+                    if started.elapsed() + wait > max_wait {
+                        return Err(TweetyError::MediaProcessingFailed(format!(
+                            "media {}: not processed after {:?}",
+                            media.media_id, max_wait
+                        )));
                     }
-                    Err(TweetyError::JsonParseError(
-                        "Error parsing multipart JSON".to_string(),
-                    ))
+                    tokio::time::sleep(wait).await;
+                    media = self.media_upload_status(media.media_id).await?;
                 }
             }
-            Err(err) => Err(TweetyError::NetworkError(err.to_string())),
         }
     }
+
+    fn media_upload_url(&self) -> String {
+        format!("{}/1.1/media/upload.json", self.upload_base_url)
+    }
+
+    /// Sends a command whose parameters are all in the query.
+    async fn media_upload_command(
+        &self,
+        method: Method,
+        query: QueryString,
+    ) -> Result<UploadedMedia, TweetyError> {
+        let url = query.append_to_url(&self.media_upload_url());
+        let value = self
+            .send_request_with::<()>(&url, method, &[], HeaderMap::new(), None)
+            .await?;
+
+        serde_json::from_value(value).map_err(|err| TweetyError::JsonParseError(err.to_string()))
+    }
+}
+
+/// Reads `reader` in segments of `CHUNK_SIZE` bytes.
+fn reader_segments(
+    reader: impl AsyncRead + Send,
+) -> impl Stream<Item = Result<Bytes, TweetyError>> + Send {
+    stream::try_unfold(Box::pin(reader), |mut reader| async move {
+        let mut segment = Vec::new();
+        (&mut reader)
+            .take(CHUNK_SIZE as u64)
+            .read_to_end(&mut segment)
+            .await
+            .map_err(|err| TweetyError::FileIOError(err.to_string()))?;
+        if segment.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some((Bytes::from(segment), reader)))
+        }
+    })
+}
+
+/// Regroups the items of `stream` in segments of `CHUNK_SIZE` bytes.
+fn stream_segments<S, E>(stream: S) -> impl Stream<Item = Result<Bytes, TweetyError>> + Send
+where
+    S: Stream<Item = Result<Bytes, E>> + Send,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let state = (Box::pin(stream), BytesMut::new(), false);
+    stream::try_unfold(state, |(mut stream, mut buffer, mut done)| async move {
+        while !done && buffer.len() < CHUNK_SIZE {
+            match stream.next().await {
+                Some(item) => {
+                    let item =
+                        item.map_err(|err| TweetyError::FileIOError(err.into().to_string()))?;
+                    buffer.extend_from_slice(&item);
+                }
+                None => done = true,
+            }
+        }
+        if buffer.is_empty() {
+            return Ok(None);
+        }
+        let segment = buffer.split_to(buffer.len().min(CHUNK_SIZE)).freeze();
+        Ok(Some((segment, (stream, buffer, done))))
+    })
 }
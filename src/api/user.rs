@@ -7,122 +7,45 @@
 //!
 
 use crate::api::client::TweetyClient;
-use crate::api::error::TweetyError;
-use crate::types::user::UserResponse;
+use crate::api::error::{ErrorDetail, TweetyError};
+use crate::api::fields::{Fields, QueryString};
+use crate::types::hydrated::Hydrated;
+use crate::types::id::UserId;
+use crate::types::response::{Includes, Response};
+use crate::types::user::{ApiResponse, User};
 use reqwest::Method;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 
-#[derive(Debug, Deserialize, Serialize)]
+/// Response of the endpoints returning a single user.
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct UserInfo {
-    pub data: UserResponse,
+    pub data: User,
+    pub includes: Option<Includes>,
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "snake_case")]
-pub enum Expansions {
-    PinnedTweetId,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "snake_case")]
-pub enum TweetFields {
-    Attachments,
-    AuthorId,
-    ContextAnnotations,
-    ConversationId,
-    CreatedAt,
-    EditControls,
-    Entities,
-    Geo,
-    Id,
-    InReplyToUserId,
-    Lang,
-    NonPublicMetrics,
-    PublicMetrics,
-    OrganicMetrics,
-    PromotedMetrics,
-    PossiblySensitive,
-    ReferencedTweets,
-    ReplySettings,
-    Source,
-    Text,
-    Withheld,
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "snake_case")]
-pub enum UserFields {
-    CreatedAt,
-    Description,
-    Entities,
-    Id,
-    Location,
-    MostRecentTweetId,
-    Name,
-    PinnedTweetId,
-    ProfileImageUrl,
-    Protected,
-    PublicMetrics,
-    Url,
-    Username,
-    Verified,
-    VerifiedType,
-    Withheld,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct UserQueryParams {
-    pub expansions: Option<Expansions>,
-    pub tweet_fields: Option<Vec<TweetFields>>,
-    pub user_fields: Option<Vec<UserFields>>,
+impl UserInfo {
+    /// Indexes `includes` to follow the pinned Tweet of the user.
+    /// See [`crate::types::hydrated`].
+    pub fn hydrate(self) -> Hydrated<User> {
+        Hydrated::new(vec![self.data], self.includes, None).with_errors(self.errors)
+    }
 }
 
-impl UserQueryParams {
-    /// Construct querys params from the provided optional parameters
-    pub fn construct_query_string(&self) -> String {
-        let mut query = vec![];
-
-        if let Some(expansion) = &self.expansions {
-            let serialized_expansion = serde_json::to_string(expansion)
-                .unwrap()
-                .trim_matches('"')
-                .to_string();
-            query.push(format!("expansions={}", serialized_expansion));
-        }
-
-        if let Some(tweet_fields) = &self.tweet_fields {
-            let fields = tweet_fields
-                .iter()
-                .map(|f| {
-                    serde_json::to_string(f)
-                        .unwrap()
-                        .trim_matches('"')
-                        .to_string()
-                })
-                .collect::<Vec<_>>()
-                .join(",");
-            query.push(format!("tweet.fields={}", fields));
-        }
+/// The user endpoints only take a field selection, see [`crate::api::fields`].
+pub type UserQueryParams = Fields;
 
-        if let Some(user_fields) = &self.user_fields {
-            let fields = user_fields
-                .iter()
-                .map(|f| {
-                    serde_json::to_string(f)
-                        .unwrap()
-                        .trim_matches('"')
-                        .to_string()
-                })
-                .collect::<Vec<_>>()
-                .join(",");
-            query.push(format!("user.fields={}", fields));
-        }
-        query.join("&")
+fn user_query(params: Option<UserQueryParams>) -> QueryString {
+    let mut query = QueryString::new();
+    if let Some(params) = params {
+        params.append_to(&mut query);
     }
+    query
 }
-/// Users lookup
-/// API reference index
+
+// Users lookup
+// API reference index
 
 impl TweetyClient {
     /// Fetches detailed information about a user specified by their ID.
@@ -142,10 +65,16 @@ impl TweetyClient {
     /// use tweety_rs::api::client::TweetyClient;
     /// use tweety_rs::api::error::TweetyError;
     /// use tweety_rs::api::user::UserQueryParams;
+    /// use tweety_rs::types::id::UserId;
     ///
     /// async fn example() -> Result<(), TweetyError> {
-    ///     let client = TweetyClient::new("your_consumer_key","your_access_token","your_consumer_key_secret","your_access_token_secret");
-    ///     let user_id = "12345";
+    ///     let client = TweetyClient::builder()
+    ///         .consumer_key("your_consumer_key")
+    ///         .consumer_key_secret("your_consumer_key_secret")
+    ///         .access_token("your_access_token")
+    ///         .access_token_secret("your_access_token_secret")
+    ///         .build()?;
+    ///     let user_id = UserId::new(12345);
     ///     let params = None;
     ///
     ///     let response = client.get_user_by_id(user_id, params).await?;
@@ -155,16 +84,12 @@ impl TweetyClient {
     /// ```
     pub async fn get_user_by_id(
         &self,
-        user_id: &str,
+        user_id: UserId,
         params: Option<UserQueryParams>,
-    ) -> Result<Value, TweetyError> {
-        let query_string = if let Some(params) = params {
-            params.construct_query_string()
-        } else {
-            String::new()
-        };
-        let url = format!("https://api.x.com/2/users/{}?{}", user_id, query_string);
-        self.send_request::<()>(&url, Method::GET, None).await
+    ) -> Result<Response<UserInfo>, TweetyError> {
+        let url =
+            user_query(params).append_to_url(&format!("{}/2/users/{}", self.api_base_url, user_id));
+        self.request::<_, ()>(&url, Method::GET, None).await
     }
 
     /// <https://developer.x.com/en/docs/x-api/users/lookup/api-reference/get-users#tab1>
@@ -172,66 +97,61 @@ impl TweetyClient {
     /// Fetches detailed information about one or more users specified by their IDs.
     pub async fn get_users(
         &self,
-        ids: Vec<String>,
+        ids: &[UserId],
         params: Option<UserQueryParams>,
-    ) -> Result<Value, TweetyError> {
-        let ids_string = ids.join(",");
-        let query_string = if let Some(params) = params {
-            params.construct_query_string()
-        } else {
-            String::new()
-        };
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        let ids: Vec<String> = ids.iter().map(UserId::to_string).collect();
+        let mut query = QueryString::new();
+        query.push_list("ids", ids.iter().map(String::as_str));
+        if let Some(params) = params {
+            params.append_to(&mut query);
+        }
 
-        let url = format!(
-            "https://api.x.com/2/users?ids={}&{}",
-            ids_string, query_string
-        );
-        self.send_request::<()>(&url, Method::GET, None).await
+        let url = query.append_to_url(&format!("{}/2/users", self.api_base_url));
+        self.request::<_, ()>(&url, Method::GET, None).await
     }
     ///  Returns a variety of information about one or more users specified by their usernames.
-    ///  Required 	string	A comma separated list of user IDs. Up to 100 are allowed in a single request.
+    ///  Required string A comma separated list of user IDs. Up to 100 are allowed in a single request.
     /// Make sure to not include a space between commas and fields.
-    pub async fn get_users_by_username(&self, username: &[&str]) -> Result<Value, TweetyError> {
+    pub async fn get_users_by_username(
+        &self,
+        username: &[&str],
+    ) -> Result<Response<UserInfo>, TweetyError> {
         let url = format!(
-            "https://api.x.com/2/users/by/username/{}",
+            "{}/2/users/by/username/{}",
+            self.api_base_url,
             username.join(",")
         );
-        self.send_request::<()>(&url, Method::GET, None).await
+        self.request::<_, ()>(&url, Method::GET, None).await
     }
     /// GET /2/users/by
     /// Returns a variety of information about one or more users specified by their usernames.
     /// usernames
-    ///  Required 	string A comma separated list of Twitter usernames (handles).
+    ///  Required string A comma separated list of Twitter usernames (handles).
     /// Up to 100 are allowed in a single request. Make sure to not include a space between commas and fields.
     pub async fn get_users_by_usernames(
         &self,
         user_names: &[&str],
         params: Option<UserQueryParams>,
-    ) -> Result<Value, TweetyError> {
-        let query_string = if let Some(params) = params {
-            params.construct_query_string()
-        } else {
-            String::new()
-        };
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        let mut query = QueryString::new();
+        query.push_list("usernames", user_names.iter().copied());
+        if let Some(params) = params {
+            params.append_to(&mut query);
+        }
 
-        let url = format!(
-            "https://api.x.com/2/users/by/?username={}&{}",
-            user_names.join(","),
-            query_string
-        );
+        let url = query.append_to_url(&format!("{}/2/users/by", self.api_base_url));
 
-        self.send_request::<()>(&url, Method::GET, None).await
+        self.request::<_, ()>(&url, Method::GET, None).await
     }
     /// Returns information about an authorized user.
     /// <https://developer.x.com/en/docs/x-api/users/lookup/api-reference/get-users-me#>
-    pub async fn get_user_me(&self, params: Option<UserQueryParams>) -> Result<Value, TweetyError> {
-        let query_string = if let Some(params) = params {
-            params.construct_query_string()
-        } else {
-            String::new()
-        };
-        let url = format!("https://api.x.com/2/users/me?{}", query_string);
+    pub async fn get_user_me(
+        &self,
+        params: Option<UserQueryParams>,
+    ) -> Result<Response<UserInfo>, TweetyError> {
+        let url = user_query(params).append_to_url(&format!("{}/2/users/me", self.api_base_url));
 
-        self.send_request::<()>(&url, Method::GET, None).await
+        self.request::<_, ()>(&url, Method::GET, None).await
     }
 }
@@ -37,25 +37,52 @@
 //! - Access Token Secret: Your access token secret
 //!
 //! You can obtain these from the [Twitter Developer portal](https://developer.x.com/en/portal/projects-and-apps).
+//! The portal only lists the access token of the app owner's account; for other users, walk them
+//! through the three-legged flow in [`api::oauth1`].
+//!
+//! [`api::credentials::Credentials`] loads them from the environment (or a `.env` file), or picks
+//! one of several accounts from a TOML/JSON profiles file. Secrets are redacted when a client is
+//! printed with `{:?}` or serialized.
+//!
+//! Endpoints that support OAuth 2.0 App-only (e.g. `full_archive_search`) can also be called with
+//! a bearer token: set it with `TweetyClientBuilder::bearer_token`, or let
+//! `TweetyClientBuilder::build_app_only` exchange the consumer key and secret for one.
 //!
 //! ## Usage
-//! To get started, you'll need to create a `TweetyClient` with your Twitter API credentials.
+//! To get started, you'll need to build a `TweetyClient` with your Twitter API credentials.
+//! `TweetyClient::builder()` also accepts timeouts, a proxy, a user agent or an existing `reqwest::Client`.
+//!
+//! Paginated endpoints have a `paginate_*` counterpart returning a `futures::Stream` of pages or
+//! items, see [`api::pagination`]. Expansions and fields are selected with [`api::fields::Fields`],
+//! shared by every endpoint. Responses with expansions can be `hydrate`d to follow them directly,
+//! e.g. `tweet.author()`, see [`types::hydrated`].
+//!
+//! Every endpoint is also a method of the [`api::twitter_api::TwitterApi`] trait, implemented by
+//! the client. Code written against the trait can be unit-tested with the in-memory fake of the
+//! `testing` module, enabled by the `testing` cargo feature.
+//!
+//! Requests can also be recorded to a JSON cassette once and replayed offline afterwards,
+//! with the credentials scrubbed, see [`api::cassette`].
+//!
+//! Dates are parsed into [`types::timestamp::Timestamp`]. Enable the `chrono` or `time` cargo
+//! feature to convert them to and from the date-time types of those crates.
 //!
 //! ### Example: How to post a tweet
 //!
-//! ```rust
-//! use tweety_rs::client::TweetyClient;
+//! ```rust,no_run
+//! use tweety_rs::TweetyClient;
 //!
-//! fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     let client = TweetyClient::new(
-//!         "your_consumer_key",
-//!         "your_consumer_key_secret",
-//!         "your_access_token",
-//!         "your_access_token_secret",
-//!     );
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = TweetyClient::builder()
+//!         .consumer_key("your_consumer_key")
+//!         .consumer_key_secret("your_consumer_key_secret")
+//!         .access_token("your_access_token")
+//!         .access_token_secret("your_access_token_secret")
+//!         .build()?;
 //!
 //!     // Post a tweet
-//!     client.post_tweet("Hello, Twitter!", None).unwrap();
+//!     client.post_tweet("Hello, Twitter!", None).await?;
 //!
 //!     Ok(())
 //! }
@@ -65,35 +92,32 @@
 //!
 //! Make a tweet with an image appended to your tweet.
 //!
-//! ```rust
+//! ```rust,no_run
+//! use std::path::Path;
 //! use tweety_rs::{
 //!     types::tweet::{Media, PostTweetParams},
 //!     TweetyClient,
 //! };
-//! use tokio;
 //!
 //! #[tokio::main]
-//! async fn main() {
-//!     let client = TweetyClient::new(
-//!         "your_consumer_key",
-//!         "your_consumer_key_secret",
-//!         "your_access_token",
-//!         "your_access_token_secret",
-//!     );
-//!
-//!     let path = Path::new(&file_path); // path of the image to be uploaded
-//!
-//!     match client.upload_file(&path).await {
-//!         Ok(value) => {
-//!             let media_string = value.to_string();
-//!             let message = format!("#{}", self.file_content.1);
-//!
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = TweetyClient::builder()
+//!         .consumer_key("your_consumer_key")
+//!         .consumer_key_secret("your_consumer_key_secret")
+//!         .access_token("your_access_token")
+//!         .access_token_secret("your_access_token_secret")
+//!         .build()?;
+//!
+//!     let path = Path::new("ferris.png"); // path of the image to be uploaded
+//!
+//!     match client.upload_file(path).await {
+//!         Ok(media_id) => {
 //!             let params = PostTweetParams {
 //!                 direct_message_deep_link: None,
 //!                 for_super_followers_only: None,
 //!                 geo: None,
 //!                 media: Some(Media {
-//!                     media_ids: vec![media_string].into(),
+//!                     media_ids: Some(vec![media_id]),
 //!                     tagged_user_ids: None,
 //!                 }),
 //!                 poll: None,
@@ -102,7 +126,7 @@
 //!                 reply_settings: None,
 //!             };
 //!
-//!             match client.post_tweet(&message, Some(params)).await {
+//!             match client.post_tweet("#Rust", Some(params)).await {
 //!                 Ok(status_code) => {
 //!                     println!("Posted tweet: {:?}", status_code);
 //!                 }
@@ -115,24 +139,34 @@
 //!             println!("Error uploading images{}", err);
 //!         }
 //!     }
+//!
+//!     Ok(())
 //! }
 //! ```
 //!
+//! Videos and GIFs go through the chunked upload instead, with
+//! [`TweetyClient::upload_media`], which waits for the media to be processed.
+//! Media in memory, readers and streams are uploaded the same way with
+//! `upload_media_bytes`, `upload_media_reader` and `upload_media_stream`,
+//! optionally reporting their progress, see [`api::uploads::UploadParams`].
+//!
 //! ### Example: Retweeting
 //!
-//! ```rust
-//! use tweety_rs::client::TweetyClient;
+//! ```rust,no_run
+//! use tweety_rs::types::id::{TweetId, UserId};
+//! use tweety_rs::TweetyClient;
 //!
-//! fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     let client = TweetyClient::new(
-//!         "your_consumer_key",
-//!         "your_consumer_key_secret",
-//!         "your_access_token",
-//!         "your_access_token_secret",
-//!     );
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let client = TweetyClient::builder()
+//!         .consumer_key("your_consumer_key")
+//!         .consumer_key_secret("your_consumer_key_secret")
+//!         .access_token("your_access_token")
+//!         .access_token_secret("your_access_token_secret")
+//!         .build()?;
 //!
-//!     // Retweet a tweet by ID
-//!     client.retweet("1234567890")?;
+//!     // Retweet a tweet by ID, as the user of the access token
+//!     client.retweet(UserId::new(2244994945), TweetId::new(1234567890)).await?;
 //!
 //!     Ok(())
 //! }
@@ -142,35 +176,41 @@
 //!
 //! Here’s an example of how to use the `get_direct_messages` function:
 //!
-//! ```rust
-//! use tweety_rs::{client::TweetyClient,error::TweetyError,direct_messages::QueryParams};
-//! use serde_json::Value;
+//! ```rust,no_run
+//! use tweety_rs::api::direct_messages::{EventType, QueryParams};
+//! use tweety_rs::api::fields::{DmEventField, Expansion, Fields, MediaField, TweetField, UserField};
+//! use tweety_rs::api::error::TweetyError;
+//! use tweety_rs::TweetyClient;
 //!
 //! #[tokio::main]
 //! async fn main() -> Result<(), TweetyError> {
 //!     // Create an instance of TweetyClient
-//!     let client = TweetyClient::new(
-//!         "your_consumer_key",
-//!         "your_consumer_key_secret",
-//!         "your_access_token",
-//!         "your_access_token_secret",
-//!     );
+//!     let client = TweetyClient::builder()
+//!         .consumer_key("your_consumer_key")
+//!         .consumer_key_secret("your_consumer_key_secret")
+//!         .access_token("your_access_token")
+//!         .access_token_secret("your_access_token_secret")
+//!         .build()?;
 //!
 //!     // Define query parameters
 //!     let params = QueryParams {
-//!         dm_event_fields: Some(vec![DMEventField::Id, DMEventField::Text]),
 //!         event_types: Some(vec![EventType::MessageCreate]),
-//!         expansions: Some(vec![Expansion::SenderId]),
 //!         max_results: Some(50),
-//!         media_fields: Some(vec![MediaField::Url, MediaField::Type]),
-//!         tweet_fields: Some(vec![TweetField::CreatedAt, TweetField::Text]),
-//!         user_fields: Some(vec![UserField::Username, UserField::Verified]),
+//!         pagination_token: None,
+//!         fields: Fields::new()
+//!             .dm_event_fields([DmEventField::Id, DmEventField::Text])
+//!             .expansions([Expansion::SenderId])
+//!             .media_fields([MediaField::Url, MediaField::Type])
+//!             .tweet_fields([TweetField::CreatedAt, TweetField::Text])
+//!             .user_fields([UserField::Username, UserField::Verified]),
 //!     };
 //!
 //!     // Fetch direct messages
 //!     match client.get_direct_messages(params).await {
 //!         Ok(response) => {
-//!             println!("Direct messages: {}", response);
+//!             for event in &response.data {
+//!                 println!("{}: {:?}", event.id, event.text);
+//!             }
 //!         }
 //!         Err(e) => {
 //!             eprintln!("Error fetching direct messages: {:?}", e);
@@ -188,6 +228,11 @@
 //! - **Free Access**: 50 requests every 24 hours.
 //! - **Elevated Access**: 300 requests per 15-minute window per user.
 //!
+//! The client records the `x-rate-limit-*` headers of every response per endpoint, see
+//! `TweetyClient::rate_limit`. Build the client with
+//! `.rate_limit_policy(RateLimitPolicy::WaitAndRetry { max_wait })` to sleep until the
+//! window resets on HTTP 429 instead of failing.
+//!
 //! ## Modules
 //!
 //! - bookmark - Manage bookmarks
@@ -198,6 +243,8 @@
 //! - following - Manage followings
 //! - hide_replies - Hide replies to tweets
 //! - like - Like tweets
+//! - media_metadata - Alt text, content warnings and subtitles of uploaded media
+//! - media_validation - Detect media types and check them against the upload limits
 //! - mentions - Manage mentions
 //! - retweets - Retweet tweets
 //! - search - Search tweets and users
@@ -227,4 +274,8 @@
 pub mod api;
 pub mod types;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use api::builder::TweetyClientBuilder;
 pub use api::client::TweetyClient;
@@ -0,0 +1,584 @@
+//! An in-memory [`TwitterApi`] for unit tests, enabled by the `testing` cargo feature.
+//!
+//! [`FakeTwitterApi`] never touches the network: every method records its call and
+//! returns the next response queued for it with [`FakeTwitterApi::respond`] or
+//! [`FakeTwitterApi::fail`], by [`ApiMethod`]. Responses are given as the JSON the API
+//! would return. A call with no response queued panics, as the test is missing one.
+//!
+//! ```rust
+//! use serde_json::json;
+//! use tweety_rs::api::twitter_api::TwitterApi;
+//! use tweety_rs::testing::{ApiMethod, FakeTwitterApi};
+//! use tweety_rs::types::id::{TweetId, UserId};
+//!
+//! # async fn example() {
+//! let api = FakeTwitterApi::new();
+//! api.respond(ApiMethod::Retweet, json!({"data": {"retweeted": true}}));
+//!
+//! let (user_id, tweet_id) = (UserId::new(2244994945), TweetId::new(1460323737035677698));
+//! let response = api.retweet(user_id, tweet_id).await.unwrap();
+//! assert!(response.data.retweeted);
+//! assert_eq!(
+//!     api.calls_to(ApiMethod::Retweet)[0].args,
+//!     ["2244994945", "1460323737035677698"]
+//! );
+//! assert_eq!(api.pending_responses(), 0);
+//! # }
+//! ```
+
+use crate::api::bookmark::{BookmarkParams, BookmarkResponse};
+use crate::api::direct_messages::{self, DmEventsResponse};
+use crate::api::error::TweetyError;
+use crate::api::fields::query_enum;
+use crate::api::followers::UserFollowersResponse;
+use crate::api::following::{FollowResponse, UnfollowResponse, UserFollowingResponse};
+use crate::api::hide_replies::HideReplyResponse;
+use crate::api::like::LikeResponse;
+use crate::api::media_metadata::{LanguageCode, MediaMetadata, Subtitles};
+use crate::api::mentions::{self, MentionsResponse};
+use crate::api::retweets::{RetweetQueryParams, RetweetResponse};
+use crate::api::search::{self, FullArchiveSearchResponse, RecentSearchResponse};
+use crate::api::tweet::{DeleteResponse, Ids, PostTweetResponseData};
+use crate::api::twitter_api::TwitterApi;
+use crate::api::uploads::{Media, UploadParams, UploadedMedia};
+use crate::api::user::{UserInfo, UserQueryParams};
+use crate::types::id::{DmConversationId, MediaId, TweetId, UserId};
+use crate::types::response::Response;
+use crate::types::tweet::{PostTweetParams, TweetResponse, TweetsResponse};
+use crate::types::user::ApiResponse;
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::Mutex;
+
+query_enum! {
+    /// The [`TwitterApi`] methods, to queue responses for and to find calls by.
+    pub enum ApiMethod {
+        GetTweet => "get_tweet",
+        GetTweetInfo => "get_tweet_info",
+        PostTweet => "post_tweet",
+        EditTweet => "edit_tweet",
+        DeleteTweet => "delete_tweet",
+        GetUserById => "get_user_by_id",
+        GetUsers => "get_users",
+        GetUsersByUsername => "get_users_by_username",
+        GetUsersByUsernames => "get_users_by_usernames",
+        GetUserMe => "get_user_me",
+        GetUserFollowers => "get_user_followers",
+        FollowUser => "follow_user",
+        UnfollowUser => "unfollow_user",
+        GetUsersFollowing => "get_users_following",
+        UnlikeTweet => "unlike_tweet",
+        GetUsersWhoLikedAPost => "get_users_who_liked_a_post",
+        GetPostsLikedByAUser => "get_posts_liked_by_a_user",
+        LikeAPost => "like_a_post",
+        FetchRetweeters => "fetch_retweeters",
+        Retweet => "retweet",
+        Unretweet => "unretweet",
+        FetchRetweets => "fetch_retweets",
+        GetUserBookmark => "get_user_bookmark",
+        BookmarkPost => "bookmark_post",
+        DeleteBookmark => "delete_bookmark",
+        HideTweet => "hide_tweet",
+        GetUsersMentions => "get_users_mentions",
+        RecentSearch => "recent_search",
+        FullArchiveSearch => "full_archive_search",
+        GetDirectMessages => "get_direct_messages",
+        GetDmEventsWithParticipant => "get_dm_events_with_participant",
+        GetDmEventsInConversation => "get_dm_events_in_conversation",
+        UploadFile => "upload_file",
+        UploadMedia => "upload_media",
+        UploadMediaBytes => "upload_media_bytes",
+        CreateMediaMetadata => "create_media_metadata",
+        CreateMediaSubtitles => "create_media_subtitles",
+        DeleteMediaSubtitles => "delete_media_subtitles",
+    }
+}
+
+/// A call made to a [`FakeTwitterApi`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub method: ApiMethod,
+    /// The arguments in order: ids, texts and paths as they were passed,
+    /// the other arguments (parameters, lists) in their `Debug` form.
+    pub args: Vec<String>,
+}
+
+/// A [`TwitterApi`] returning canned responses, see the [module documentation](self).
+#[derive(Debug, Default)]
+pub struct FakeTwitterApi {
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    calls: Vec<Call>,
+    responses: HashMap<ApiMethod, VecDeque<Result<Value, TweetyError>>>,
+}
+
+impl FakeTwitterApi {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues the body returned by the next call to `method`: the JSON the API
+    /// would return, or the typed response itself.
+    /// Responses queued for the same method are returned in order.
+    /// A body that can't be serialized is returned as a `TweetyError::JsonParseError`.
+    pub fn respond(&self, method: ApiMethod, body: impl Serialize) -> &Self {
+        let body =
+            serde_json::to_value(body).map_err(|err| TweetyError::JsonParseError(err.to_string()));
+        self.enqueue(method, body)
+    }
+
+    /// Queues the error returned by the next call to `method`.
+    pub fn fail(&self, method: ApiMethod, error: impl Into<TweetyError>) -> &Self {
+        self.enqueue(method, Err(error.into()))
+    }
+
+    /// Every call made so far, in order.
+    pub fn calls(&self) -> Vec<Call> {
+        self.state().calls.clone()
+    }
+
+    pub fn calls_to(&self, method: ApiMethod) -> Vec<Call> {
+        self.state()
+            .calls
+            .iter()
+            .filter(|call| call.method == method)
+            .cloned()
+            .collect()
+    }
+
+    /// Responses queued and not returned yet.
+    pub fn pending_responses(&self) -> usize {
+        self.state().responses.values().map(VecDeque::len).sum()
+    }
+
+    fn enqueue(&self, method: ApiMethod, response: Result<Value, TweetyError>) -> &Self {
+        self.state()
+            .responses
+            .entry(method)
+            .or_default()
+            .push_back(response);
+        self
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        // A panicking test must not hide the calls from the others.
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Records the call and pops its response.
+    ///
+    /// # Panics
+    ///
+    /// If no response is queued for `method`: the test is missing a `respond` or `fail`.
+    fn call(&self, method: ApiMethod, args: Vec<String>) -> Result<Value, TweetyError> {
+        let mut state = self.state();
+        state.calls.push(Call { method, args });
+        let response = state
+            .responses
+            .get_mut(&method)
+            .and_then(VecDeque::pop_front);
+        drop(state);
+        response.unwrap_or_else(|| {
+            panic!(
+                "no response queued for FakeTwitterApi::{}, queue one with `respond` or `fail`",
+                method
+            )
+        })
+    }
+
+    fn parse<T: DeserializeOwned>(
+        &self,
+        method: ApiMethod,
+        args: Vec<String>,
+    ) -> Result<T, TweetyError> {
+        let body = self.call(method, args)?;
+        serde_json::from_value(body).map_err(|err| TweetyError::JsonParseError(err.to_string()))
+    }
+
+    fn response<T: DeserializeOwned>(
+        &self,
+        method: ApiMethod,
+        args: Vec<String>,
+    ) -> Result<Response<T>, TweetyError> {
+        Response::from_value(self.call(method, args)?)
+    }
+}
+
+fn debug(arg: impl Debug) -> String {
+    format!("{:?}", arg)
+}
+
+impl TwitterApi for FakeTwitterApi {
+    async fn get_tweet(&self, tweet_id: Ids) -> Result<Response<TweetsResponse>, TweetyError> {
+        self.response(ApiMethod::GetTweet, vec![tweet_id.to_string()])
+    }
+
+    async fn get_tweet_info(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<TweetResponse>, TweetyError> {
+        self.response(ApiMethod::GetTweetInfo, vec![tweet_id.to_string()])
+    }
+
+    async fn post_tweet(
+        &self,
+        message: &str,
+        body_params: Option<PostTweetParams>,
+    ) -> Result<PostTweetResponseData, TweetyError> {
+        self.parse(
+            ApiMethod::PostTweet,
+            vec![message.to_string(), debug(body_params)],
+        )
+    }
+
+    async fn edit_tweet(
+        &self,
+        message: &str,
+        tweet_id: TweetId,
+    ) -> Result<Response<PostTweetResponseData>, TweetyError> {
+        self.response(
+            ApiMethod::EditTweet,
+            vec![message.to_string(), tweet_id.to_string()],
+        )
+    }
+
+    async fn delete_tweet(&self, tweet_id: TweetId) -> Result<DeleteResponse, TweetyError> {
+        self.parse(ApiMethod::DeleteTweet, vec![tweet_id.to_string()])
+    }
+
+    async fn get_user_by_id(
+        &self,
+        user_id: UserId,
+        params: Option<UserQueryParams>,
+    ) -> Result<Response<UserInfo>, TweetyError> {
+        self.response(
+            ApiMethod::GetUserById,
+            vec![user_id.to_string(), debug(params)],
+        )
+    }
+
+    async fn get_users(
+        &self,
+        ids: &[UserId],
+        params: Option<UserQueryParams>,
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        self.response(ApiMethod::GetUsers, vec![debug(ids), debug(params)])
+    }
+
+    async fn get_users_by_username(
+        &self,
+        username: &[&str],
+    ) -> Result<Response<UserInfo>, TweetyError> {
+        self.response(ApiMethod::GetUsersByUsername, vec![debug(username)])
+    }
+
+    async fn get_users_by_usernames(
+        &self,
+        user_names: &[&str],
+        params: Option<UserQueryParams>,
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        self.response(
+            ApiMethod::GetUsersByUsernames,
+            vec![debug(user_names), debug(params)],
+        )
+    }
+
+    async fn get_user_me(
+        &self,
+        params: Option<UserQueryParams>,
+    ) -> Result<Response<UserInfo>, TweetyError> {
+        self.response(ApiMethod::GetUserMe, vec![debug(params)])
+    }
+
+    async fn get_user_followers(
+        &self,
+        user_id: UserId,
+        params: Option<UserQueryParams>,
+    ) -> Result<UserFollowersResponse, TweetyError> {
+        self.parse(
+            ApiMethod::GetUserFollowers,
+            vec![user_id.to_string(), debug(params)],
+        )
+    }
+
+    async fn follow_user(
+        &self,
+        user_id: UserId,
+        target_user_id: UserId,
+    ) -> Result<FollowResponse, TweetyError> {
+        self.parse(
+            ApiMethod::FollowUser,
+            vec![user_id.to_string(), target_user_id.to_string()],
+        )
+    }
+
+    async fn unfollow_user(
+        &self,
+        source_userid: UserId,
+        target_userid: UserId,
+    ) -> Result<UnfollowResponse, TweetyError> {
+        self.parse(
+            ApiMethod::UnfollowUser,
+            vec![source_userid.to_string(), target_userid.to_string()],
+        )
+    }
+
+    async fn get_users_following(
+        &self,
+        user_id: UserId,
+        query: Option<UserQueryParams>,
+    ) -> Result<UserFollowingResponse, TweetyError> {
+        self.parse(
+            ApiMethod::GetUsersFollowing,
+            vec![user_id.to_string(), debug(query)],
+        )
+    }
+
+    async fn unlike_tweet(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<LikeResponse>, TweetyError> {
+        self.response(
+            ApiMethod::UnlikeTweet,
+            vec![user_id.to_string(), tweet_id.to_string()],
+        )
+    }
+
+    async fn get_users_who_liked_a_post(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        self.response(ApiMethod::GetUsersWhoLikedAPost, vec![tweet_id.to_string()])
+    }
+
+    async fn get_posts_liked_by_a_user(
+        &self,
+        user_id: UserId,
+    ) -> Result<Response<TweetsResponse>, TweetyError> {
+        self.response(ApiMethod::GetPostsLikedByAUser, vec![user_id.to_string()])
+    }
+
+    async fn like_a_post(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<LikeResponse>, TweetyError> {
+        self.response(
+            ApiMethod::LikeAPost,
+            vec![user_id.to_string(), tweet_id.to_string()],
+        )
+    }
+
+    async fn fetch_retweeters(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<ApiResponse>, TweetyError> {
+        self.response(ApiMethod::FetchRetweeters, vec![tweet_id.to_string()])
+    }
+
+    async fn retweet(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<RetweetResponse>, TweetyError> {
+        self.response(
+            ApiMethod::Retweet,
+            vec![user_id.to_string(), tweet_id.to_string()],
+        )
+    }
+
+    async fn unretweet(
+        &self,
+        user_id: UserId,
+        source_tweet_id: TweetId,
+    ) -> Result<Response<RetweetResponse>, TweetyError> {
+        self.response(
+            ApiMethod::Unretweet,
+            vec![user_id.to_string(), source_tweet_id.to_string()],
+        )
+    }
+
+    async fn fetch_retweets(
+        &self,
+        tweet_id: TweetId,
+        params: Option<RetweetQueryParams>,
+    ) -> Result<Response<TweetsResponse>, TweetyError> {
+        self.response(
+            ApiMethod::FetchRetweets,
+            vec![tweet_id.to_string(), debug(params)],
+        )
+    }
+
+    async fn get_user_bookmark(
+        &self,
+        user_id: UserId,
+        params: Option<BookmarkParams>,
+    ) -> Result<Response<TweetsResponse>, TweetyError> {
+        self.response(
+            ApiMethod::GetUserBookmark,
+            vec![user_id.to_string(), debug(params)],
+        )
+    }
+
+    async fn bookmark_post(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<BookmarkResponse>, TweetyError> {
+        self.response(
+            ApiMethod::BookmarkPost,
+            vec![user_id.to_string(), tweet_id.to_string()],
+        )
+    }
+
+    async fn delete_bookmark(
+        &self,
+        user_id: UserId,
+        tweet_id: TweetId,
+    ) -> Result<Response<BookmarkResponse>, TweetyError> {
+        self.response(
+            ApiMethod::DeleteBookmark,
+            vec![user_id.to_string(), tweet_id.to_string()],
+        )
+    }
+
+    async fn hide_tweet(
+        &self,
+        tweet_id: TweetId,
+    ) -> Result<Response<HideReplyResponse>, TweetyError> {
+        self.response(ApiMethod::HideTweet, vec![tweet_id.to_string()])
+    }
+
+    async fn get_users_mentions(
+        &self,
+        user_id: UserId,
+        query_params: Option<mentions::QueryParams>,
+    ) -> Result<Response<MentionsResponse>, TweetyError> {
+        self.response(
+            ApiMethod::GetUsersMentions,
+            vec![user_id.to_string(), debug(query_params)],
+        )
+    }
+
+    async fn recent_search(
+        &self,
+        query: &str,
+        query_params: Option<search::QueryParams>,
+    ) -> Result<Response<RecentSearchResponse>, TweetyError> {
+        self.response(
+            ApiMethod::RecentSearch,
+            vec![query.to_string(), debug(query_params)],
+        )
+    }
+
+    async fn full_archive_search(
+        &self,
+        query: &str,
+        query_params: Option<search::QueryParams>,
+    ) -> Result<Response<FullArchiveSearchResponse>, TweetyError> {
+        self.response(
+            ApiMethod::FullArchiveSearch,
+            vec![query.to_string(), debug(query_params)],
+        )
+    }
+
+    async fn get_direct_messages(
+        &self,
+        params: direct_messages::QueryParams,
+    ) -> Result<Response<DmEventsResponse>, TweetyError> {
+        self.response(ApiMethod::GetDirectMessages, vec![debug(params)])
+    }
+
+    async fn get_dm_events_with_participant(
+        &self,
+        participant_id: UserId,
+        params: direct_messages::QueryParams,
+    ) -> Result<Response<DmEventsResponse>, TweetyError> {
+        self.response(
+            ApiMethod::GetDmEventsWithParticipant,
+            vec![participant_id.to_string(), debug(params)],
+        )
+    }
+
+    async fn get_dm_events_in_conversation(
+        &self,
+        dm_conversation_id: &DmConversationId,
+        params: direct_messages::QueryParams,
+    ) -> Result<Response<DmEventsResponse>, TweetyError> {
+        self.response(
+            ApiMethod::GetDmEventsInConversation,
+            vec![dm_conversation_id.to_string(), debug(params)],
+        )
+    }
+
+    async fn upload_file(&self, path: &Path) -> Result<MediaId, TweetyError> {
+        let media: Media = self.parse(ApiMethod::UploadFile, vec![path.display().to_string()])?;
+        Ok(media.media_id)
+    }
+
+    async fn upload_media(
+        &self,
+        path: &Path,
+        params: UploadParams,
+    ) -> Result<UploadedMedia, TweetyError> {
+        self.parse(
+            ApiMethod::UploadMedia,
+            vec![path.display().to_string(), debug(params)],
+        )
+    }
+
+    async fn upload_media_bytes(
+        &self,
+        bytes: Bytes,
+        params: UploadParams,
+    ) -> Result<UploadedMedia, TweetyError> {
+        self.parse(
+            ApiMethod::UploadMediaBytes,
+            vec![format!("{} bytes", bytes.len()), debug(params)],
+        )
+    }
+
+    async fn create_media_metadata(
+        &self,
+        media_id: MediaId,
+        metadata: &MediaMetadata,
+    ) -> Result<(), TweetyError> {
+        self.parse(
+            ApiMethod::CreateMediaMetadata,
+            vec![media_id.to_string(), debug(metadata)],
+        )
+    }
+
+    async fn create_media_subtitles(
+        &self,
+        video_id: MediaId,
+        subtitles: &[Subtitles],
+    ) -> Result<(), TweetyError> {
+        self.parse(
+            ApiMethod::CreateMediaSubtitles,
+            vec![video_id.to_string(), debug(subtitles)],
+        )
+    }
+
+    async fn delete_media_subtitles(
+        &self,
+        video_id: MediaId,
+        language_codes: &[LanguageCode],
+    ) -> Result<(), TweetyError> {
+        self.parse(
+            ApiMethod::DeleteMediaSubtitles,
+            vec![video_id.to_string(), debug(language_codes)],
+        )
+    }
+}
@@ -0,0 +1,35 @@
+use crate::api::direct_messages::EventType;
+use crate::types::id::{DmConversationId, DmEventId, TweetId, UserId};
+use crate::types::timestamp::Timestamp;
+use serde::{Deserialize, Serialize};
+
+/// A Direct Message event: a message, or a participant joining or leaving a group.
+/// Only `id` and `event_type` (and `text` for messages) are returned by default,
+/// the other fields have to be requested through `dm_event.fields`.
+/// [Docs](https://developer.x.com/en/docs/x-api/data-dictionary/object-model/dm-events)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DmEvent {
+    pub id: DmEventId,
+    pub event_type: EventType,
+    /// Only set on `MessageCreate` events.
+    pub text: Option<String>,
+    pub sender_id: Option<UserId>,
+    /// Only set on `ParticipantsJoin` and `ParticipantsLeave` events.
+    pub participant_ids: Option<Vec<UserId>>,
+    pub dm_conversation_id: Option<DmConversationId>,
+    pub created_at: Option<Timestamp>,
+    pub attachments: Option<DmAttachments>,
+    pub referenced_tweets: Option<Vec<DmReferencedTweet>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DmAttachments {
+    pub media_keys: Option<Vec<String>>,
+    pub card_ids: Option<Vec<String>>,
+}
+
+/// A Tweet shared in a message, returned in `includes.tweets`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DmReferencedTweet {
+    pub id: TweetId,
+}
@@ -0,0 +1,93 @@
+use crate::types::id::UserId;
+use serde::{Deserialize, Serialize};
+
+/// Entities parsed out of a Tweet's text.
+/// [Docs](https://developer.x.com/en/docs/x-api/data-dictionary/object-model/tweet)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TweetEntities {
+    pub annotations: Option<Vec<Annotation>>,
+    pub cashtags: Option<Vec<Cashtag>>,
+    pub hashtags: Option<Vec<Hashtag>>,
+    pub mentions: Option<Vec<Mention>>,
+    pub urls: Option<Vec<Url>>,
+}
+
+/// Entities parsed out of a user's `url` and `description`.
+/// [Docs](https://developer.x.com/en/docs/x-api/data-dictionary/object-model/user)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserEntities {
+    pub url: Option<UrlEntities>,
+    pub description: Option<DescriptionEntities>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UrlEntities {
+    pub urls: Option<Vec<Url>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DescriptionEntities {
+    pub urls: Option<Vec<Url>>,
+    pub hashtags: Option<Vec<Hashtag>>,
+    pub mentions: Option<Vec<Mention>>,
+    pub cashtags: Option<Vec<Cashtag>>,
+}
+
+/// A named entity recognized in the text, e.g. a person or a place.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub start: u32,
+    pub end: u32,
+    pub probability: Option<f64>,
+    #[serde(rename = "type")]
+    pub annotation_type: Option<String>,
+    pub normalized_text: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cashtag {
+    pub start: u32,
+    pub end: u32,
+    pub tag: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Hashtag {
+    pub start: u32,
+    pub end: u32,
+    pub tag: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Mention {
+    pub start: u32,
+    pub end: u32,
+    pub username: String,
+    /// Only set on Tweet mentions.
+    pub id: Option<UserId>,
+}
+
+/// A URL, expanded and, for Tweets, unwound and enriched with its page's metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Url {
+    pub start: u32,
+    pub end: u32,
+    pub url: String,
+    pub expanded_url: Option<String>,
+    pub display_url: Option<String>,
+    pub unwound_url: Option<String>,
+    /// Set when the URL points at an attached media.
+    pub media_key: Option<String>,
+    pub images: Option<Vec<UrlImage>>,
+    /// HTTP status of the unwound URL.
+    pub status: Option<u16>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UrlImage {
+    pub url: String,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
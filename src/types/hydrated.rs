@@ -0,0 +1,300 @@
+//! Expansions resolved into linked objects.
+//!
+//! Objects requested through `expansions` are not nested in the primary `data`, the API
+//! returns them once in `includes` and only references them by id (or `media_key`).
+//! [`Hydrated`] indexes `includes` so those references can be followed directly:
+//!
+//! ```rust,no_run
+//! use tweety_rs::api::fields::{Expansion, Fields, UserField};
+//! use tweety_rs::api::search::QueryParams;
+//! use tweety_rs::TweetyClient;
+//!
+//! # async fn example(client: TweetyClient) -> Result<(), tweety_rs::api::error::TweetyError> {
+//! let params = QueryParams {
+//!     fields: Fields::new()
+//!         .expansions([Expansion::AuthorId, Expansion::AttachmentsMediaKeys])
+//!         .user_fields([UserField::Username]),
+//!     ..Default::default()
+//! };
+//!
+//! let page = client
+//!     .recent_search("#rustlang", Some(params))
+//!     .await?
+//!     .into_inner()
+//!     .hydrate();
+//! for tweet in page.tweets() {
+//!     let author = tweet.author().map(|user| user.username.as_str());
+//!     println!("{:?}: {} ({} media)", author, tweet.text, tweet.media().len());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [Docs](https://developer.x.com/en/docs/x-api/expansions)
+
+use crate::api::error::ErrorDetail;
+use crate::types::id::{TweetId, UserId};
+use crate::types::media::Media;
+use crate::types::place::Place;
+use crate::types::poll::Poll;
+use crate::types::response::{Includes, Meta};
+use crate::types::tweet::{ReferenceType, Tweet};
+use crate::types::user::User;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Deref;
+
+/// The `includes` of a response, indexed by id and `media_key`.
+#[derive(Debug, Clone, Default)]
+pub struct IncludesIndex {
+    includes: Includes,
+    tweets: HashMap<TweetId, usize>,
+    users: HashMap<UserId, usize>,
+    media: HashMap<String, usize>,
+    places: HashMap<String, usize>,
+    polls: HashMap<String, usize>,
+}
+
+fn positions<T, K: Eq + Hash>(items: &[T], key: impl Fn(&T) -> K) -> HashMap<K, usize> {
+    items
+        .iter()
+        .enumerate()
+        .map(|(position, item)| (key(item), position))
+        .collect()
+}
+
+impl From<Includes> for IncludesIndex {
+    fn from(includes: Includes) -> Self {
+        IncludesIndex {
+            tweets: positions(&includes.tweets, |tweet| tweet.id),
+            users: positions(&includes.users, |user| user.id),
+            media: positions(&includes.media, |media| media.media_key.clone()),
+            places: positions(&includes.places, |place| place.id.clone()),
+            polls: positions(&includes.polls, |poll| poll.id.clone()),
+            includes,
+        }
+    }
+}
+
+impl IncludesIndex {
+    pub fn tweet(&self, id: TweetId) -> Option<&Tweet> {
+        self.tweets.get(&id).map(|&i| &self.includes.tweets[i])
+    }
+
+    pub fn user(&self, id: UserId) -> Option<&User> {
+        self.users.get(&id).map(|&i| &self.includes.users[i])
+    }
+
+    pub fn media(&self, media_key: &str) -> Option<&Media> {
+        self.media.get(media_key).map(|&i| &self.includes.media[i])
+    }
+
+    pub fn place(&self, id: &str) -> Option<&Place> {
+        self.places.get(id).map(|&i| &self.includes.places[i])
+    }
+
+    pub fn poll(&self, id: &str) -> Option<&Poll> {
+        self.polls.get(id).map(|&i| &self.includes.polls[i])
+    }
+
+    /// The raw `includes` object.
+    pub fn includes(&self) -> &Includes {
+        &self.includes
+    }
+
+    pub fn into_includes(self) -> Includes {
+        self.includes
+    }
+
+    /// Links `tweet` to the objects of this index.
+    pub fn expand<'a>(&'a self, tweet: &'a Tweet) -> ExpandedTweet<'a> {
+        ExpandedTweet {
+            tweet,
+            includes: self,
+        }
+    }
+
+    /// Links `user` to the objects of this index.
+    pub fn expand_user<'a>(&'a self, user: &'a User) -> ExpandedUser<'a> {
+        ExpandedUser {
+            user,
+            includes: self,
+        }
+    }
+}
+
+/// A Tweet whose references are resolved against the `includes` of its response.
+/// Dereferences to the [`Tweet`] itself.
+///
+/// Every accessor returns `None` (or skips the object) when the matching expansion
+/// was not requested, or when the referenced object is not available anymore.
+#[derive(Debug, Clone, Copy)]
+pub struct ExpandedTweet<'a> {
+    tweet: &'a Tweet,
+    includes: &'a IncludesIndex,
+}
+
+impl<'a> ExpandedTweet<'a> {
+    pub fn tweet(&self) -> &'a Tweet {
+        self.tweet
+    }
+
+    /// Needs the `author_id` expansion, or `referenced_tweets.id.author_id`
+    /// for referenced Tweets.
+    pub fn author(&self) -> Option<&'a User> {
+        self.includes.user(self.tweet.author_id?)
+    }
+
+    /// Needs the `in_reply_to_user_id` expansion.
+    pub fn in_reply_to_user(&self) -> Option<&'a User> {
+        self.includes.user(self.tweet.in_reply_to_user_id?)
+    }
+
+    /// Needs the `attachments.media_keys` expansion.
+    pub fn media(&self) -> Vec<&'a Media> {
+        let includes = self.includes;
+        self.tweet
+            .attachments
+            .iter()
+            .flat_map(|attachments| attachments.media_keys.iter().flatten())
+            .filter_map(|media_key| includes.media(media_key))
+            .collect()
+    }
+
+    /// Needs the `attachments.poll_ids` expansion.
+    pub fn poll(&self) -> Option<&'a Poll> {
+        let includes = self.includes;
+        self.tweet
+            .attachments
+            .as_ref()?
+            .poll_ids
+            .iter()
+            .flatten()
+            .find_map(|poll_id| includes.poll(poll_id))
+    }
+
+    /// Needs the `geo.place_id` expansion.
+    pub fn place(&self) -> Option<&'a Place> {
+        self.includes
+            .place(self.tweet.geo.as_ref()?.place_id.as_deref()?)
+    }
+
+    /// Users mentioned in the text. Needs the `entities.mentions.username` expansion.
+    pub fn mentioned_users(&self) -> Vec<&'a User> {
+        let includes = self.includes;
+        self.tweet
+            .entities
+            .iter()
+            .flat_map(|entities| entities.mentions.iter().flatten())
+            .filter_map(|mention| includes.user(mention.id?))
+            .collect()
+    }
+
+    /// The retweeted, quoted or replied-to Tweets, themselves expanded.
+    /// Needs the `referenced_tweets.id` expansion.
+    pub fn referenced_tweets(&self) -> Vec<(ReferenceType, ExpandedTweet<'a>)> {
+        let includes = self.includes;
+        self.tweet
+            .referenced_tweets
+            .iter()
+            .flatten()
+            .filter_map(|reference| {
+                let tweet = includes.tweet(reference.id)?;
+                Some((reference.reference_type, includes.expand(tweet)))
+            })
+            .collect()
+    }
+
+    pub fn retweeted(&self) -> Option<ExpandedTweet<'a>> {
+        self.referenced(ReferenceType::Retweeted)
+    }
+
+    pub fn quoted(&self) -> Option<ExpandedTweet<'a>> {
+        self.referenced(ReferenceType::Quoted)
+    }
+
+    pub fn replied_to(&self) -> Option<ExpandedTweet<'a>> {
+        self.referenced(ReferenceType::RepliedTo)
+    }
+
+    fn referenced(&self, reference_type: ReferenceType) -> Option<ExpandedTweet<'a>> {
+        self.referenced_tweets()
+            .into_iter()
+            .find(|(kind, _)| *kind == reference_type)
+            .map(|(_, tweet)| tweet)
+    }
+}
+
+impl Deref for ExpandedTweet<'_> {
+    type Target = Tweet;
+
+    fn deref(&self) -> &Tweet {
+        self.tweet
+    }
+}
+
+/// A user whose references are resolved against the `includes` of its response.
+/// Dereferences to the [`User`] itself.
+#[derive(Debug, Clone, Copy)]
+pub struct ExpandedUser<'a> {
+    user: &'a User,
+    includes: &'a IncludesIndex,
+}
+
+impl<'a> ExpandedUser<'a> {
+    pub fn user(&self) -> &'a User {
+        self.user
+    }
+
+    /// Needs the `pinned_tweet_id` expansion.
+    pub fn pinned_tweet(&self) -> Option<ExpandedTweet<'a>> {
+        let tweet = self.includes.tweet(self.user.pinned_tweet_id?)?;
+        Some(self.includes.expand(tweet))
+    }
+}
+
+impl Deref for ExpandedUser<'_> {
+    type Target = User;
+
+    fn deref(&self) -> &User {
+        self.user
+    }
+}
+
+/// A response whose `includes` are indexed, see the [module documentation](self).
+#[derive(Debug, Clone, Default)]
+pub struct Hydrated<T> {
+    pub data: Vec<T>,
+    pub includes: IncludesIndex,
+    pub meta: Option<Meta>,
+    /// Resources that could not be returned, see [`ErrorDetail`].
+    pub errors: Vec<ErrorDetail>,
+}
+
+impl<T> Hydrated<T> {
+    pub fn new(data: Vec<T>, includes: Option<Includes>, meta: Option<Meta>) -> Self {
+        Hydrated {
+            data,
+            includes: includes.unwrap_or_default().into(),
+            meta,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn with_errors(mut self, errors: Vec<ErrorDetail>) -> Self {
+        self.errors = errors;
+        self
+    }
+}
+
+impl Hydrated<Tweet> {
+    pub fn tweets(&self) -> impl Iterator<Item = ExpandedTweet<'_>> {
+        self.data.iter().map(|tweet| self.includes.expand(tweet))
+    }
+}
+
+impl Hydrated<User> {
+    pub fn users(&self) -> impl Iterator<Item = ExpandedUser<'_>> {
+        self.data.iter().map(|user| self.includes.expand_user(user))
+    }
+}
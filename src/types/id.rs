@@ -0,0 +1,262 @@
+//! Typed identifiers.
+//!
+//! Tweets, users, Lists, media and Direct Message events are identified by
+//! [snowflake ids](https://developer.x.com/en/docs/twitter-ids): 64-bit integers whose high bits
+//! are the creation time in milliseconds. They are ordered chronologically, so the newest of two
+//! Tweets is simply the greater id, and they can be built from a point in time to use as
+//! `since_id`/`until_id`:
+//!
+//! ```rust
+//! use std::time::{Duration, UNIX_EPOCH};
+//! use tweety_rs::types::id::TweetId;
+//!
+//! let id: TweetId = "1212092628029698048".parse().unwrap();
+//! assert_eq!(id.timestamp_millis(), 1577820376771); // 2019-12-31T19:26:16.771Z
+//!
+//! let midnight = TweetId::from_time(UNIX_EPOCH + Duration::from_secs(1577836800));
+//! assert!(id < midnight);
+//! ```
+//!
+//! The API sends ids as strings, they are serialized back the same way but also
+//! deserialize from JSON numbers.
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Milliseconds between the Unix epoch and the first snowflake id (2010-11-04T01:42:54.657Z).
+pub const TWITTER_EPOCH_MILLIS: u64 = 1288834974657;
+
+/// Bits below the timestamp: datacenter, worker and sequence number.
+const TIMESTAMP_SHIFT: u32 = 22;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid id: {0:?}")]
+pub struct ParseIdError(String);
+
+macro_rules! snowflake_id {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(u64);
+
+        impl $name {
+            pub const fn new(id: u64) -> Self {
+                $name(id)
+            }
+
+            pub const fn get(self) -> u64 {
+                self.0
+            }
+
+            /// Creation time in milliseconds since the Unix epoch.
+            /// Meaningless for ids created before November 2010, which are not snowflakes.
+            pub const fn timestamp_millis(self) -> u64 {
+                (self.0 >> TIMESTAMP_SHIFT) + TWITTER_EPOCH_MILLIS
+            }
+
+            pub fn created_at(self) -> SystemTime {
+                UNIX_EPOCH + Duration::from_millis(self.timestamp_millis())
+            }
+
+            /// The smallest id that could have been created at `time`. Every id created
+            /// at or after `time` is greater or equal, every id created before is smaller.
+            pub fn from_time(time: SystemTime) -> Self {
+                let millis = time
+                    .duration_since(UNIX_EPOCH)
+                    .map(|elapsed| elapsed.as_millis() as u64)
+                    .unwrap_or_default();
+                $name(millis.saturating_sub(TWITTER_EPOCH_MILLIS) << TIMESTAMP_SHIFT)
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(id: u64) -> Self {
+                $name(id)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(id: $name) -> u64 {
+                id.0
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = ParseIdError;
+
+            fn from_str(id: &str) -> Result<Self, Self::Err> {
+                parse_u64(id).map($name)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_any(U64Visitor).map($name)
+            }
+        }
+    };
+}
+
+snowflake_id! {
+    /// Id of a Tweet (Post).
+    TweetId
+}
+
+snowflake_id! {
+    /// Id of a user account.
+    UserId
+}
+
+snowflake_id! {
+    /// Id of a List.
+    ListId
+}
+
+snowflake_id! {
+    /// Id of an uploaded media, as returned by the upload endpoints.
+    MediaId
+}
+
+snowflake_id! {
+    /// Id of a Direct Message event.
+    DmEventId
+}
+
+fn parse_u64(id: &str) -> Result<u64, ParseIdError> {
+    id.trim()
+        .parse::<u64>()
+        .map_err(|_| ParseIdError(id.to_string()))
+}
+
+/// Accepts an id sent either as a JSON string or as a JSON number.
+struct U64Visitor;
+
+impl Visitor<'_> for U64Visitor {
+    type Value = u64;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an id as a string or a non-negative integer")
+    }
+
+    fn visit_u64<E: de::Error>(self, id: u64) -> Result<u64, E> {
+        Ok(id)
+    }
+
+    fn visit_i64<E: de::Error>(self, id: i64) -> Result<u64, E> {
+        u64::try_from(id).map_err(|_| E::invalid_value(de::Unexpected::Signed(id), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, id: &str) -> Result<u64, E> {
+        parse_u64(id).map_err(|_| E::invalid_value(de::Unexpected::Str(id), &self))
+    }
+}
+
+/// Id of a Direct Message conversation.
+///
+/// 1-1 conversations are identified by the ids of both participants joined by a dash,
+/// e.g. `"2244994945-1470797706"`, group conversations by a snowflake id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DmConversationId(String);
+
+impl DmConversationId {
+    /// The id of the 1-1 conversation between two users, the smaller id first.
+    pub fn one_to_one(a: UserId, b: UserId) -> Self {
+        let (low, high) = if a <= b { (a, b) } else { (b, a) };
+        DmConversationId(format!("{}-{}", low, high))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Both participants of a 1-1 conversation, `None` for a group conversation.
+    pub fn participants(&self) -> Option<(UserId, UserId)> {
+        let (a, b) = self.0.split_once('-')?;
+        Some((a.parse().ok()?, b.parse().ok()?))
+    }
+
+    pub fn is_group(&self) -> bool {
+        self.participants().is_none()
+    }
+
+    /// Creation time of a group conversation, `None` for a 1-1 conversation.
+    pub fn created_at(&self) -> Option<SystemTime> {
+        let id = parse_u64(&self.0).ok()?;
+        Some(TweetId::new(id).created_at())
+    }
+}
+
+impl FromStr for DmConversationId {
+    type Err = ParseIdError;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        let id = id.trim();
+        let valid = match id.split_once('-') {
+            Some((a, b)) => parse_u64(a).is_ok() && parse_u64(b).is_ok(),
+            None => parse_u64(id).is_ok(),
+        };
+        if !valid {
+            return Err(ParseIdError(id.to_string()));
+        }
+        Ok(DmConversationId(id.to_string()))
+    }
+}
+
+impl fmt::Display for DmConversationId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Serialize for DmConversationId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for DmConversationId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ConversationVisitor;
+
+        impl Visitor<'_> for ConversationVisitor {
+            type Value = DmConversationId;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a conversation id as a string or a non-negative integer")
+            }
+
+            fn visit_u64<E: de::Error>(self, id: u64) -> Result<DmConversationId, E> {
+                Ok(DmConversationId(id.to_string()))
+            }
+
+            fn visit_i64<E: de::Error>(self, id: i64) -> Result<DmConversationId, E> {
+                U64Visitor
+                    .visit_i64(id)
+                    .map(|id| DmConversationId(id.to_string()))
+            }
+
+            fn visit_str<E: de::Error>(self, id: &str) -> Result<DmConversationId, E> {
+                id.parse()
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(id), &self))
+            }
+        }
+
+        deserializer.deserialize_any(ConversationVisitor)
+    }
+}
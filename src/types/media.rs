@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+
+/// A photo, video or GIF attached to a Tweet, returned in `includes.media`.
+/// [Docs](https://developer.x.com/en/docs/x-api/data-dictionary/object-model/media)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Media {
+    pub media_key: String,
+    #[serde(rename = "type")]
+    pub media_type: MediaType,
+    /// Only set for photos.
+    pub url: Option<String>,
+    pub duration_ms: Option<u64>,
+    pub height: Option<u32>,
+    pub width: Option<u32>,
+    pub preview_image_url: Option<String>,
+    pub alt_text: Option<String>,
+    pub public_metrics: Option<MediaPublicMetrics>,
+    pub non_public_metrics: Option<MediaPrivateMetrics>,
+    pub organic_metrics: Option<MediaPrivateMetrics>,
+    pub promoted_metrics: Option<MediaPrivateMetrics>,
+    /// Renditions of a video or GIF.
+    pub variants: Option<Vec<MediaVariant>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MediaType {
+    Photo,
+    Video,
+    AnimatedGif,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaPublicMetrics {
+    pub view_count: Option<u64>,
+}
+
+/// Metrics only available to the owner of the media, or for promoted content.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaPrivateMetrics {
+    pub playback_0_count: Option<u64>,
+    pub playback_25_count: Option<u64>,
+    pub playback_50_count: Option<u64>,
+    pub playback_75_count: Option<u64>,
+    pub playback_100_count: Option<u64>,
+    pub view_count: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MediaVariant {
+    pub bit_rate: Option<u64>,
+    pub content_type: String,
+    pub url: String,
+}
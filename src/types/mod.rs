@@ -1,3 +1,11 @@
+pub mod direct_message;
+pub mod entities;
+pub mod hydrated;
+pub mod id;
+pub mod media;
+pub mod place;
+pub mod poll;
+pub mod response;
+pub mod timestamp;
 pub mod tweet;
-pub mod types;
 pub mod user;
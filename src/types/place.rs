@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A place tagged in a Tweet, returned in `includes.places`.
+/// [Docs](https://developer.x.com/en/docs/x-api/data-dictionary/object-model/place)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Place {
+    pub id: String,
+    pub full_name: String,
+    pub name: Option<String>,
+    pub country: Option<String>,
+    /// ISO Alpha-2 country code.
+    pub country_code: Option<String>,
+    pub contained_within: Option<Vec<String>>,
+    pub geo: Option<PlaceGeo>,
+    pub place_type: Option<PlaceType>,
+}
+
+/// GeoJSON bounding box of the place.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaceGeo {
+    #[serde(rename = "type")]
+    pub geo_type: String,
+    /// `[west, south, east, north]`.
+    pub bbox: Vec<f64>,
+    #[serde(default)]
+    pub properties: Value,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaceType {
+    Poi,
+    Neighborhood,
+    City,
+    Admin,
+    Country,
+    #[serde(other)]
+    Unknown,
+}
@@ -0,0 +1,29 @@
+use crate::types::timestamp::Timestamp;
+use serde::{Deserialize, Serialize};
+
+/// A poll attached to a Tweet, returned in `includes.polls`.
+/// [Docs](https://developer.x.com/en/docs/x-api/data-dictionary/object-model/poll)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Poll {
+    pub id: String,
+    pub options: Vec<PollOption>,
+    pub duration_minutes: Option<u32>,
+    pub end_datetime: Option<Timestamp>,
+    pub voting_status: Option<VotingStatus>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PollOption {
+    pub position: u32,
+    pub label: String,
+    pub votes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VotingStatus {
+    Open,
+    Closed,
+    #[serde(other)]
+    Unknown,
+}
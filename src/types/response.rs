@@ -0,0 +1,97 @@
+use crate::api::error::TweetyError;
+use crate::types::id::TweetId;
+use crate::types::media::Media;
+use crate::types::place::Place;
+use crate::types::poll::Poll;
+use crate::types::tweet::Tweet;
+use crate::types::user::User;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::ops::Deref;
+
+/// Objects referenced by the primary `data` and requested through `expansions`.
+/// [Docs](https://developer.x.com/en/docs/x-api/expansions)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Includes {
+    #[serde(default)]
+    pub tweets: Vec<Tweet>,
+    #[serde(default)]
+    pub users: Vec<User>,
+    #[serde(default)]
+    pub media: Vec<Media>,
+    #[serde(default)]
+    pub places: Vec<Place>,
+    #[serde(default)]
+    pub polls: Vec<Poll>,
+}
+
+/// The `meta` object of list endpoints.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Meta {
+    #[serde(default)]
+    pub result_count: u32,
+    /// Pass it as `since_id` on the next poll to only get newer Tweets.
+    pub newest_id: Option<TweetId>,
+    pub oldest_id: Option<TweetId>,
+    /// Absent on the last page.
+    pub next_token: Option<String>,
+    pub previous_token: Option<String>,
+}
+
+/// A typed response along with the JSON body it was parsed from, for the fields
+/// the typed model does not cover. Dereferences to the typed response.
+///
+/// ```rust,no_run
+/// # use tweety_rs::types::id::TweetId;
+/// # async fn example(client: tweety_rs::TweetyClient) -> Result<(), tweety_rs::api::error::TweetyError> {
+/// let response = client.get_tweet_info(TweetId::new(1212092628029698048)).await?;
+/// println!("{}", response.data.text);
+/// println!("{}", response.raw()["data"]["text"]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+    parsed: T,
+    raw: Value,
+}
+
+impl<T: DeserializeOwned> Response<T> {
+    pub(crate) fn from_value(raw: Value) -> Result<Self, TweetyError> {
+        let parsed =
+            T::deserialize(&raw).map_err(|err| TweetyError::JsonParseError(err.to_string()))?;
+        Ok(Response { parsed, raw })
+    }
+}
+
+impl<T: Serialize> Response<T> {
+    /// Wraps an already typed response, the raw JSON is its serialization.
+    pub fn new(parsed: T) -> Self {
+        let raw = serde_json::to_value(&parsed).unwrap_or_default();
+        Response { parsed, raw }
+    }
+}
+
+impl<T> Response<T> {
+    /// The JSON body as returned by the API.
+    pub fn raw(&self) -> &Value {
+        &self.raw
+    }
+
+    pub fn into_raw(self) -> Value {
+        self.raw
+    }
+
+    pub fn into_inner(self) -> T {
+        self.parsed
+    }
+}
+
+impl<T> Deref for Response<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.parsed
+    }
+}
@@ -0,0 +1,339 @@
+//! Date-times sent and returned by the API.
+//!
+//! The API uses RFC 3339 (ISO 8601) strings in UTC, e.g. `2019-12-31T19:26:16.000Z`.
+//! [`Timestamp`] parses and validates them and is serialized back in the same format.
+//!
+//! It converts to and from [`SystemTime`], and, with the `chrono` or `time` cargo features,
+//! to and from `chrono::DateTime<Utc>` and `time::OffsetDateTime`. Those have a smaller range,
+//! so converting to them is a `TryFrom` failing with [`TimestampRangeError`]:
+//!
+//! ```rust
+//! use tweety_rs::types::timestamp::Timestamp;
+//!
+//! let created_at: Timestamp = "2019-12-31T19:26:16.000Z".parse().unwrap();
+//! assert_eq!(created_at.unix_timestamp(), 1577820376);
+//! assert_eq!(created_at.to_string(), "2019-12-31T19:26:16Z");
+//! assert!("2019-12-31".parse::<Timestamp>().is_err());
+//! ```
+
+use crate::api::error::TweetyError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// A point in time with nanosecond precision, in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    /// Seconds since the Unix epoch, negative before 1970.
+    secs: i64,
+    nanos: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("invalid RFC 3339 date-time: {0:?}")]
+pub struct ParseTimestampError(String);
+
+/// A [`Timestamp`] outside the range of the date-time type it is converted to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("{timestamp} is out of the range of {target}")]
+pub struct TimestampRangeError {
+    pub timestamp: Timestamp,
+    pub target: &'static str,
+}
+
+const SECS_PER_DAY: i64 = 86_400;
+
+impl Timestamp {
+    /// `nanos` above one second are carried over into `secs`.
+    pub fn from_unix(secs: i64, nanos: u32) -> Self {
+        Timestamp {
+            secs: secs + (nanos / 1_000_000_000) as i64,
+            nanos: nanos % 1_000_000_000,
+        }
+    }
+
+    pub fn now() -> Self {
+        SystemTime::now().into()
+    }
+
+    /// Seconds since the Unix epoch.
+    pub fn unix_timestamp(&self) -> i64 {
+        self.secs
+    }
+
+    pub fn subsec_nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    /// Formats as `YYYY-MM-DDTHH:MM:SSZ`, the format expected by the
+    /// `start_time` and `end_time` query parameters.
+    pub fn to_rfc3339_seconds(&self) -> String {
+        let (year, month, day) = civil_from_days(self.secs.div_euclid(SECS_PER_DAY));
+        let secs_of_day = self.secs.rem_euclid(SECS_PER_DAY);
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            secs_of_day / 3600,
+            secs_of_day % 3600 / 60,
+            secs_of_day % 60
+        )
+    }
+
+    /// Parses `YYYY-MM-DDTHH:MM:SS[.fraction](Z|±HH:MM)`.
+    pub fn parse_rfc3339(input: &str) -> Result<Self, ParseTimestampError> {
+        parse_rfc3339(input.trim()).ok_or_else(|| ParseTimestampError(input.to_string()))
+    }
+}
+
+fn parse_rfc3339(input: &str) -> Option<Timestamp> {
+    let bytes = input.as_bytes();
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        let digits = bytes.get(range)?;
+        if !digits.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        std::str::from_utf8(digits).ok()?.parse().ok()
+    };
+    let separator = |index: usize, expected: &[u8]| {
+        bytes.get(index).is_some_and(|byte| expected.contains(byte))
+    };
+
+    if !(separator(4, b"-")
+        && separator(7, b"-")
+        && separator(10, b"Tt ")
+        && separator(13, b":")
+        && separator(16, b":"))
+    {
+        return None;
+    }
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        // Leap seconds are folded into the next second.
+        || second > 60
+    {
+        return None;
+    }
+
+    let mut rest = &input[19..];
+    let mut nanos = 0u32;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return None;
+        }
+        // Only nanosecond precision is kept.
+        for (position, digit) in fraction.bytes().take(digits.min(9)).enumerate() {
+            nanos += (digit - b'0') as u32 * 10u32.pow(8 - position as u32);
+        }
+        rest = &fraction[digits..];
+    }
+
+    let offset_secs = match rest.as_bytes() {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), _, _, b':', _, _] => {
+            let hours = number(input.len() - 5..input.len() - 3)?;
+            let minutes = number(input.len() - 2..input.len())?;
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = hours * 3600 + minutes * 60;
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+
+    let secs =
+        days_from_civil(year, month, day) * SECS_PER_DAY + hour * 3600 + minute * 60 + second
+            - offset_secs;
+    Some(Timestamp::from_unix(secs, nanos))
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date.
+/// [Algorithm](https://howardhinnant.github.io/date_algorithms.html#days_from_civil)
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Inverse of [`days_from_civil`].
+/// [Algorithm](https://howardhinnant.github.io/date_algorithms.html#civil_from_days)
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// RFC 3339 in UTC. The fraction of a second, if any, is written in milli-, micro-
+/// or nanoseconds, whichever is exact, so parsing the output gives the same timestamp.
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let seconds = self.to_rfc3339_seconds();
+        let without_zone = seconds.trim_end_matches('Z');
+        match self.nanos {
+            0 => f.write_str(&seconds),
+            nanos if nanos % 1_000_000 == 0 => {
+                write!(f, "{}.{:03}Z", without_zone, nanos / 1_000_000)
+            }
+            nanos if nanos % 1_000 == 0 => write!(f, "{}.{:06}Z", without_zone, nanos / 1_000),
+            nanos => write!(f, "{}.{:09}Z", without_zone, nanos),
+        }
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = ParseTimestampError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Timestamp::parse_rfc3339(input)
+    }
+}
+
+impl From<SystemTime> for Timestamp {
+    fn from(time: SystemTime) -> Self {
+        match time.duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => Timestamp::from_unix(elapsed.as_secs() as i64, elapsed.subsec_nanos()),
+            Err(err) => {
+                let before = err.duration();
+                match before.subsec_nanos() {
+                    0 => Timestamp::from_unix(-(before.as_secs() as i64), 0),
+                    nanos => {
+                        Timestamp::from_unix(-(before.as_secs() as i64) - 1, 1_000_000_000 - nanos)
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl From<Timestamp> for SystemTime {
+    fn from(timestamp: Timestamp) -> SystemTime {
+        let nanos = Duration::from_nanos(timestamp.nanos as u64);
+        if timestamp.secs >= 0 {
+            UNIX_EPOCH + Duration::from_secs(timestamp.secs as u64) + nanos
+        } else {
+            UNIX_EPOCH - Duration::from_secs(timestamp.secs.unsigned_abs()) + nanos
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
+    fn from(date_time: chrono::DateTime<chrono::Utc>) -> Self {
+        Timestamp::from_unix(date_time.timestamp(), date_time.timestamp_subsec_nanos())
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<Timestamp> for chrono::DateTime<chrono::Utc> {
+    type Error = TimestampRangeError;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, Self::Error> {
+        chrono::DateTime::from_timestamp(timestamp.secs, timestamp.nanos).ok_or(
+            TimestampRangeError {
+                timestamp,
+                target: "chrono::DateTime",
+            },
+        )
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<time::OffsetDateTime> for Timestamp {
+    fn from(date_time: time::OffsetDateTime) -> Self {
+        Timestamp::from_unix(date_time.unix_timestamp(), date_time.nanosecond())
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<Timestamp> for time::OffsetDateTime {
+    type Error = TimestampRangeError;
+
+    fn try_from(timestamp: Timestamp) -> Result<Self, Self::Error> {
+        let nanos = timestamp.secs as i128 * 1_000_000_000 + timestamp.nanos as i128;
+        time::OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(|_| TimestampRangeError {
+            timestamp,
+            target: "time::OffsetDateTime",
+        })
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let input = String::deserialize(deserializer)?;
+        input.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Checks the `start_time` and `end_time` query parameters before sending them:
+/// the API rejects times in the future and a `start_time` that is not before `end_time`.
+pub(crate) fn validate_time_range(
+    start_time: Option<Timestamp>,
+    end_time: Option<Timestamp>,
+) -> Result<(), TweetyError> {
+    let now = Timestamp::now();
+    for (name, time) in [("start_time", start_time), ("end_time", end_time)] {
+        if let Some(time) = time.filter(|time| *time > now) {
+            return Err(TweetyError::InvalidParameter(format!(
+                "{} {} is in the future",
+                name, time
+            )));
+        }
+    }
+    if let (Some(start_time), Some(end_time)) = (start_time, end_time) {
+        if start_time >= end_time {
+            return Err(TweetyError::InvalidParameter(format!(
+                "start_time {} must be before end_time {}",
+                start_time, end_time
+            )));
+        }
+    }
+    Ok(())
+}
@@ -1,6 +1,168 @@
+use crate::api::error::ErrorDetail;
+use crate::types::entities::TweetEntities;
+use crate::types::hydrated::Hydrated;
+use crate::types::id::{MediaId, TweetId, UserId};
+use crate::types::response::{Includes, Meta};
+use crate::types::timestamp::Timestamp;
+use crate::types::user::Withheld;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+/// A Tweet (Post). Only `id`, `text` and `edit_history_tweet_ids` are returned
+/// by default, the other fields have to be requested through `tweet.fields`.
+/// [Docs](https://developer.x.com/en/docs/x-api/data-dictionary/object-model/tweet)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Tweet {
+    pub id: TweetId,
+    pub text: String,
+    pub edit_history_tweet_ids: Option<Vec<TweetId>>,
+    pub attachments: Option<Attachments>,
+    pub author_id: Option<UserId>,
+    pub context_annotations: Option<Vec<ContextAnnotation>>,
+    /// Id of the Tweet that started the conversation.
+    pub conversation_id: Option<TweetId>,
+    pub created_at: Option<Timestamp>,
+    pub edit_controls: Option<EditControls>,
+    pub entities: Option<TweetEntities>,
+    pub geo: Option<TweetGeo>,
+    pub in_reply_to_user_id: Option<UserId>,
+    pub lang: Option<String>,
+    pub note_tweet: Option<NoteTweet>,
+    pub possibly_sensitive: Option<bool>,
+    pub public_metrics: Option<TweetPublicMetrics>,
+    pub non_public_metrics: Option<TweetPrivateMetrics>,
+    pub organic_metrics: Option<TweetPrivateMetrics>,
+    pub promoted_metrics: Option<TweetPrivateMetrics>,
+    pub referenced_tweets: Option<Vec<ReferencedTweet>>,
+    pub reply_settings: Option<String>,
+    pub source: Option<String>,
+    pub withheld: Option<Withheld>,
+}
+
+/// Response of the endpoints returning a single Tweet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TweetResponse {
+    pub data: Tweet,
+    pub includes: Option<Includes>,
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
+}
+
+impl TweetResponse {
+    /// Indexes `includes` to follow the expansions of the Tweet.
+    /// See [`crate::types::hydrated`].
+    pub fn hydrate(self) -> Hydrated<Tweet> {
+        Hydrated::new(vec![self.data], self.includes, None).with_errors(self.errors)
+    }
+}
+
+/// Response of the endpoints returning a list of Tweets.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TweetsResponse {
+    #[serde(default)]
+    pub data: Vec<Tweet>,
+    pub includes: Option<Includes>,
+    /// Only set on paginated endpoints.
+    pub meta: Option<Meta>,
+    /// Resources that could not be returned, see [`ErrorDetail`].
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
+}
+
+impl TweetsResponse {
+    /// Indexes `includes` to follow the expansions of every Tweet.
+    /// See [`crate::types::hydrated`].
+    pub fn hydrate(self) -> Hydrated<Tweet> {
+        Hydrated::new(self.data, self.includes, self.meta).with_errors(self.errors)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attachments {
+    pub media_keys: Option<Vec<String>>,
+    pub poll_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextAnnotation {
+    pub domain: ContextEntity,
+    pub entity: ContextEntity,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContextEntity {
+    pub id: String,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditControls {
+    pub edits_remaining: u32,
+    pub is_edit_eligible: bool,
+    pub editable_until: Timestamp,
+}
+
+/// Location tagged in a Tweet. The place itself is returned in `includes.places`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TweetGeo {
+    pub place_id: Option<String>,
+    pub coordinates: Option<Coordinates>,
+}
+
+/// GeoJSON point, as `[longitude, latitude]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Coordinates {
+    #[serde(rename = "type")]
+    pub coordinates_type: String,
+    pub coordinates: Vec<f64>,
+}
+
+/// Full text of a Tweet longer than 280 characters.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoteTweet {
+    pub text: String,
+    pub entities: Option<TweetEntities>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TweetPublicMetrics {
+    pub retweet_count: Option<u64>,
+    pub reply_count: Option<u64>,
+    pub like_count: Option<u64>,
+    pub quote_count: Option<u64>,
+    pub bookmark_count: Option<u64>,
+    pub impression_count: Option<u64>,
+}
+
+/// Metrics only available to the author of the Tweet, or for promoted content.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TweetPrivateMetrics {
+    pub impression_count: Option<u64>,
+    pub like_count: Option<u64>,
+    pub reply_count: Option<u64>,
+    pub retweet_count: Option<u64>,
+    pub url_link_clicks: Option<u64>,
+    pub user_profile_clicks: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferencedTweet {
+    #[serde(rename = "type")]
+    pub reference_type: ReferenceType,
+    pub id: TweetId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceType {
+    Retweeted,
+    Quoted,
+    RepliedTo,
+    #[serde(other)]
+    Unknown,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PostTweetParams {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -19,7 +181,7 @@ pub struct PostTweetParams {
     pub poll: Option<Poll>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub quote_tweet_id: Option<String>,
+    pub quote_tweet_id: Option<TweetId>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reply: Option<Reply>,
@@ -31,7 +193,7 @@ pub struct PostTweetParams {
 impl PostTweetParams {
     /// takes the message and appends it to the body json
     pub fn to_json(&self, message: &str) -> Value {
-        let mut json_payload = serde_json::to_value(&self).unwrap();
+        let mut json_payload = serde_json::to_value(self).unwrap();
         json_payload["text"] = serde_json::Value::String(message.to_string());
 
         json_payload
@@ -47,9 +209,9 @@ pub struct Geo {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Media {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub media_ids: Option<Vec<String>>,
+    pub media_ids: Option<Vec<MediaId>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub tagged_user_ids: Option<Vec<String>>,
+    pub tagged_user_ids: Option<Vec<UserId>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -63,7 +225,7 @@ pub struct Poll {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Reply {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub in_reply_to_tweet_id: Option<String>,
+    pub in_reply_to_tweet_id: Option<TweetId>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub exclude_reply_user_ids: Option<Vec<String>>,
+    pub exclude_reply_user_ids: Option<Vec<UserId>>,
 }
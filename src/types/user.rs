@@ -1,117 +1,89 @@
+use crate::api::error::ErrorDetail;
+use crate::types::entities::UserEntities;
+use crate::types::hydrated::Hydrated;
+use crate::types::id::{TweetId, UserId};
+use crate::types::response::{Includes, Meta};
+use crate::types::timestamp::Timestamp;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize)]
+/// Response of the endpoints returning a list of users.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiResponse {
-    pub data: Vec<UserResponse>,
+    #[serde(default)]
+    pub data: Vec<User>,
     pub includes: Option<Includes>,
+    /// Only set on paginated endpoints.
+    pub meta: Option<Meta>,
+    /// Resources that could not be returned, see [`ErrorDetail`].
+    #[serde(default)]
+    pub errors: Vec<ErrorDetail>,
 }
 
-#[derive(Deserialize)]
-pub struct Tweet {
-    pub created_at: String,
-    pub text: String,
-    pub id: String,
+impl ApiResponse {
+    /// Indexes `includes` to follow the pinned Tweet of every user.
+    /// See [`crate::types::hydrated`].
+    pub fn hydrate(self) -> Hydrated<User> {
+        Hydrated::new(self.data, self.includes, self.meta).with_errors(self.errors)
+    }
 }
 
-#[derive(Deserialize)]
-pub struct Includes {
-    pub tweets: Vec<Tweet>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct UserResponse {
-    pub id: String,
+/// A user account. Only `id`, `name` and `username` are returned by default,
+/// the other fields have to be requested through `user.fields`.
+/// [Docs](https://developer.x.com/en/docs/x-api/data-dictionary/object-model/user)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct User {
+    pub id: UserId,
     pub name: String,
     pub username: String,
-    pub created_at: Option<String>, // ISO 8601 date format
-    pub most_recent_tweet_id: Option<String>,
+    pub created_at: Option<Timestamp>,
+    pub most_recent_tweet_id: Option<TweetId>,
     pub protected: Option<bool>,
     pub withheld: Option<Withheld>,
     pub location: Option<String>,
     pub url: Option<String>,
     pub description: Option<String>,
     pub verified: Option<bool>,
-    pub verified_type: Option<VerifiedType>, // Enum could be used here
-    pub entities: Option<Entities>,
+    pub verified_type: Option<VerifiedType>,
+    pub entities: Option<UserEntities>,
     pub profile_image_url: Option<String>,
-    pub public_metrics: Option<PublicMetrics>,
-    pub pinned_tweet_id: Option<String>,
+    pub public_metrics: Option<UserPublicMetrics>,
+    pub pinned_tweet_id: Option<TweetId>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum VerifiedType {
     Blue,
     Business,
     Government,
     None,
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Withholding details of a user or a Tweet.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Withheld {
+    /// Only set on Tweets withheld because of a DMCA notice.
+    pub copyright: Option<bool>,
     pub country_codes: Option<Vec<String>>,
-    pub scope: Option<WithheldScope>, // Enum could be used here
+    pub scope: Option<WithheldScope>,
 }
-#[derive(Debug, Serialize, Deserialize)]
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum WithheldScope {
     Tweet,
     User,
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Entities {
-    pub url: Option<UrlEntities>,
-    pub description: Option<DescriptionEntities>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct UrlEntities {
-    pub urls: Option<Vec<Url>>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Url {
-    pub start: Option<u32>,
-    pub end: Option<u32>,
-    pub url: Option<String>,
-    pub expanded_url: Option<String>,
-    pub display_url: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DescriptionEntities {
-    pub urls: Option<Vec<Url>>,
-    pub hashtags: Option<Vec<Hashtag>>,
-    pub mentions: Option<Vec<Mention>>,
-    pub cashtags: Option<Vec<Cashtag>>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Hashtag {
-    pub start: Option<u32>,
-    pub end: Option<u32>,
-    pub hashtag: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Mention {
-    pub start: Option<u32>,
-    pub end: Option<u32>,
-    pub username: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Cashtag {
-    pub start: Option<u32>,
-    pub end: Option<u32>,
-    pub cashtag: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PublicMetrics {
-    pub followers_count: Option<u32>,
-    pub following_count: Option<u32>,
-    pub tweet_count: Option<u32>,
-    pub listed_count: Option<u32>,
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserPublicMetrics {
+    pub followers_count: Option<u64>,
+    pub following_count: Option<u64>,
+    pub tweet_count: Option<u64>,
+    pub listed_count: Option<u64>,
+    pub like_count: Option<u64>,
 }
@@ -0,0 +1,184 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use reqwest::Method;
+use std::path::PathBuf;
+use tweety_rs::api::cassette::{Cassette, CassetteMode, REDACTED, REDACTED_TOKEN};
+use tweety_rs::api::error::TweetyError;
+use tweety_rs::types::id::TweetId;
+use tweety_rs::TweetyClient;
+
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("tweety-{}-{}.json", std::process::id(), name))
+}
+
+fn client(server: &MockServer, cassette: Cassette, access_token: &str) -> TweetyClient {
+    TweetyClient::builder()
+        .consumer_key("consumer_key_1234")
+        .consumer_key_secret("consumer_secret_1234")
+        .access_token(access_token)
+        .access_token_secret("access_secret_1234")
+        .api_base_url(server.url())
+        .cassette(cassette)
+        .build()
+        .unwrap()
+}
+
+const TWEET: &str = r#"{"data":{"id":"20","text":"just setting up my twttr"}}"#;
+
+#[tokio::test]
+async fn test_record_then_replay_without_network() {
+    let server = MockServer::start().await;
+    server.enqueue(
+        MockResponse::json(200, TWEET)
+            .header("x-rate-limit-limit", "900")
+            .header("x-rate-limit-remaining", "899")
+            .header("x-rate-limit-reset", "4102444800"),
+    );
+    server.enqueue(MockResponse::json(
+        201,
+        r#"{"data":{"id":"21","text":"Hello"}}"#,
+    ));
+    let path = cassette_path("record-replay");
+
+    let recorder = client(
+        &server,
+        Cassette::open(&path, CassetteMode::Record).unwrap(),
+        "1234-access_token",
+    );
+    assert!(recorder.cassette().unwrap().is_recording());
+    let recorded_tweet = recorder.get_tweet_info(TweetId::new(20)).await.unwrap();
+    recorder.post_tweet("Hello", None).await.unwrap();
+    assert_eq!(server.requests().len(), 2);
+
+    let json = std::fs::read_to_string(&path).unwrap();
+    for secret in ["consumer_key_1234", "1234-access_token", "secret_1234"] {
+        assert!(!json.contains(secret), "{} leaked into {}", secret, json);
+    }
+    assert!(json.contains(REDACTED));
+    assert!(json.contains("oauth_nonce"));
+
+    // Other credentials, another nonce and timestamp: still replayed, and in order.
+    let player = client(
+        &server,
+        Cassette::open(&path, CassetteMode::Replay).unwrap(),
+        "5678-other_token",
+    );
+    let replayed_tweet = player.get_tweet_info(TweetId::new(20)).await.unwrap();
+    let posted = player.post_tweet("Hello", None).await.unwrap();
+    assert_eq!(replayed_tweet.raw(), recorded_tweet.raw());
+    assert_eq!(posted.data.id, TweetId::new(21));
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(player.cassette().unwrap().unplayed(), 0);
+    assert_eq!(
+        player
+            .rate_limit(Method::GET, "/2/tweets/:id")
+            .map(|rate_limit| rate_limit.remaining),
+        Some(899)
+    );
+
+    // Each interaction is replayed once.
+    let err = player.get_tweet_info(TweetId::new(20)).await.unwrap_err();
+    assert!(matches!(err, TweetyError::CassetteMismatch(_)), "{:?}", err);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_replay_rejects_unrecorded_requests() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        201,
+        r#"{"data":{"id":"21","text":"Hello"}}"#,
+    ));
+    let path = cassette_path("mismatch");
+
+    let recorder = client(
+        &server,
+        Cassette::open(&path, CassetteMode::Once).unwrap(),
+        "1234-access_token",
+    );
+    assert!(recorder.cassette().unwrap().is_recording());
+    recorder.post_tweet("Hello", None).await.unwrap();
+
+    let player = client(
+        &server,
+        Cassette::open(&path, CassetteMode::Once).unwrap(),
+        "1234-access_token",
+    );
+    assert!(!player.cassette().unwrap().is_recording());
+    let err = player.post_tweet("Goodbye", None).await.unwrap_err();
+    assert!(
+        matches!(err, TweetyError::CassetteMismatch(ref message) if message.contains("Goodbye")),
+        "{:?}",
+        err
+    );
+    assert_eq!(server.requests().len(), 1);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn test_tokens_are_scrubbed_from_bodies() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"token_type":"bearer","access_token":"AAAA-bearer_token"}"#,
+    ));
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"access_token":"AAAA-bearer_token"}"#,
+    ));
+    let path = cassette_path("bodies");
+
+    let recorder = client(
+        &server,
+        Cassette::open(&path, CassetteMode::Record).unwrap(),
+        "1234-access_token",
+    );
+    let token = recorder
+        .request_bearer_token("consumer_key_1234", "consumer_secret_1234")
+        .await
+        .unwrap();
+    recorder
+        .invalidate_bearer_token(
+            "consumer_key_1234",
+            "consumer_secret_1234",
+            token.access_token.expose(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(server.requests()[1].body, "access_token=AAAA-bearer_token");
+
+    let json = std::fs::read_to_string(&path).unwrap();
+    for secret in ["bearer_token", "consumer_key_1234", "secret_1234"] {
+        assert!(!json.contains(secret), "{} leaked into {}", secret, json);
+    }
+    assert!(json.contains(&format!("access_token={}", REDACTED_TOKEN)));
+    assert!(json.contains("grant_type=client_credentials"));
+
+    // Any token matches the scrubbed one.
+    let player = client(
+        &server,
+        Cassette::open(&path, CassetteMode::Replay).unwrap(),
+        "1234-access_token",
+    );
+    let token = player
+        .request_bearer_token("consumer_key", "consumer_secret")
+        .await
+        .unwrap();
+    assert_eq!(token.access_token.expose(), REDACTED_TOKEN);
+    player
+        .invalidate_bearer_token("consumer_key", "consumer_secret", "other_token")
+        .await
+        .unwrap();
+    assert_eq!(player.cassette().unwrap().unplayed(), 0);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_replaying_a_missing_cassette_fails() {
+    let err = Cassette::open(cassette_path("missing"), CassetteMode::Replay).unwrap_err();
+    assert!(matches!(err, TweetyError::FileIOError(_)), "{:?}", err);
+}
@@ -0,0 +1,145 @@
+use std::path::PathBuf;
+use tweety_rs::api::credentials::{Credentials, Secret};
+use tweety_rs::api::error::TweetyError;
+use tweety_rs::api::oauth1::AccessToken;
+use tweety_rs::api::oauth2::OAuth2Token;
+use tweety_rs::TweetyClient;
+
+fn write_config(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("tweety-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_client_debug_and_serialize_redact_secrets() {
+    let client = TweetyClient::builder()
+        .consumer_key("ck-value")
+        .consumer_key_secret("cks-value")
+        .access_token("at-value")
+        .access_token_secret("ats-value")
+        .build()
+        .unwrap();
+
+    let debug = format!("{:?}", client);
+    let json = serde_json::to_string(&client).unwrap();
+    for output in [&debug, &json] {
+        assert!(output.contains("[REDACTED]"));
+        for secret in ["ck-value", "cks-value", "at-value", "ats-value"] {
+            assert!(!output.contains(secret), "{} leaked in {}", secret, output);
+        }
+    }
+
+    let secret = Secret::new("hunter2");
+    assert_eq!(secret.to_string(), "[REDACTED]");
+    assert_eq!(secret.expose(), "hunter2");
+}
+
+#[test]
+fn test_secrets_serialize_in_full_to_be_persisted() {
+    let secret: Secret = serde_json::from_str(r#""hunter2""#).unwrap();
+    assert_eq!(secret.expose(), "hunter2");
+    assert_eq!(serde_json::to_string(&secret).unwrap(), r#""hunter2""#);
+
+    let credentials = Credentials::new("ck", "cks", "at", "ats");
+    let json = serde_json::to_string(&credentials).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Credentials>(&json).unwrap(),
+        credentials
+    );
+
+    let access_token: AccessToken = serde_json::from_str(
+        r#"{"oauth_token":"at","oauth_token_secret":"ats","user_id":"12","screen_name":"jack"}"#,
+    )
+    .unwrap();
+    let json = serde_json::to_string(&access_token).unwrap();
+    let access_token: AccessToken = serde_json::from_str(&json).unwrap();
+    assert_eq!(access_token.oauth_token.expose(), "at");
+    assert_eq!(access_token.oauth_token_secret.expose(), "ats");
+    assert!(!format!("{:?}", access_token).contains("ats"));
+
+    let token: OAuth2Token = serde_json::from_str(
+        r#"{"token_type":"bearer","access_token":"at2","refresh_token":"rt2","expires_in":7200,"scope":"tweet.read"}"#,
+    )
+    .unwrap();
+    let json = serde_json::to_string(&token).unwrap();
+    let token: OAuth2Token = serde_json::from_str(&json).unwrap();
+    assert_eq!(token.access_token.expose(), "at2");
+    assert_eq!(
+        token.refresh_token.as_ref().map(Secret::expose),
+        Some("rt2")
+    );
+    let debug = format!("{:?}", token);
+    assert!(
+        !debug.contains("at2") && !debug.contains("rt2"),
+        "{}",
+        debug
+    );
+}
+
+#[test]
+fn test_load_profiles_from_toml_and_json() {
+    let toml = write_config(
+        "accounts.toml",
+        r#"
+[personal]
+consumer_key = "ck"
+consumer_key_secret = "cks"
+access_token = "at-personal"
+access_token_secret = "ats-personal"
+
+[bot]
+consumer_key = "ck"
+consumer_key_secret = "cks"
+access_token = "at-bot"
+access_token_secret = "ats-bot"
+"#,
+    );
+    let profiles = Credentials::profiles(&toml).unwrap();
+    assert_eq!(profiles.len(), 2);
+    let bot = Credentials::from_profile(&toml, "bot").unwrap();
+    assert_eq!(bot.access_token.expose(), "at-bot");
+    assert_eq!(bot.access_token_secret.expose(), "ats-bot");
+
+    let json = write_config(
+        "accounts.json",
+        r#"{"personal":{"consumer_key":"ck","consumer_key_secret":"cks","access_token":"at-personal","access_token_secret":"ats-personal"}}"#,
+    );
+    let personal = Credentials::from_profile(&json, "personal").unwrap();
+    assert_eq!(
+        personal,
+        Credentials::new("ck", "cks", "at-personal", "ats-personal")
+    );
+
+    let client = TweetyClient::builder()
+        .credentials(personal)
+        .build()
+        .unwrap();
+    assert!(client.is_initialized());
+
+    let missing = Credentials::from_profile(&toml, "unknown").unwrap_err();
+    assert!(matches!(missing, TweetyError::ConfigError(message) if message.contains("unknown")));
+
+    std::fs::remove_file(toml).unwrap();
+    std::fs::remove_file(json).unwrap();
+}
+
+#[test]
+fn test_credentials_from_env() {
+    std::env::set_var("CONSUMER_API_KEY", "env-ck");
+    std::env::set_var("CONSUMER_API_SECRET", "env-cks");
+    std::env::set_var("ACCESS_TOKEN", "env-at");
+    std::env::set_var("ACCESS_TOKEN_SECRET", "env-ats");
+
+    let credentials = Credentials::from_env().unwrap();
+    assert_eq!(
+        credentials,
+        Credentials::new("env-ck", "env-cks", "env-at", "env-ats")
+    );
+
+    std::env::remove_var("ACCESS_TOKEN_SECRET");
+    let err = Credentials::from_env().unwrap_err();
+    assert!(
+        matches!(err, TweetyError::ConfigError(message) if message.contains("ACCESS_TOKEN_SECRET"))
+    );
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use common::{mock_client, MockResponse, MockServer};
+use tweety_rs::api::direct_messages::{EventType, QueryParams as DmQueryParams};
+use tweety_rs::api::fields::{DmEventField, Expansion, Fields, TweetField, UserField};
+use tweety_rs::api::search::{QueryParams, SortOrder};
+use tweety_rs::TweetyClient;
+
+#[test]
+fn test_fields_skip_duplicates_and_empty_lists() {
+    let fields = Fields::new()
+        .tweet_fields([TweetField::CreatedAt, TweetField::Lang])
+        .tweet_fields([TweetField::CreatedAt])
+        .expansions([Expansion::ReferencedTweetsIdAuthorId]);
+
+    assert_eq!(
+        fields.to_query_string(),
+        "expansions=referenced_tweets.id.author_id&tweet.fields=created_at,lang"
+    );
+    assert!(Fields::new().is_empty());
+    assert_eq!(Fields::new().to_query_string(), "");
+}
+
+#[tokio::test]
+async fn test_search_query_is_percent_escaped() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, r#"{"meta":{"result_count":0}}"#));
+
+    let client = mock_client(&server);
+    let params = QueryParams {
+        sort_order: Some(SortOrder::Recency),
+        fields: Fields::new()
+            .tweet_fields([TweetField::CreatedAt, TweetField::AuthorId])
+            .user_fields([UserField::Username]),
+        ..Default::default()
+    };
+    client
+        .recent_search("from:rust has:media #100DaysOfCode", Some(params))
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].path,
+        "/2/tweets/search/recent?query=from%3Arust+has%3Amedia+%23100DaysOfCode\
+         &sort_order=recency&tweet.fields=created_at%2Cauthor_id&user.fields=username"
+    );
+}
+
+#[tokio::test]
+async fn test_dm_query_has_no_quoted_values() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, r#"{"meta":{"result_count":0}}"#));
+    server.enqueue(MockResponse::json(200, r#"{"meta":{"result_count":0}}"#));
+
+    let client = mock_client(&server);
+    let params = DmQueryParams {
+        event_types: Some(vec![EventType::MessageCreate]),
+        max_results: Some(20),
+        fields: Fields::new()
+            .dm_event_fields([DmEventField::Id, DmEventField::SenderId])
+            .expansions([Expansion::SenderId]),
+        ..Default::default()
+    };
+    client.get_direct_messages(params).await.unwrap();
+
+    // Bearer requests go out with the URL as built, without OAuth 1.0a
+    // re-encoding its query, so an empty query would show up as a trailing `?`.
+    let bearer_client = TweetyClient::builder()
+        .bearer_token("bearer")
+        .api_base_url(server.url())
+        .build()
+        .unwrap();
+    bearer_client
+        .get_direct_messages(DmQueryParams::default())
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].path,
+        "/2/dm_events?event_types=MessageCreate&max_results=20\
+         &expansions=sender_id&dm_event.fields=id%2Csender_id"
+    );
+    assert_eq!(requests[1].path, "/2/dm_events");
+}
+
+#[tokio::test]
+async fn test_user_lookups_share_the_encoder() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":{"id":"1","name":"Me","username":"me"}}"#,
+    ));
+    server.enqueue(MockResponse::json(200, r#"{"data":[]}"#));
+
+    let client = mock_client(&server);
+    client.get_user_me(None).await.unwrap();
+    client
+        .get_users_by_usernames(
+            &["XDevelopers", "rustlang"],
+            Some(Fields::new().expansions([Expansion::PinnedTweetId])),
+        )
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/2/users/me");
+    assert_eq!(
+        requests[1].path,
+        "/2/users/by?usernames=XDevelopers%2Crustlang&expansions=pinned_tweet_id"
+    );
+}
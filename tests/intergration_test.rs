@@ -1,24 +1,36 @@
-use dotenvy::dotenv;
-use std::env;
+//! Replays the fixtures of `tests/fixtures`, offline.
+//!
+//! The fixtures are cassettes (see `api::cassette`) written by hand from the example
+//! responses of the API docs, not recordings of the live API. They are addressed to
+//! the reserved `fixtures.invalid` domain so they can't be mistaken for one, and are
+//! only ever replayed: a request that doesn't match a fixture fails the test.
+
+use std::path::Path;
+use tweety_rs::api::cassette::{Cassette, CassetteMode};
 use tweety_rs::api::direct_messages::*;
-use tweety_rs::api::user::{Expansions, TweetFields, UserFields, UserQueryParams};
+use tweety_rs::api::fields::{DmEventField, Expansion, Fields, MediaField, TweetField, UserField};
+use tweety_rs::types::id::{TweetId, UserId};
 use tweety_rs::types::tweet::PostTweetParams;
 use tweety_rs::TweetyClient;
 
-fn setup_client() -> TweetyClient {
-    dotenv().expect(".env file not found");
+const FIXTURES_BASE_URL: &str = "http://api.fixtures.invalid";
 
-    let consumer_key = env::var("CONSUMER_API_KEY").expect("CONSUMER_API_KEY not set");
-    let access_token = env::var("ACCESS_TOKEN").expect("ACCESS_TOKEN not set");
-    let consumer_secret = env::var("CONSUMER_API_SECRET").expect("CONSUMER_API_SECRET not set");
-    let access_secret = env::var("ACCESS_TOKEN_SECRET").expect("ACCESS_TOKEN_SECRET not set");
+fn setup_client(fixture: &str) -> TweetyClient {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(format!("{}.json", fixture));
+    let cassette = Cassette::open(path, CassetteMode::Replay).expect("failed to open fixture");
 
-    TweetyClient::new(
-        &consumer_key,
-        &access_token,
-        &consumer_secret,
-        &access_secret,
-    )
+    // Replaying works with any credentials.
+    TweetyClient::builder()
+        .consumer_key("consumer_key")
+        .consumer_key_secret("consumer_key_secret")
+        .access_token("access_token")
+        .access_token_secret("access_token_secret")
+        .api_base_url(FIXTURES_BASE_URL)
+        .cassette(cassette)
+        .build()
+        .expect("failed to build client")
 }
 
 #[cfg(test)]
@@ -29,8 +41,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_user_by_id() {
-        let client = setup_client();
-        let response_user_id = client.get_user_by_id("2244994945", None).await;
+        let client = setup_client("get_user_by_id");
+        let response_user_id = client.get_user_by_id(UserId::new(2244994945), None).await;
         assert!(
             response_user_id.is_ok(),
             "Request failed{:?}",
@@ -40,20 +52,19 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_user_me() {
-        let client = setup_client();
-        let params = UserQueryParams {
-            expansions: Some(Expansions::PinnedTweetId),
-            tweet_fields: Some(vec![
-                TweetFields::CreatedAt,
-                TweetFields::Text,
-                TweetFields::PublicMetrics,
-            ]),
-            user_fields: Some(vec![
-                UserFields::Name,
-                UserFields::Username,
-                UserFields::ProfileImageUrl,
-            ]),
-        };
+        let client = setup_client("get_user_me");
+        let params = Fields::new()
+            .expansions([Expansion::PinnedTweetId])
+            .tweet_fields([
+                TweetField::CreatedAt,
+                TweetField::Text,
+                TweetField::PublicMetrics,
+            ])
+            .user_fields([
+                UserField::Name,
+                UserField::Username,
+                UserField::ProfileImageUrl,
+            ]);
 
         let response = client.get_user_me(Some(params)).await;
         assert!(response.is_ok(), "Request failed {:?}", response);
@@ -62,7 +73,7 @@ mod tests {
     /// test post_tweet
     #[tokio::test]
     async fn test_post_a_tweet() {
-        let client = setup_client();
+        let client = setup_client("post_tweet");
         let message = String::from("Hello testing tweety-rs in the attic");
         let body_param = PostTweetParams {
             direct_message_deep_link: None,
@@ -82,24 +93,27 @@ mod tests {
     /// test get tweet info
     #[tokio::test]
     async fn test_get_tweet_info() {
-        let client = setup_client();
-        let response = client.get_tweet_info("1828783668107559176").await;
+        let client = setup_client("get_tweet_info");
+        let response = client
+            .get_tweet_info(TweetId::new(1828783668107559176))
+            .await;
         assert!(response.is_ok(), "Request failed {:?}", response);
     }
     /// test get dm messages
     #[tokio::test]
     async fn test_get_dm_messages() {
-        let client = setup_client();
+        let client = setup_client("get_direct_messages");
 
         let params = QueryParams {
-            dm_event_fields: Some(vec![DMEventField::Id, DMEventField::Text]),
             event_types: Some(vec![EventType::MessageCreate]),
-            expansions: Some(vec![Expansion::SenderId]),
             max_results: Some(50),
-            media_fields: Some(vec![MediaField::Url, MediaField::Type]),
             pagination_token: Some("pagination123".to_string()),
-            tweet_fields: Some(vec![TweetField::CreatedAt, TweetField::Text]),
-            user_fields: Some(vec![UserField::Username, UserField::Verified]),
+            fields: Fields::new()
+                .dm_event_fields([DmEventField::Id, DmEventField::Text])
+                .expansions([Expansion::SenderId])
+                .media_fields([MediaField::Url, MediaField::Type])
+                .tweet_fields([TweetField::CreatedAt, TweetField::Text])
+                .user_fields([UserField::Username, UserField::Verified]),
         };
 
         let response_user_id = client.get_direct_messages(params).await;
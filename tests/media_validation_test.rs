@@ -0,0 +1,206 @@
+use tweety_rs::api::error::MediaError;
+use tweety_rs::api::media_validation::{
+    image_dimensions, sniff_media_type, validate_media, MediaLimits,
+};
+use tweety_rs::api::uploads::MediaCategory;
+
+const MB: u64 = 1024 * 1024;
+
+fn ferris() -> Vec<u8> {
+    std::fs::read("tests/assets/ferrisTheRock.png").unwrap()
+}
+
+fn gif(width: u16, height: u16) -> Vec<u8> {
+    let mut gif = b"GIF89a".to_vec();
+    gif.extend_from_slice(&width.to_le_bytes());
+    gif.extend_from_slice(&height.to_le_bytes());
+    gif.extend_from_slice(&[0; 8]);
+    gif
+}
+
+/// SOI, an APP0 segment, then a baseline start of frame.
+fn jpeg(width: u16, height: u16) -> Vec<u8> {
+    let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10];
+    jpeg.extend_from_slice(b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0");
+    jpeg.extend_from_slice(&[0xFF, 0xC0, 0x00, 0x11, 0x08]);
+    jpeg.extend_from_slice(&height.to_be_bytes());
+    jpeg.extend_from_slice(&width.to_be_bytes());
+    jpeg.extend_from_slice(&[0x03; 10]);
+    jpeg
+}
+
+#[test]
+fn test_sniff_media_type() {
+    let mut webp = b"RIFF\0\0\0\0WEBPVP8X".to_vec();
+    webp.extend_from_slice(&[0; 8]);
+    let cases: [(&[u8], Option<&str>); 8] = [
+        (&ferris(), Some("image/png")),
+        (&jpeg(1, 1), Some("image/jpeg")),
+        (&gif(1, 1), Some("image/gif")),
+        (b"GIF87a", Some("image/gif")),
+        (&webp, Some("image/webp")),
+        (b"\0\0\0\x20ftypisom\0\0\x02\0", Some("video/mp4")),
+        (b"\0\0\0\x14ftypqt  \0\0\x02\0", Some("video/quicktime")),
+        (b"1\n00:00:01,000 --> 00:00:02,000\nHello", None),
+    ];
+    for (header, expected) in cases {
+        assert_eq!(sniff_media_type(header), expected, "{:?}", expected);
+    }
+    assert_eq!(sniff_media_type(b""), None);
+}
+
+#[test]
+fn test_only_mp4_brands_of_iso_media_are_videos() {
+    let heic = b"\0\0\0\x18ftypheic\0\0\0\0mif1heic";
+    assert_eq!(sniff_media_type(heic), None);
+    assert_eq!(sniff_media_type(b"\0\0\0\x18ftypmif1\0\0\0\0"), None);
+    assert_eq!(sniff_media_type(b"\0\0\0\x1cftypavif\0\0\0\0"), None);
+    assert_eq!(
+        sniff_media_type(b"\0\0\0\x18ftypmp42\0\0\0\0"),
+        Some("video/mp4")
+    );
+
+    assert_eq!(
+        validate_media(heic, 1000, "image/heic", MediaCategory::TweetImage),
+        Err(MediaError::UnsupportedType {
+            media_type: "image/heic".to_string(),
+            category: MediaCategory::TweetImage,
+        })
+    );
+}
+
+#[test]
+fn test_image_dimensions() {
+    assert_eq!(image_dimensions(&ferris()), Some((460, 344)));
+    assert_eq!(image_dimensions(&gif(320, 240)), Some((320, 240)));
+    assert_eq!(image_dimensions(&jpeg(1920, 1080)), Some((1920, 1080)));
+
+    let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0".to_vec();
+    webp.extend_from_slice(&[0x3F, 0x01, 0x00, 0xEF, 0x00, 0x00]);
+    assert_eq!(image_dimensions(&webp), Some((320, 240)));
+
+    // Cut before the start of frame.
+    assert_eq!(image_dimensions(&jpeg(1920, 1080)[..20]), None);
+    assert_eq!(image_dimensions(b"\0\0\0\x20ftypisom"), None);
+}
+
+#[test]
+fn test_valid_media() {
+    let png = ferris();
+    validate_media(
+        &png,
+        png.len() as u64,
+        "image/png",
+        MediaCategory::TweetImage,
+    )
+    .unwrap();
+    validate_media(&jpeg(4096, 4096), MB, "image/jpg", MediaCategory::DmImage).unwrap();
+    validate_media(
+        &gif(1280, 1080),
+        14 * MB,
+        "image/gif",
+        MediaCategory::TweetGif,
+    )
+    .unwrap();
+    validate_media(
+        b"\0\0\0\x20ftypisom",
+        500 * MB,
+        "video/mp4",
+        MediaCategory::TweetVideo,
+    )
+    .unwrap();
+    // Unknown content: the declared type is trusted.
+    validate_media(b"1\n00:00:01,000", 10, "text/srt", MediaCategory::Subtitles).unwrap();
+}
+
+#[test]
+fn test_each_limit_has_its_own_error() {
+    let png = ferris();
+    let cases = [
+        (
+            validate_media(&png, 0, "image/png", MediaCategory::TweetImage),
+            MediaError::Empty,
+        ),
+        (
+            validate_media(&png, 1000, "image/jpeg", MediaCategory::TweetImage),
+            MediaError::TypeMismatch {
+                declared: "image/jpeg".to_string(),
+                detected: "image/png",
+            },
+        ),
+        (
+            validate_media(&png, 1000, "image/png", MediaCategory::TweetVideo),
+            MediaError::UnsupportedType {
+                media_type: "image/png".to_string(),
+                category: MediaCategory::TweetVideo,
+            },
+        ),
+        (
+            validate_media(&png, 5 * MB + 1, "image/png", MediaCategory::TweetImage),
+            MediaError::TooLarge {
+                size: 5 * MB + 1,
+                limit: 5 * MB,
+                category: MediaCategory::TweetImage,
+            },
+        ),
+        (
+            validate_media(&gif(2, 2), 16 * MB, "image/gif", MediaCategory::TweetGif),
+            MediaError::TooLarge {
+                size: 16 * MB,
+                limit: 15 * MB,
+                category: MediaCategory::TweetGif,
+            },
+        ),
+        (
+            validate_media(
+                b"\0\0\0\x20ftypisom",
+                513 * MB,
+                "video/mp4",
+                MediaCategory::DmVideo,
+            ),
+            MediaError::TooLarge {
+                size: 513 * MB,
+                limit: 512 * MB,
+                category: MediaCategory::DmVideo,
+            },
+        ),
+        (
+            validate_media(&gif(1920, 1080), MB, "image/gif", MediaCategory::TweetGif),
+            MediaError::DimensionsTooLarge {
+                width: 1920,
+                height: 1080,
+                max_width: 1280,
+                max_height: 1080,
+                category: MediaCategory::TweetGif,
+            },
+        ),
+        (
+            validate_media(&jpeg(2, 600), MB, "image/jpeg", MediaCategory::TweetImage),
+            MediaError::DimensionsTooSmall {
+                width: 2,
+                height: 600,
+                min_width: 4,
+                min_height: 4,
+                category: MediaCategory::TweetImage,
+            },
+        ),
+    ];
+    for (result, expected) in cases {
+        assert_eq!(result, Err(expected));
+    }
+}
+
+#[test]
+fn test_limits_per_category() {
+    let image = MediaLimits::for_category(MediaCategory::TweetImage);
+    assert_eq!(image.max_bytes, Some(5 * MB));
+    assert!(image.media_types.contains(&"image/webp"));
+    assert_eq!(
+        MediaLimits::for_category(MediaCategory::DmGif).max_bytes,
+        Some(15 * MB)
+    );
+    assert_eq!(
+        MediaLimits::for_category(MediaCategory::TweetVideo).max_bytes,
+        Some(512 * MB)
+    );
+}
@@ -0,0 +1,546 @@
+mod common;
+
+use common::{mock_client, MockResponse, MockServer};
+use reqwest::Method;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tweety_rs::api::auth::Auth;
+use tweety_rs::api::error::{ApiErrorKind, ProblemType, TweetyError};
+use tweety_rs::api::rate_limit::RateLimitPolicy;
+use tweety_rs::api::retry::RetryPolicy;
+use tweety_rs::types::id::{TweetId, UserId};
+use tweety_rs::TweetyClient;
+
+#[tokio::test]
+async fn test_requests_go_to_configured_base_url() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":{"id":"1828783668107559176","text":"hello","edit_history_tweet_ids":["1828783668107559176"]}}"#,
+    ));
+
+    let client = mock_client(&server);
+    let response = client
+        .get_tweet_info(TweetId::new(1828783668107559176))
+        .await;
+    assert!(response.is_ok(), "Request failed {:?}", response);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/2/tweets/1828783668107559176");
+    assert!(requests[0]
+        .header("authorization")
+        .unwrap()
+        .starts_with("OAuth "));
+}
+
+#[tokio::test]
+async fn test_base_urls_default_to_production() {
+    let client = TweetyClient::builder()
+        .consumer_key("a")
+        .consumer_key_secret("b")
+        .access_token("c")
+        .access_token_secret("d")
+        .build()
+        .unwrap();
+    assert_eq!(client.api_base_url(), "https://api.x.com");
+    assert_eq!(client.upload_base_url(), "https://upload.twitter.com");
+
+    let client = client.with_api_base_url("http://127.0.0.1:9000/");
+    assert_eq!(client.api_base_url(), "http://127.0.0.1:9000");
+}
+
+#[tokio::test]
+async fn test_builder_requires_credentials() {
+    let result = TweetyClient::builder().consumer_key("a").build();
+    assert!(matches!(result, Err(TweetyError::MissingCredentials)));
+}
+
+#[tokio::test]
+async fn test_builder_sends_user_agent() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":{"id":"1","text":"hello"}}"#,
+    ));
+
+    let client = TweetyClient::builder()
+        .consumer_key("consumer_key")
+        .consumer_key_secret("consumer_key_secret")
+        .access_token("access_token")
+        .access_token_secret("access_token_secret")
+        .api_base_url(server.url())
+        .user_agent("my-bot/1.0")
+        .build()
+        .unwrap();
+
+    client.get_tweet_info(TweetId::new(1)).await.unwrap();
+    assert_eq!(
+        server.requests()[0].header("user-agent"),
+        Some("my-bot/1.0")
+    );
+}
+
+#[tokio::test]
+async fn test_put_sends_json_body() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, r#"{"data":{"hidden":true}}"#));
+
+    let client = mock_client(&server);
+    let response = client.hide_tweet(TweetId::new(1234)).await;
+    assert!(response.is_ok(), "Request failed {:?}", response);
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "PUT");
+    assert_eq!(request.path, "/2/tweets/1234/hidden");
+    assert_eq!(request.header("content-type"), Some("application/json"));
+    assert_eq!(request.body, r#"{"hidden":true}"#);
+}
+
+#[tokio::test]
+async fn test_user_actions_on_a_tweet_send_the_tweet_id() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, r#"{"data":{"retweeted":true}}"#));
+    server.enqueue(MockResponse::json(200, r#"{"data":{"liked":true}}"#));
+    server.enqueue(MockResponse::json(200, r#"{"data":{"bookmarked":true}}"#));
+
+    let client = mock_client(&server);
+    let (user_id, tweet_id) = (UserId::new(2244994945), TweetId::new(1228393702244134912));
+    assert!(
+        client
+            .retweet(user_id, tweet_id)
+            .await
+            .unwrap()
+            .data
+            .retweeted
+    );
+    assert!(
+        client
+            .like_a_post(user_id, tweet_id)
+            .await
+            .unwrap()
+            .data
+            .liked
+    );
+    assert!(
+        client
+            .bookmark_post(user_id, tweet_id)
+            .await
+            .unwrap()
+            .data
+            .bookmarked
+    );
+
+    let requests = server.requests();
+    let paths: Vec<_> = requests
+        .iter()
+        .map(|request| request.path.as_str())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "/2/users/2244994945/retweets",
+            "/2/users/2244994945/likes",
+            "/2/users/2244994945/bookmarks",
+        ]
+    );
+    for request in &requests {
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.header("content-type"), Some("application/json"));
+        assert_eq!(request.body, r#"{"tweet_id":"1228393702244134912"}"#);
+    }
+}
+
+#[tokio::test]
+async fn test_patch_does_not_panic() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":{"id":"1234","text":"edited","edit_history_tweet_ids":["1233","1234"]}}"#,
+    ));
+
+    let client = mock_client(&server);
+    let response = client
+        .edit_tweet("edited", TweetId::new(1234))
+        .await
+        .unwrap();
+    assert_eq!(response.data.text, "edited");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.method, "PATCH");
+    assert_eq!(request.body, r#"{"text":"edited"}"#);
+}
+
+#[tokio::test]
+async fn test_problem_json_is_parsed_into_twitter_error() {
+    let server = MockServer::start().await;
+    server.enqueue(
+        MockResponse::json(
+            403,
+            r#"{"detail":"You are not allowed to create a Tweet with duplicate content.","type":"about:blank","title":"Forbidden","status":403}"#,
+        )
+        .header("x-rate-limit-limit", "200")
+        .header("x-rate-limit-remaining", "199")
+        .header("x-rate-limit-reset", "1700000000"),
+    );
+
+    let client = mock_client(&server);
+    let err = client.post_tweet("hello", None).await.unwrap_err();
+    let api_error = err.api_error().expect("expected an API error");
+
+    assert_eq!(api_error.status, 403);
+    assert_eq!(api_error.title.as_deref(), Some("Forbidden"));
+    assert_eq!(api_error.kind(), ApiErrorKind::DuplicateContent);
+    assert!(api_error.is_duplicate_content());
+
+    let rate_limit = api_error.rate_limit.expect("expected rate-limit headers");
+    assert_eq!(rate_limit.limit, 200);
+    assert_eq!(rate_limit.remaining, 199);
+    assert_eq!(rate_limit.reset, 1700000000);
+}
+
+#[tokio::test]
+async fn test_error_kinds() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        404,
+        r#"{"errors":[{"parameters":{"id":["1"]},"message":"The id query parameter value [1] does not exist"}],"title":"Not Found Error","detail":"Could not find tweet with id: [1].","type":"https://api.twitter.com/2/problems/resource-not-found","status":404}"#,
+    ));
+    server.enqueue(MockResponse::json(
+        429,
+        r#"{"title":"Too Many Requests","detail":"Too Many Requests","type":"about:blank","status":429}"#,
+    ));
+    server.enqueue(MockResponse::json(
+        401,
+        r#"{"title":"Unauthorized","type":"about:blank","status":401,"detail":"Unauthorized"}"#,
+    ));
+
+    let client = mock_client(&server);
+
+    let not_found = client.get_tweet_info(TweetId::new(1)).await.unwrap_err();
+    let not_found = not_found.api_error().unwrap();
+    assert!(not_found.is_not_found());
+    assert_eq!(not_found.errors.len(), 1);
+
+    let rate_limited = client.get_tweet_info(TweetId::new(1)).await.unwrap_err();
+    assert!(rate_limited.api_error().unwrap().is_rate_limited());
+
+    let unauthorized = client.get_tweet_info(TweetId::new(1)).await.unwrap_err();
+    assert!(unauthorized.api_error().unwrap().is_not_authorized());
+}
+
+#[tokio::test]
+async fn test_search_and_mentions_keep_the_raw_json_and_parse_errors() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":[{"id":"1","text":"hi"}],"meta":{"result_count":1}}"#,
+    ));
+    server.enqueue(MockResponse::json(200, r#"{"meta":{"result_count":0}}"#));
+    server.enqueue(MockResponse::json(200, r#"{"data":[{"id":"1"}]}"#));
+
+    let client = mock_client(&server);
+    let search = client.recent_search("rust", None).await.unwrap();
+    assert_eq!(search.data[0].text, "hi");
+    assert_eq!(search.raw()["meta"]["result_count"], 1);
+
+    let mentions = client
+        .get_users_mentions(UserId::new(42), None)
+        .await
+        .unwrap();
+    assert!(mentions.data.is_empty());
+
+    match client.recent_search("rust", None).await.unwrap_err() {
+        TweetyError::JsonParseError(message) => {
+            assert!(message.contains("missing field `text`"), "{}", message)
+        }
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+fn epoch_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[tokio::test]
+async fn test_rate_limit_is_tracked_per_endpoint() {
+    let server = MockServer::start().await;
+    let reset = (epoch_secs() + 900).to_string();
+    server.enqueue(
+        MockResponse::json(200, r#"{"data":{"id":"1","text":"hi"}}"#)
+            .header("x-rate-limit-limit", "900")
+            .header("x-rate-limit-remaining", "899")
+            .header("x-rate-limit-reset", &reset),
+    );
+
+    let client = mock_client(&server);
+    client
+        .get_tweet_info(TweetId::new(1828783668107559176))
+        .await
+        .unwrap();
+
+    let rate_limit = client
+        .rate_limit(Method::GET, "/2/tweets/:id")
+        .expect("rate limit should be recorded");
+    assert_eq!(rate_limit.limit, 900);
+    assert_eq!(rate_limit.remaining, 899);
+    assert_eq!(
+        client.rate_limit(Method::GET, "/2/tweets/42"),
+        Some(rate_limit)
+    );
+    assert!(client.rate_limit(Method::DELETE, "/2/tweets/:id").is_none());
+    assert!(client.rate_limits().contains_key("GET /2/tweets/:id"));
+}
+
+#[tokio::test]
+async fn test_wait_and_retry_on_429() {
+    let server = MockServer::start().await;
+    let reset = epoch_secs().to_string();
+    server.enqueue(
+        MockResponse::json(429, r#"{"title":"Too Many Requests","status":429}"#)
+            .header("x-rate-limit-limit", "900")
+            .header("x-rate-limit-remaining", "0")
+            .header("x-rate-limit-reset", &reset),
+    );
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":{"id":"1","text":"hi"}}"#,
+    ));
+
+    let client = TweetyClient::builder()
+        .consumer_key("consumer_key")
+        .consumer_key_secret("consumer_key_secret")
+        .access_token("access_token")
+        .access_token_secret("access_token_secret")
+        .api_base_url(server.url())
+        .rate_limit_policy(RateLimitPolicy::WaitAndRetry {
+            max_wait: Duration::from_secs(5),
+        })
+        .build()
+        .unwrap();
+
+    let response = client.get_tweet_info(TweetId::new(1)).await;
+    assert!(response.is_ok(), "Request failed {:?}", response);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_429_fails_by_default() {
+    let server = MockServer::start().await;
+    server.enqueue(
+        MockResponse::json(429, r#"{"title":"Too Many Requests","status":429}"#)
+            .header("x-rate-limit-limit", "900")
+            .header("x-rate-limit-remaining", "0")
+            .header("x-rate-limit-reset", &epoch_secs().to_string()),
+    );
+
+    let client = mock_client(&server);
+    let err = client.get_tweet_info(TweetId::new(1)).await.unwrap_err();
+    assert!(err.api_error().unwrap().is_rate_limited());
+    assert_eq!(server.requests().len(), 1);
+}
+
+fn retrying_client(server: &MockServer) -> TweetyClient {
+    TweetyClient::builder()
+        .consumer_key("consumer_key")
+        .consumer_key_secret("consumer_key_secret")
+        .access_token("access_token")
+        .access_token_secret("access_token_secret")
+        .api_base_url(server.url())
+        .retry_policy(RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            jitter: false,
+            ..Default::default()
+        })
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_retry_on_503() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        503,
+        r#"{"title":"Service Unavailable"}"#,
+    ));
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":{"id":"1","text":"hi"}}"#,
+    ));
+
+    let client = retrying_client(&server);
+    let response = client.get_tweet_info(TweetId::new(1)).await;
+    assert!(response.is_ok(), "Request failed {:?}", response);
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_post_is_not_retried_by_default() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        503,
+        r#"{"title":"Service Unavailable"}"#,
+    ));
+    server.enqueue(MockResponse::json(
+        201,
+        r#"{"data":{"id":"1","text":"hi"}}"#,
+    ));
+
+    let client = retrying_client(&server);
+    let err = client.post_tweet("hello", None).await.unwrap_err();
+    assert_eq!(err.api_error().unwrap().status, 503);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn test_retry_gives_up_after_max_attempts() {
+    let server = MockServer::start().await;
+    for _ in 0..4 {
+        server.enqueue(MockResponse::json(502, r#"{"title":"Bad Gateway"}"#));
+    }
+
+    let client = retrying_client(&server);
+    let err = client.get_tweet_info(TweetId::new(1)).await.unwrap_err();
+    assert_eq!(err.api_error().unwrap().status, 502);
+    assert_eq!(server.requests().len(), 3);
+}
+
+#[test]
+fn test_retry_backoff_is_exponential_and_capped() {
+    let policy = RetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(350),
+        jitter: false,
+        ..Default::default()
+    };
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(3), Duration::from_millis(350));
+
+    let jittered = RetryPolicy {
+        jitter: true,
+        ..policy
+    };
+    let delay = jittered.backoff(2);
+    assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+}
+
+#[tokio::test]
+async fn test_app_only_bearer_token() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"token_type":"bearer","access_token":"AAAA%2FAAA%3DAAAAAAAA"}"#,
+    ));
+    server.enqueue(MockResponse::json(200, r#"{"meta":{"result_count":0}}"#));
+
+    let client = TweetyClient::builder()
+        .consumer_key("consumer_key")
+        .consumer_key_secret("consumer_key_secret")
+        .api_base_url(server.url())
+        .build_app_only()
+        .await
+        .unwrap();
+    assert!(matches!(client.auth(), Auth::Bearer { token } if token == "AAAA%2FAAA%3DAAAAAAAA"));
+
+    client
+        .full_archive_search("from:rustlang", None)
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/oauth2/token");
+    assert_eq!(
+        requests[0].header("authorization"),
+        Some("Basic Y29uc3VtZXJfa2V5OmNvbnN1bWVyX2tleV9zZWNyZXQ=")
+    );
+    assert_eq!(requests[0].body, "grant_type=client_credentials");
+    assert_eq!(
+        requests[1].header("authorization"),
+        Some("Bearer AAAA%2FAAA%3DAAAAAAAA")
+    );
+}
+
+#[tokio::test]
+async fn test_invalidate_bearer_token() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"access_token":"AAAA%2FAAA%3DAAAAAAAA"}"#,
+    ));
+
+    let client = TweetyClient::builder()
+        .bearer_token("AAAA%2FAAA%3DAAAAAAAA")
+        .api_base_url(server.url())
+        .build()
+        .unwrap();
+    let invalidated = client
+        .invalidate_bearer_token(
+            "consumer_key",
+            "consumer_key_secret",
+            "AAAA%2FAAA%3DAAAAAAAA",
+        )
+        .await
+        .unwrap();
+    assert_eq!(invalidated.access_token.expose(), "AAAA%2FAAA%3DAAAAAAAA");
+
+    let request = &server.requests()[0];
+    assert_eq!(request.path, "/oauth2/invalidate_token");
+    assert_eq!(request.body, "access_token=AAAA%252FAAA%253DAAAAAAAA");
+}
+
+#[tokio::test]
+async fn test_partial_errors_are_kept_next_to_data() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{
+          "data": [{"id": "2244994945", "name": "Developers", "username": "XDevelopers"}],
+          "errors": [
+            {"value": "1", "detail": "Could not find user with ids: [1].", "title": "Not Found Error", "resource_type": "user", "parameter": "ids", "resource_id": "1", "type": "https://api.twitter.com/2/problems/resource-not-found"},
+            {"value": "2", "detail": "User has been suspended: [2].", "title": "Forbidden", "resource_type": "user", "parameter": "ids", "resource_id": "2", "type": "https://api.twitter.com/2/problems/resource-unavailable"}
+          ],
+          "meta": {"result_count": 1}
+        }"#,
+    ));
+
+    let client = mock_client(&server);
+    let followers = client
+        .get_user_followers(UserId::new(2244994945), None)
+        .await
+        .unwrap();
+
+    assert_eq!(followers.data[0].username, "XDevelopers");
+    assert_eq!(followers.errors.len(), 2);
+    assert_eq!(followers.errors[0].resource_id.as_deref(), Some("1"));
+    assert_eq!(
+        followers.errors[0].problem_type(),
+        ProblemType::ResourceNotFound
+    );
+    assert_eq!(
+        followers.errors[1].problem_type(),
+        ProblemType::ResourceUnavailable
+    );
+    assert_eq!(followers.hydrate().errors.len(), 2);
+}
+
+#[tokio::test]
+async fn test_response_with_only_errors_is_an_error() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"errors":[{"value":"1","detail":"Could not find tweet with id: [1].","title":"Not Found Error","resource_type":"tweet","parameter":"id","resource_id":"1","type":"https://api.twitter.com/2/problems/resource-not-found"}]}"#,
+    ));
+
+    let client = mock_client(&server);
+    let err = client.get_tweet_info(TweetId::new(1)).await.unwrap_err();
+    let api_error = err.api_error().expect("expected an API error");
+
+    assert_eq!(api_error.status, 200);
+    assert_eq!(api_error.problem_type(), ProblemType::ResourceNotFound);
+    assert!(api_error.is_not_found());
+}
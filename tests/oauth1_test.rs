@@ -0,0 +1,127 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use tweety_rs::api::error::TweetyError;
+use tweety_rs::api::oauth1::OAuth1Client;
+
+#[tokio::test]
+async fn test_pin_based_flow_obtains_access_token() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        "oauth_token=request-token&oauth_token_secret=request-secret&oauth_callback_confirmed=true",
+    ));
+    server.enqueue(MockResponse::json(
+        200,
+        "oauth_token=access-token&oauth_token_secret=access-secret&user_id=6253282&screen_name=XDevelopers",
+    ));
+
+    let oauth1 = OAuth1Client::new("consumer_key", "consumer_secret").api_base_url(server.url());
+    let request_token = oauth1.request_token("oob").await.unwrap();
+    assert_eq!(request_token.oauth_token, "request-token");
+    assert_eq!(request_token.oauth_token_secret.expose(), "request-secret");
+    assert!(request_token.oauth_callback_confirmed);
+
+    let authorize_url = oauth1.authorize_url(&request_token).unwrap();
+    assert_eq!(
+        authorize_url.as_str(),
+        format!("{}/oauth/authorize?oauth_token=request-token", server.url())
+    );
+
+    let access_token = oauth1
+        .access_token(&request_token, "1234567")
+        .await
+        .unwrap();
+    assert_eq!(access_token.oauth_token.expose(), "access-token");
+    assert_eq!(access_token.oauth_token_secret.expose(), "access-secret");
+    assert_eq!(access_token.user_id.as_deref(), Some("6253282"));
+    assert_eq!(access_token.screen_name.as_deref(), Some("XDevelopers"));
+    let debug = format!("{:?} {:?} {:?}", oauth1, request_token, access_token);
+    for secret in ["request-secret", "access-token", "access-secret"] {
+        assert!(!debug.contains(secret), "{} leaked in {}", secret, debug);
+    }
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert!(requests[0].path.starts_with("/oauth/request_token"));
+    let authorization = requests[0].header("authorization").unwrap();
+    assert!(authorization.starts_with("OAuth "));
+    assert!(authorization.contains("oauth_callback=\"oob\""));
+    assert!(authorization.contains("oauth_consumer_key=\"consumer_key\""));
+
+    assert!(requests[1].path.starts_with("/oauth/access_token"));
+    let authorization = requests[1].header("authorization").unwrap();
+    assert!(authorization.contains("oauth_token=\"request-token\""));
+    assert!(authorization.contains("oauth_verifier=\"1234567\""));
+}
+
+#[tokio::test]
+async fn test_request_token_failure_is_reported() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        401,
+        r#"{"errors":[{"code":32,"message":"Could not authenticate you."}]}"#,
+    ));
+
+    let oauth1 = OAuth1Client::new("consumer_key", "wrong_secret").api_base_url(server.url());
+    let err = oauth1.request_token("oob").await.unwrap_err();
+    let api_error = err.api_error().expect("expected an API error");
+    assert_eq!(api_error.status, 401);
+    assert!(api_error.is_not_authorized());
+}
+
+#[tokio::test]
+async fn test_incomplete_token_response_is_an_oauth_error() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, "oauth_callback_confirmed=true"));
+
+    let oauth1 = OAuth1Client::new("consumer_key", "consumer_secret").api_base_url(server.url());
+    let err = oauth1.request_token("oob").await.unwrap_err();
+    assert!(matches!(err, TweetyError::OAuthError(_)));
+}
+
+#[tokio::test]
+async fn test_invalidate_access_token() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"access_token":"access-token"}"#,
+    ));
+
+    let oauth1 = OAuth1Client::new("consumer_key", "consumer_secret").api_base_url(server.url());
+    let invalidated = oauth1
+        .invalidate_token("access-token", "access-secret")
+        .await
+        .unwrap();
+    assert_eq!(invalidated.access_token.expose(), "access-token");
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/1.1/oauth/invalidate_token");
+    assert!(requests[0]
+        .header("authorization")
+        .unwrap()
+        .contains("oauth_token=\"access-token\""));
+}
+
+#[tokio::test]
+async fn test_malformed_base_url_is_an_error() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        "oauth_token=request-token&oauth_token_secret=request-secret&oauth_callback_confirmed=true",
+    ));
+    let request_token = OAuth1Client::new("consumer_key", "consumer_secret")
+        .api_base_url(server.url())
+        .request_token("oob")
+        .await
+        .unwrap();
+
+    let oauth1 = OAuth1Client::new("consumer_key", "consumer_secret").api_base_url("not a url");
+    for result in [
+        oauth1.authorize_url(&request_token),
+        oauth1.authenticate_url(&request_token),
+    ] {
+        let err = result.unwrap_err();
+        assert!(matches!(err, TweetyError::UrlParseError(_)), "{:?}", err);
+    }
+}
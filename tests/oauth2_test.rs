@@ -0,0 +1,212 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use std::time::Duration;
+use tweety_rs::api::credentials::Secret;
+use tweety_rs::api::error::TweetyError;
+use tweety_rs::api::oauth2::{listen_for_code, OAuth2Client, OAuth2Token, PkceChallenge, Scope};
+use tweety_rs::types::id::TweetId;
+use tweety_rs::TweetyClient;
+
+#[test]
+fn test_pkce_challenge_is_base64url_sha256_of_verifier() {
+    let pkce = PkceChallenge::from_verifier("dBjftJeZ4CVP-mJ92K9Dp7o9xUSvBxmGS6RZJ8xYfYa");
+    assert_eq!(
+        pkce.challenge,
+        "ffLwbDtOro8inzfK-cCyCwN0a44LA3GJrzOtgszDgpc"
+    );
+
+    let generated = PkceChallenge::new();
+    assert_eq!(generated.verifier.len(), 64);
+    assert_ne!(generated.verifier, PkceChallenge::new().verifier);
+}
+
+#[test]
+fn test_authorization_url_contains_scopes_and_challenge() {
+    let oauth2 = OAuth2Client::new("client_id", "http://127.0.0.1:8976/callback").scopes(vec![
+        Scope::TweetRead,
+        Scope::DmRead,
+        Scope::OfflineAccess,
+    ]);
+    let request = oauth2.authorization_request().unwrap();
+
+    let params: Vec<(String, String)> = request.url.query_pairs().into_owned().collect();
+    let param = |name: &str| {
+        params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    assert!(request
+        .url
+        .as_str()
+        .starts_with("https://x.com/i/oauth2/authorize?"));
+    assert_eq!(param("response_type"), Some("code"));
+    assert_eq!(param("client_id"), Some("client_id"));
+    assert_eq!(
+        param("redirect_uri"),
+        Some("http://127.0.0.1:8976/callback")
+    );
+    assert_eq!(param("scope"), Some("tweet.read dm.read offline.access"));
+    assert_eq!(param("state"), Some(request.state.as_str()));
+    assert_eq!(
+        param("code_challenge"),
+        Some(request.pkce.challenge.as_str())
+    );
+    assert_eq!(param("code_challenge_method"), Some("S256"));
+}
+
+#[test]
+fn test_malformed_authorize_url_is_an_error() {
+    let oauth2 =
+        OAuth2Client::new("client_id", "http://127.0.0.1:8976/callback").authorize_url("not a url");
+
+    let err = oauth2.authorization_request().unwrap_err();
+    assert!(matches!(err, TweetyError::UrlParseError(_)), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_exchange_code_and_refresh_when_expired() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"token_type":"bearer","expires_in":0,"access_token":"first","refresh_token":"refresh-1","scope":"tweet.read offline.access"}"#,
+    ));
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"token_type":"bearer","expires_in":7200,"access_token":"second","refresh_token":"refresh-2","scope":"tweet.read offline.access"}"#,
+    ));
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":{"id":"1","text":"hi"}}"#,
+    ));
+
+    let oauth2 =
+        OAuth2Client::new("client_id", "http://127.0.0.1/callback").api_base_url(server.url());
+    let token = oauth2
+        .exchange_code("the-code", "the-verifier")
+        .await
+        .unwrap();
+    assert_eq!(token.access_token.expose(), "first");
+    assert!(token.is_expired());
+
+    let client = TweetyClient::builder()
+        .oauth2(oauth2, token)
+        .api_base_url(server.url())
+        .build()
+        .unwrap();
+    client.get_tweet_info(TweetId::new(1)).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/2/oauth2/token");
+    assert!(requests[0].body.contains("grant_type=authorization_code"));
+    assert!(requests[0].body.contains("code=the-code"));
+    assert!(requests[0].body.contains("code_verifier=the-verifier"));
+
+    assert_eq!(requests[1].path, "/2/oauth2/token");
+    assert!(requests[1].body.contains("grant_type=refresh_token"));
+    assert!(requests[1].body.contains("refresh_token=refresh-1"));
+
+    assert_eq!(requests[2].header("authorization"), Some("Bearer second"));
+    let current = client.oauth2_token().unwrap();
+    assert_eq!(
+        current.refresh_token.as_ref().map(Secret::expose),
+        Some("refresh-2")
+    );
+    assert!(!current.is_expired());
+}
+
+#[tokio::test]
+async fn test_token_is_not_refreshed_before_expiry() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":{"id":"1","text":"hi"}}"#,
+    ));
+
+    let token: OAuth2Token = serde_json::from_str(
+        r#"{"token_type":"bearer","access_token":"valid","refresh_token":"refresh","expires_in":7200,"scope":null,"expires_at":null}"#,
+    )
+    .unwrap();
+    let client = TweetyClient::builder()
+        .oauth2(
+            OAuth2Client::new("client_id", "http://127.0.0.1/callback").api_base_url(server.url()),
+            token,
+        )
+        .api_base_url(server.url())
+        .build()
+        .unwrap();
+    client.get_tweet_info(TweetId::new(1)).await.unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("authorization"), Some("Bearer valid"));
+}
+
+#[tokio::test]
+async fn test_expired_token_without_refresh_token_is_an_error() {
+    let server = MockServer::start().await;
+    let token: OAuth2Token = serde_json::from_str(
+        r#"{"token_type":"bearer","access_token":"expired","expires_in":0,"expires_at":0}"#,
+    )
+    .unwrap();
+    let client = TweetyClient::builder()
+        .oauth2(
+            OAuth2Client::new("client_id", "http://127.0.0.1/callback").api_base_url(server.url()),
+            token,
+        )
+        .api_base_url(server.url())
+        .build()
+        .unwrap();
+
+    let err = client.get_tweet_info(TweetId::new(1)).await.unwrap_err();
+    assert!(matches!(err, TweetyError::OAuthError(_)), "{:?}", err);
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_loopback_listener_captures_code() {
+    let listener = tokio::spawn(listen_for_code("127.0.0.1:18976", "expected-state"));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let body = reqwest::get("http://127.0.0.1:18976/callback?state=expected-state&code=abc123")
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(body.contains("Authorization complete"));
+    assert_eq!(listener.await.unwrap().unwrap(), "abc123");
+}
+
+#[tokio::test]
+async fn test_loopback_listener_ignores_unrelated_requests() {
+    let listener = tokio::spawn(listen_for_code("127.0.0.1:18978", "expected-state"));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let favicon = reqwest::get("http://127.0.0.1:18978/favicon.ico")
+        .await
+        .unwrap();
+    assert_eq!(favicon.status(), 404);
+    assert!(!listener.is_finished());
+
+    reqwest::get("http://127.0.0.1:18978/callback?state=expected-state&code=abc123")
+        .await
+        .unwrap();
+    assert_eq!(listener.await.unwrap().unwrap(), "abc123");
+}
+
+#[tokio::test]
+async fn test_loopback_listener_rejects_state_mismatch() {
+    let listener = tokio::spawn(listen_for_code("127.0.0.1:18977", "expected-state"));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    reqwest::get("http://127.0.0.1:18977/callback?state=forged&code=abc123")
+        .await
+        .unwrap();
+    assert!(matches!(
+        listener.await.unwrap(),
+        Err(TweetyError::OAuthError(_))
+    ));
+}
@@ -0,0 +1,183 @@
+mod common;
+
+use common::{mock_client, MockResponse, MockServer};
+use futures::StreamExt;
+use serde_json::Value;
+use std::time::Duration;
+use tweety_rs::api::pagination::{Checkpoint, Paginator};
+use tweety_rs::api::search::QueryParams;
+use tweety_rs::types::id::UserId;
+use tweety_rs::types::user::User;
+
+fn followers_page(ids: &[&str], next_token: Option<&str>) -> MockResponse {
+    let data: Vec<Value> = ids
+        .iter()
+        .map(|id| serde_json::json!({"id": id, "name": "Name", "username": format!("user{}", id)}))
+        .collect();
+    let mut meta = serde_json::json!({"result_count": ids.len()});
+    if let Some(token) = next_token {
+        meta["next_token"] = Value::from(token);
+    }
+    MockResponse::json(
+        200,
+        &serde_json::json!({"data": data, "meta": meta}).to_string(),
+    )
+}
+
+#[tokio::test]
+async fn test_items_follow_pagination_token_until_exhausted() {
+    let server = MockServer::start().await;
+    server.enqueue(followers_page(&["1", "2"], Some("page-2")));
+    server.enqueue(followers_page(&["3", "4"], Some("page-3")));
+    server.enqueue(followers_page(&["5"], None));
+
+    let client = mock_client(&server);
+    let ids: Vec<u64> = client
+        .paginate_user_followers(UserId::new(42), None)
+        .items()
+        .map(|follower| follower.unwrap().id.get())
+        .collect()
+        .await;
+    assert_eq!(ids, [1, 2, 3, 4, 5]);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].path.starts_with("/2/users/42/followers"));
+    assert!(!requests[0].path.contains("pagination_token"));
+    assert!(requests[1].path.contains("pagination_token=page-2"));
+    assert!(requests[2].path.contains("pagination_token=page-3"));
+}
+
+#[tokio::test]
+async fn test_max_items_stops_without_fetching_more_pages() {
+    let server = MockServer::start().await;
+    server.enqueue(followers_page(&["1", "2"], Some("page-2")));
+    server.enqueue(followers_page(&["3", "4"], Some("page-3")));
+
+    let client = mock_client(&server);
+    let pages: Vec<_> = client
+        .paginate_users_following(UserId::new(42), None)
+        .max_items(3)
+        .pages()
+        .collect()
+        .await;
+
+    assert_eq!(pages.len(), 2);
+    let last = pages[1].as_ref().unwrap();
+    assert_eq!(last.data.len(), 1);
+    assert_eq!(last.next_token.as_deref(), Some("page-3"));
+    assert_eq!(last.meta.as_ref().unwrap().result_count, 2);
+    assert!(last.includes.is_none());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_search_uses_next_token_and_starts_from_given_token() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":[{"id":"10","text":"hi"}],"meta":{"result_count":1,"next_token":"b"}}"#,
+    ));
+    server.enqueue(MockResponse::json(200, r#"{"meta":{"result_count":0}}"#));
+
+    let client = mock_client(&server);
+    let params = QueryParams {
+        next_token: Some("a".to_string()),
+        ..Default::default()
+    };
+    let items: Vec<_> = client
+        .paginate_full_archive_search("rust", Some(params))
+        .items()
+        .collect()
+        .await;
+    assert_eq!(items.len(), 1);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].path.contains("next_token=a"));
+    assert!(requests[1].path.contains("next_token=b"));
+    assert!(!requests[1].path.contains("next_token=a"));
+}
+
+#[tokio::test]
+async fn test_stream_ends_after_an_error() {
+    let server = MockServer::start().await;
+    server.enqueue(followers_page(&["1"], Some("page-2")));
+    server.enqueue(MockResponse::json(
+        503,
+        r#"{"title":"Service Unavailable","detail":"Try again later","type":"about:blank","status":503}"#,
+    ));
+
+    let client = mock_client(&server);
+    let items: Vec<_> = client
+        .paginate_user_followers(UserId::new(42), None)
+        .items()
+        .collect()
+        .await;
+
+    assert_eq!(items.len(), 2);
+    assert!(items[0].is_ok());
+    assert_eq!(
+        items[1].as_ref().unwrap_err().api_error().unwrap().status,
+        503
+    );
+}
+
+#[tokio::test]
+async fn test_checkpoint_save_and_resume() {
+    let server = MockServer::start().await;
+    server.enqueue(
+        followers_page(&["1", "2"], Some("page-2"))
+            .header("x-rate-limit-limit", "15")
+            .header("x-rate-limit-remaining", "0")
+            .header("x-rate-limit-reset", "1"),
+    );
+    server.enqueue(followers_page(&["3"], Some("page-3")));
+    server.enqueue(followers_page(&["4"], None));
+
+    let client = mock_client(&server);
+    let mut pages = client
+        .paginate_user_followers(UserId::new(42), None)
+        .pages();
+    let first = pages.next().await.unwrap().unwrap();
+    drop(pages);
+
+    let checkpoint = first.checkpoint;
+    assert_eq!(checkpoint.next_token.as_deref(), Some("page-2"));
+    assert_eq!(checkpoint.items_seen, 2);
+    assert!(!checkpoint.done);
+    assert_eq!(checkpoint.rate_limit.unwrap().remaining, 0);
+    assert_eq!(checkpoint.ready_in(), Duration::ZERO);
+
+    let path = std::env::temp_dir().join(format!("tweety-{}-checkpoint.json", std::process::id()));
+    checkpoint.save(&path).unwrap();
+    let loaded = Checkpoint::load(&path).unwrap();
+    assert_eq!(loaded, checkpoint);
+    std::fs::remove_file(&path).unwrap();
+
+    let resumed = mock_client(&server);
+    let pages: Vec<_> = Paginator::<User>::resume(&resumed, loaded)
+        .pages()
+        .collect()
+        .await;
+    let ids: Vec<u64> = pages
+        .iter()
+        .flat_map(|page| page.as_ref().unwrap().data.iter().map(|f| f.id.get()))
+        .collect();
+    assert_eq!(ids, [3, 4]);
+
+    let last = pages.last().unwrap().as_ref().unwrap().checkpoint.clone();
+    assert_eq!(last.items_seen, 4);
+    assert!(last.done);
+
+    let requests = server.requests();
+    assert!(requests[1].path.contains("pagination_token=page-2"));
+    assert!(requests[2].path.contains("pagination_token=page-3"));
+
+    let nothing: Vec<_> = Paginator::<User>::resume(&resumed, last)
+        .items()
+        .collect()
+        .await;
+    assert!(nothing.is_empty());
+    assert_eq!(server.requests().len(), 3);
+}
@@ -0,0 +1,247 @@
+mod common;
+
+use common::{mock_client, MockResponse, MockServer};
+use futures::StreamExt;
+use std::time::{Duration, UNIX_EPOCH};
+use tweety_rs::api::error::TweetyError;
+use tweety_rs::api::mentions;
+use tweety_rs::api::search::QueryParams;
+use tweety_rs::types::id::UserId;
+use tweety_rs::types::timestamp::Timestamp;
+use tweety_rs::types::tweet::Tweet;
+
+#[test]
+fn test_rfc3339_parsing() {
+    let utc: Timestamp = "2019-12-31T19:26:16.000Z".parse().unwrap();
+    assert_eq!(utc.unix_timestamp(), 1577820376);
+    assert_eq!(utc.subsec_nanos(), 0);
+
+    let offset: Timestamp = "2019-12-31T21:26:16.25+02:00".parse().unwrap();
+    assert_eq!(offset.unix_timestamp(), utc.unix_timestamp());
+    assert_eq!(offset.subsec_nanos(), 250_000_000);
+    assert_eq!(offset.to_string(), "2019-12-31T19:26:16.250Z");
+
+    let leap_day: Timestamp = "2024-02-29T00:00:00Z".parse().unwrap();
+    assert_eq!(leap_day.to_rfc3339_seconds(), "2024-02-29T00:00:00Z");
+    let before_epoch: Timestamp = "1969-12-31T23:59:59Z".parse().unwrap();
+    assert_eq!(before_epoch.unix_timestamp(), -1);
+    assert_eq!(before_epoch.to_string(), "1969-12-31T23:59:59Z");
+
+    for invalid in [
+        "2019-12-31",
+        "2019-12-31T19:26:16",
+        "2023-02-29T00:00:00Z",
+        "2019-13-01T00:00:00Z",
+        "2019-12-31T24:00:00Z",
+        "2019-12-31T19:26:16.Z",
+        "2019-12-31T19:26:16+0200",
+        "yesterday",
+    ] {
+        assert!(invalid.parse::<Timestamp>().is_err(), "{}", invalid);
+    }
+}
+
+#[test]
+fn test_rfc3339_edge_cases() {
+    let unix = |input: &str| input.parse::<Timestamp>().unwrap().unix_timestamp();
+
+    // Leap years: every 4 years, except centuries not divisible by 400.
+    assert_eq!(unix("2000-02-29T00:00:00Z"), 951782400);
+    assert_eq!(
+        unix("2000-03-01T00:00:00Z") - unix("2000-02-28T00:00:00Z"),
+        2 * 86_400
+    );
+    assert_eq!(
+        unix("2100-03-01T00:00:00Z") - unix("2100-02-28T00:00:00Z"),
+        86_400
+    );
+    for invalid in [
+        "1900-02-29T00:00:00Z",
+        "2100-02-29T00:00:00Z",
+        "2024-04-31T00:00:00Z",
+    ] {
+        assert!(invalid.parse::<Timestamp>().is_err(), "{}", invalid);
+    }
+
+    // Offsets move the date across day and year boundaries.
+    let new_year: Timestamp = "2020-01-01T00:30:00+01:00".parse().unwrap();
+    assert_eq!(new_year.to_string(), "2019-12-31T23:30:00Z");
+    let west: Timestamp = "2019-12-31T23:30:00-05:30".parse().unwrap();
+    assert_eq!(west.to_string(), "2020-01-01T05:00:00Z");
+    assert_eq!(unix("1970-01-01T00:00:00-00:00"), 0);
+    for invalid in ["2019-12-31T19:26:16+24:00", "2019-12-31T19:26:16+02:60"] {
+        assert!(invalid.parse::<Timestamp>().is_err(), "{}", invalid);
+    }
+
+    // Fractional seconds of any precision, truncated to nanoseconds.
+    let nanos = |input: &str| input.parse::<Timestamp>().unwrap().subsec_nanos();
+    assert_eq!(nanos("2019-12-31T19:26:16.5Z"), 500_000_000);
+    assert_eq!(nanos("2019-12-31T19:26:16.000000001Z"), 1);
+    assert_eq!(nanos("2019-12-31T19:26:16.1234567899Z"), 123_456_789);
+
+    // A leap second is folded into the next second.
+    assert_eq!(unix("2016-12-31T23:59:60Z"), unix("2017-01-01T00:00:00Z"));
+    // Lowercase separators and a space instead of `T` are allowed by RFC 3339.
+    assert_eq!(unix("2019-12-31 19:26:16z"), unix("2019-12-31T19:26:16Z"));
+}
+
+#[test]
+fn test_display_roundtrips_at_full_precision() {
+    for (nanos, shown) in [
+        (0, "2019-12-31T19:26:16Z"),
+        (500_000_000, "2019-12-31T19:26:16.500Z"),
+        (123_456_000, "2019-12-31T19:26:16.123456Z"),
+        (1, "2019-12-31T19:26:16.000000001Z"),
+        (123_456_789, "2019-12-31T19:26:16.123456789Z"),
+    ] {
+        let timestamp = Timestamp::from_unix(1577820376, nanos);
+        assert_eq!(timestamp.to_string(), shown);
+        assert_eq!(shown.parse::<Timestamp>().unwrap(), timestamp);
+
+        let json = serde_json::to_string(&timestamp).unwrap();
+        assert_eq!(serde_json::from_str::<Timestamp>(&json).unwrap(), timestamp);
+    }
+
+    let before_epoch = Timestamp::from_unix(-1, 999_999_999);
+    assert_eq!(before_epoch.to_string(), "1969-12-31T23:59:59.999999999Z");
+    assert_eq!(
+        before_epoch.to_string().parse::<Timestamp>().unwrap(),
+        before_epoch
+    );
+}
+
+#[test]
+fn test_models_parse_timestamps() {
+    let tweet: Tweet = serde_json::from_str(
+        r#"{"id":"1","text":"hi","created_at":"2019-12-31T19:26:16.000Z","edit_controls":{"edits_remaining":5,"is_edit_eligible":true,"editable_until":"2019-12-31T20:26:16.000Z"}}"#,
+    )
+    .unwrap();
+    let created_at = tweet.created_at.unwrap();
+    let editable_until = tweet.edit_controls.unwrap().editable_until;
+    assert_eq!(
+        editable_until.unix_timestamp() - created_at.unix_timestamp(),
+        3600
+    );
+    assert_eq!(
+        std::time::SystemTime::from(created_at),
+        UNIX_EPOCH + Duration::from_secs(1577820376)
+    );
+
+    let json = serde_json::to_value(created_at).unwrap();
+    assert_eq!(json, "2019-12-31T19:26:16Z");
+    assert!(
+        serde_json::from_str::<Tweet>(r#"{"id":"1","text":"hi","created_at":"Tue Dec 31"}"#)
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_search_times_are_sent_with_seconds_precision() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, r#"{"meta":{"result_count":0}}"#));
+
+    let client = mock_client(&server);
+    let params = QueryParams {
+        start_time: Some("2024-01-01T00:00:00.123Z".parse().unwrap()),
+        end_time: Some("2024-01-02T00:00:00Z".parse().unwrap()),
+        ..Default::default()
+    };
+    client.recent_search("rust", Some(params)).await.unwrap();
+
+    assert_eq!(
+        server.requests()[0].path,
+        "/2/tweets/search/recent?query=rust&end_time=2024-01-02T00%3A00%3A00Z\
+         &start_time=2024-01-01T00%3A00%3A00Z"
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_time_ranges_are_rejected_before_sending() {
+    let server = MockServer::start().await;
+    let client = mock_client(&server);
+
+    let reversed = QueryParams {
+        start_time: Some("2024-01-02T00:00:00Z".parse().unwrap()),
+        end_time: Some("2024-01-01T00:00:00Z".parse().unwrap()),
+        ..Default::default()
+    };
+    let future = QueryParams {
+        end_time: Some((std::time::SystemTime::now() + Duration::from_secs(3600)).into()),
+        ..Default::default()
+    };
+
+    for params in [reversed, future] {
+        let err = client
+            .recent_search("rust", Some(params.clone()))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TweetyError::InvalidParameter(_)), "{:?}", err);
+
+        // The paginators validate the same parameters before the first request.
+        for paginator in [
+            client.paginate_recent_search("rust", Some(params.clone())),
+            client.paginate_full_archive_search("rust", Some(params)),
+        ] {
+            let pages: Vec<_> = paginator.pages().collect().await;
+            assert_eq!(pages.len(), 1);
+            assert!(matches!(pages[0], Err(TweetyError::InvalidParameter(_))));
+        }
+    }
+    let mentions = mentions::QueryParams {
+        end_time: Some((std::time::SystemTime::now() + Duration::from_secs(3600)).into()),
+        ..Default::default()
+    };
+    let items: Vec<_> = client
+        .paginate_users_mentions(UserId::new(42), Some(mentions))
+        .items()
+        .collect()
+        .await;
+    assert!(matches!(items[..], [Err(TweetyError::InvalidParameter(_))]));
+    assert!(server.requests().is_empty());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_conversions() {
+    let timestamp: Timestamp = "2019-12-31T19:26:16.5Z".parse().unwrap();
+    let date_time = chrono::DateTime::<chrono::Utc>::try_from(timestamp).unwrap();
+    assert_eq!(date_time.to_rfc3339(), "2019-12-31T19:26:16.500+00:00");
+    assert_eq!(Timestamp::from(date_time), timestamp);
+
+    let out_of_range = Timestamp::from_unix(i64::MAX, 0);
+    let err = chrono::DateTime::<chrono::Utc>::try_from(out_of_range).unwrap_err();
+    assert_eq!(err.timestamp, out_of_range);
+}
+
+/// The parser agrees with chrono's on the edge cases of the format.
+#[cfg(feature = "chrono")]
+#[test]
+fn test_rfc3339_parsing_matches_chrono() {
+    for input in [
+        "2000-02-29T12:00:00Z",
+        "2024-02-29T23:59:59.999999999Z",
+        "1999-12-31T23:30:00-01:00",
+        "2020-01-01T00:15:00+14:00",
+        "2019-12-31T19:26:16.123456789123Z",
+        "2019-12-31t19:26:16z",
+        "1970-01-01T00:00:00-00:00",
+        "1600-03-01T00:00:00Z",
+    ] {
+        let timestamp: Timestamp = input.parse().unwrap();
+        let expected = chrono::DateTime::parse_from_rfc3339(input).unwrap();
+        assert_eq!(timestamp, Timestamp::from(expected.to_utc()), "{}", input);
+    }
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_time_conversions() {
+    let timestamp: Timestamp = "2019-12-31T19:26:16.5Z".parse().unwrap();
+    let date_time = time::OffsetDateTime::try_from(timestamp).unwrap();
+    assert_eq!(date_time.unix_timestamp(), 1577820376);
+    assert_eq!(date_time.millisecond(), 500);
+    assert_eq!(Timestamp::from(date_time), timestamp);
+
+    let out_of_range = Timestamp::from_unix(i64::MAX, 0);
+    assert!(time::OffsetDateTime::try_from(out_of_range).is_err());
+}
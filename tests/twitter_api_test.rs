@@ -0,0 +1,153 @@
+mod common;
+
+use common::{MockResponse, MockServer};
+use tweety_rs::api::error::TweetyError;
+use tweety_rs::api::twitter_api::TwitterApi;
+use tweety_rs::types::id::{TweetId, UserId};
+use tweety_rs::TweetyClient;
+
+/// Bot logic written against the trait, exercised below with both implementations.
+async fn retweet_mentions(
+    api: &impl TwitterApi,
+    user_id: UserId,
+) -> Result<Vec<TweetId>, TweetyError> {
+    let mentions = api.get_users_mentions(user_id, None).await?;
+    let mut retweeted = Vec::new();
+    for mention in &mentions.data {
+        if api.retweet(user_id, mention.id).await?.data.retweeted {
+            retweeted.push(mention.id);
+        }
+    }
+    Ok(retweeted)
+}
+
+const MENTIONS: &str = r#"{
+    "data": [{"id": "1", "text": "@bot hi"}, {"id": "2", "text": "@bot hello"}],
+    "meta": {"result_count": 2}
+}"#;
+
+#[tokio::test]
+async fn test_client_implements_the_trait() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, MENTIONS));
+    server.enqueue(MockResponse::json(200, r#"{"data":{"retweeted":true}}"#));
+    server.enqueue(MockResponse::json(200, r#"{"data":{"retweeted":true}}"#));
+
+    let client = TweetyClient::builder()
+        .consumer_key("consumer_key")
+        .consumer_key_secret("consumer_secret")
+        .access_token("1234-access_token")
+        .access_token_secret("access_secret")
+        .api_base_url(server.url())
+        .build()
+        .unwrap();
+
+    let retweeted = retweet_mentions(&client, UserId::new(1234)).await.unwrap();
+    assert_eq!(retweeted, [TweetId::new(1), TweetId::new(2)]);
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].path, "/2/users/1234/mentions");
+    assert_eq!(requests[1].path, "/2/users/1234/retweets");
+    assert_eq!(requests[1].body, r#"{"tweet_id":"1"}"#);
+}
+
+#[cfg(feature = "testing")]
+mod fake {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+    use tweety_rs::api::error::TwitterError;
+    use tweety_rs::testing::{ApiMethod, Call, FakeTwitterApi};
+
+    #[tokio::test]
+    async fn test_fake_records_calls_and_returns_canned_responses() {
+        let api = FakeTwitterApi::new();
+        api.respond(
+            ApiMethod::GetUsersMentions,
+            serde_json::from_str::<serde_json::Value>(MENTIONS).unwrap(),
+        )
+        .respond(ApiMethod::Retweet, json!({"data": {"retweeted": true}}))
+        .respond(ApiMethod::Retweet, json!({"data": {"retweeted": false}}));
+
+        let retweeted = retweet_mentions(&api, UserId::new(1234)).await.unwrap();
+        assert_eq!(retweeted, [TweetId::new(1)]);
+        assert_eq!(
+            api.calls(),
+            [
+                Call {
+                    method: ApiMethod::GetUsersMentions,
+                    args: vec!["1234".to_string(), "None".to_string()],
+                },
+                Call {
+                    method: ApiMethod::Retweet,
+                    args: vec!["1234".to_string(), "1".to_string()],
+                },
+                Call {
+                    method: ApiMethod::Retweet,
+                    args: vec!["1234".to_string(), "2".to_string()],
+                },
+            ]
+        );
+        assert_eq!(api.pending_responses(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_fake_returns_queued_errors() {
+        let api = FakeTwitterApi::new();
+        api.respond(
+            ApiMethod::GetUsersMentions,
+            serde_json::from_str::<serde_json::Value>(MENTIONS).unwrap(),
+        );
+        api.fail(
+            ApiMethod::Retweet,
+            TwitterError {
+                title: Some("Too Many Requests".to_string()),
+                status: 429,
+                ..Default::default()
+            },
+        );
+
+        let err = retweet_mentions(&api, UserId::new(1234)).await.unwrap_err();
+        assert!(
+            matches!(err, TweetyError::ApiError(ref e) if e.status == 429),
+            "{:?}",
+            err
+        );
+        assert_eq!(api.calls_to(ApiMethod::Retweet).len(), 1);
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "no response queued for FakeTwitterApi::get_user_me")]
+    async fn test_fake_panics_without_a_queued_response() {
+        let api = FakeTwitterApi::new();
+        let _ = api.get_user_me(None).await;
+    }
+
+    #[tokio::test]
+    async fn test_fake_upload_returns_media_id() {
+        let api = FakeTwitterApi::new();
+        api.respond(
+            ApiMethod::UploadFile,
+            json!({"media_id": 710511363345354753u64}),
+        );
+
+        let media_id = api
+            .upload_file(std::path::Path::new("ferris.png"))
+            .await
+            .unwrap();
+        assert_eq!(media_id.get(), 710511363345354753);
+        assert_eq!(api.calls()[0].args, ["ferris.png"]);
+    }
+
+    #[tokio::test]
+    async fn test_fake_returns_an_error_for_bodies_that_are_not_json() {
+        let api = FakeTwitterApi::new();
+        // JSON object keys must be strings.
+        api.respond(ApiMethod::GetUserMe, HashMap::from([((1, 2), 3)]));
+
+        let err = api.get_user_me(None).await.unwrap_err();
+        assert!(matches!(err, TweetyError::JsonParseError(_)), "{:?}", err);
+        assert_eq!(api.calls_to(ApiMethod::GetUserMe).len(), 1);
+        assert_eq!(ApiMethod::GetUserMe.as_str(), "get_user_me");
+    }
+}
@@ -0,0 +1,338 @@
+mod common;
+
+use common::{mock_client, MockResponse, MockServer};
+use std::time::{Duration, UNIX_EPOCH};
+use tweety_rs::api::direct_messages::{DmEventsResponse, EventType};
+use tweety_rs::api::tweet::Ids;
+use tweety_rs::types::id::{DmConversationId, ListId, MediaId, TweetId, UserId};
+use tweety_rs::types::media::MediaType;
+use tweety_rs::types::poll::VotingStatus;
+use tweety_rs::types::response::{Includes, Meta};
+use tweety_rs::types::tweet::{ReferenceType, Tweet};
+use tweety_rs::types::user::{User, VerifiedType, WithheldScope};
+
+const TWEETS_WITH_INCLUDES: &str = r#"{
+  "data": [{
+    "id": "1212092628029698048",
+    "text": "We believe the best future version of our API will come from building it with YOU. https://t.co/bZNZTAv9GR",
+    "edit_history_tweet_ids": ["1212092628029698048"],
+    "author_id": "2244994945",
+    "created_at": "2019-12-31T19:26:16.000Z",
+    "lang": "en",
+    "possibly_sensitive": false,
+    "attachments": {"media_keys": ["3_1212092626188328960"], "poll_ids": ["1199786642468413448"]},
+    "geo": {"place_id": "01a9a39529b27f36"},
+    "referenced_tweets": [{"type": "replied_to", "id": "1212092627178287104"}],
+    "public_metrics": {"retweet_count": 7, "reply_count": 3, "like_count": 38, "quote_count": 1},
+    "entities": {
+      "urls": [{"start": 84, "end": 107, "url": "https://t.co/bZNZTAv9GR", "expanded_url": "https://x.com/x/status/1/photo/1", "display_url": "pic.x.com/bZNZTAv9GR", "media_key": "3_1212092626188328960"}],
+      "annotations": [{"start": 43, "end": 45, "probability": 0.5, "type": "Product", "normalized_text": "API"}],
+      "mentions": [{"start": 0, "end": 8, "username": "XDevelopers", "id": "2244994945"}]
+    },
+    "context_annotations": [{"domain": {"id": "46", "name": "Brand Category"}, "entity": {"id": "781974596148793345", "name": "Business & finance"}}]
+  }],
+  "includes": {
+    "users": [{"id": "2244994945", "name": "Developers", "username": "XDevelopers", "public_metrics": {"followers_count": 583423, "following_count": 2048, "tweet_count": 14052, "listed_count": 1672}}],
+    "media": [{"media_key": "3_1212092626188328960", "type": "photo", "url": "https://pbs.twimg.com/media/ENIiR.jpg", "width": 1200, "height": 675}],
+    "places": [{"id": "01a9a39529b27f36", "full_name": "Manhattan, NY", "country_code": "US", "place_type": "city", "geo": {"type": "Feature", "bbox": [-74.02, 40.70, -73.90, 40.88], "properties": {}}}],
+    "polls": [{"id": "1199786642468413448", "voting_status": "closed", "duration_minutes": 1440, "options": [{"position": 1, "label": "C Sharp", "votes": 795}, {"position": 2, "label": "C Hashtag", "votes": 156}]}]
+  },
+  "meta": {"newest_id": "1212092628029698048", "oldest_id": "1212092628029698048", "result_count": 1, "next_token": "b26v89c19zqg8o3fo7gesq314yb9l2l4ptqy"}
+}"#;
+
+fn assert_canonical_tweet(tweet: &Tweet) {
+    assert_eq!(tweet.author_id, Some(UserId::new(2244994945)));
+    let referenced = tweet.referenced_tweets.as_ref().unwrap();
+    assert_eq!(referenced[0].reference_type, ReferenceType::RepliedTo);
+    let entities = tweet.entities.as_ref().unwrap();
+    assert_eq!(entities.urls.as_ref().unwrap()[0].end, 107);
+    assert_eq!(tweet.public_metrics.as_ref().unwrap().like_count, Some(38));
+}
+
+fn assert_includes(includes: &Includes) {
+    assert_eq!(includes.users[0].username, "XDevelopers");
+    assert_eq!(includes.media[0].media_type, MediaType::Photo);
+    assert_eq!(includes.places[0].full_name, "Manhattan, NY");
+    assert_eq!(includes.polls[0].options[1].votes, 156);
+    assert!(includes.tweets.is_empty());
+}
+
+#[tokio::test]
+async fn test_search_and_mentions_share_the_tweet_model() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, TWEETS_WITH_INCLUDES));
+    server.enqueue(MockResponse::json(200, TWEETS_WITH_INCLUDES));
+
+    let client = mock_client(&server);
+    let search = client
+        .recent_search("from:XDevelopers", None)
+        .await
+        .unwrap();
+    let mentions = client
+        .get_users_mentions(UserId::new(2244994945), None)
+        .await
+        .unwrap();
+
+    // Both endpoints return the same types, so the same code handles them.
+    for (tweets, includes, meta) in [
+        (&search.data, &search.includes, &search.meta),
+        (&mentions.data, &mentions.includes, &mentions.meta),
+    ] {
+        assert_canonical_tweet(&tweets[0]);
+        assert_includes(includes.as_ref().unwrap());
+        assert_eq!(
+            meta.next_token.as_deref(),
+            Some("b26v89c19zqg8o3fo7gesq314yb9l2l4ptqy")
+        );
+    }
+    assert_eq!(search.data, mentions.data);
+}
+
+#[test]
+fn test_minimal_objects_only_need_default_fields() {
+    let tweet: Tweet = serde_json::from_str(r#"{"id":"1","text":"hello"}"#).unwrap();
+    assert_eq!(tweet.text, "hello");
+    assert!(tweet.created_at.is_none());
+
+    let empty_page: tweety_rs::api::followers::UserFollowersResponse =
+        serde_json::from_str(r#"{"meta":{"result_count":0}}"#).unwrap();
+    assert!(empty_page.data.is_empty());
+    assert!(empty_page.meta.next_token.is_none());
+}
+
+#[test]
+fn test_unknown_enum_values_do_not_fail_parsing() {
+    let tweet: Tweet = serde_json::from_str(
+        r#"{"id":"1","text":"hi","referenced_tweets":[{"type":"edited_from","id":"2"}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        tweet.referenced_tweets.unwrap()[0].reference_type,
+        ReferenceType::Unknown
+    );
+
+    let user: User = serde_json::from_str(
+        r#"{"id":"1","name":"n","username":"u","verified_type":"platinum","withheld":{"country_codes":["DE"],"scope":"account"}}"#,
+    )
+    .unwrap();
+    assert_eq!(user.verified_type, Some(VerifiedType::Unknown));
+    assert_eq!(user.withheld.unwrap().scope, Some(WithheldScope::Unknown));
+
+    let media_type: MediaType = serde_json::from_str(r#""audio""#).unwrap();
+    assert_eq!(media_type, MediaType::Unknown);
+    let voting_status: VotingStatus = serde_json::from_str(r#""paused""#).unwrap();
+    assert_eq!(voting_status, VotingStatus::Unknown);
+}
+
+#[tokio::test]
+async fn test_hydrated_search_resolves_expansions() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, TWEETS_WITH_INCLUDES));
+
+    let client = mock_client(&server);
+    let page = client
+        .recent_search("from:XDevelopers", None)
+        .await
+        .unwrap()
+        .into_inner()
+        .hydrate();
+
+    let tweet = page.tweets().next().unwrap();
+    assert_eq!(tweet.author().unwrap().username, "XDevelopers");
+    assert_eq!(tweet.media()[0].width, Some(1200));
+    assert_eq!(tweet.poll().unwrap().options[0].label, "C Sharp");
+    assert_eq!(tweet.place().unwrap().full_name, "Manhattan, NY");
+    assert_eq!(tweet.mentioned_users()[0].id, UserId::new(2244994945));
+    // The replied-to Tweet was not expanded.
+    assert!(tweet.referenced_tweets().is_empty());
+    assert!(tweet.replied_to().is_none());
+}
+
+#[test]
+fn test_hydrated_referenced_tweets_are_expanded_too() {
+    let response: tweety_rs::api::mentions::MentionsResponse = serde_json::from_str(
+        r#"{
+          "data": [{"id": "3", "text": "RT", "author_id": "10", "referenced_tweets": [{"type": "quoted", "id": "2"}]}],
+          "includes": {
+            "tweets": [{"id": "2", "text": "original", "author_id": "20"}],
+            "users": [{"id": "10", "name": "Quoting", "username": "quoting"}, {"id": "20", "name": "Original", "username": "original"}]
+          },
+          "meta": {"result_count": 1}
+        }"#,
+    )
+    .unwrap();
+
+    let page = response.hydrate();
+    let tweet = page.tweets().next().unwrap();
+    let (kind, quoted) = tweet.referenced_tweets()[0];
+    assert_eq!(kind, ReferenceType::Quoted);
+    assert_eq!(quoted.text, "original");
+    assert_eq!(quoted.author().unwrap().username, "original");
+    assert_eq!(tweet.quoted().unwrap().id, TweetId::new(2));
+    assert!(tweet.media().is_empty() && tweet.poll().is_none());
+    assert_eq!(page.includes.user(UserId::new(10)).unwrap().name, "Quoting");
+}
+
+#[test]
+fn test_ids_parse_from_strings_and_numbers() {
+    #[derive(serde::Deserialize)]
+    struct Ids {
+        tweet: TweetId,
+        media: MediaId,
+        list: ListId,
+        conversation: DmConversationId,
+    }
+
+    let ids: Ids = serde_json::from_str(
+        r#"{"tweet": "1212092628029698048", "media": 1212092626188328960, "list": "1146654567674912769", "conversation": "1470797706-2244994945"}"#,
+    )
+    .unwrap();
+    assert_eq!(ids.list.to_string(), "1146654567674912769");
+    assert_eq!(ids.tweet, TweetId::new(1212092628029698048));
+    assert_eq!(ids.media.get(), 1212092626188328960);
+    assert_eq!(
+        serde_json::to_string(&ids.tweet).unwrap(),
+        r#""1212092628029698048""#
+    );
+
+    let (low, high) = ids.conversation.participants().unwrap();
+    assert_eq!(
+        DmConversationId::one_to_one(high, low),
+        ids.conversation,
+        "participants are ordered"
+    );
+    assert!(ids.conversation.created_at().is_none());
+    assert!("12a".parse::<TweetId>().is_err());
+    assert!(serde_json::from_str::<UserId>("-1").is_err());
+}
+
+#[test]
+fn test_ids_compare_chronologically() {
+    let older: TweetId = "1212092627178287104".parse().unwrap();
+    let newer: TweetId = "1212092628029698048".parse().unwrap();
+    assert!(older < newer);
+    assert!(older.created_at() <= newer.created_at());
+
+    let at = newer.created_at();
+    assert!(TweetId::from_time(at) <= newer);
+    assert!(TweetId::from_time(at + Duration::from_millis(1)) > newer);
+    assert_eq!(
+        at.duration_since(UNIX_EPOCH).unwrap().as_millis(),
+        1577820376771
+    );
+
+    let meta: Meta =
+        serde_json::from_str(r#"{"result_count": 2, "newest_id": "1212092628029698048"}"#).unwrap();
+    assert_eq!(meta.newest_id, Some(newer));
+}
+
+#[tokio::test]
+async fn test_lookups_are_typed_and_keep_the_raw_json() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{
+          "data": [{"id": "20", "text": "just setting up my twttr", "author_id": "12"}],
+          "errors": [{"value": "1", "detail": "Could not find tweet with ids: [1].", "resource_type": "tweet", "parameter": "ids", "resource_id": "1", "type": "https://api.twitter.com/2/problems/resource-not-found"}],
+          "includes": {"users": [{"id": "12", "name": "jack", "username": "jack", "withheld_reason": "new"}]}
+        }"#,
+    ));
+    server.enqueue(MockResponse::json(200, r#"{"data":{"liked":true}}"#));
+
+    let client = mock_client(&server);
+    let tweets = client
+        .get_tweet(Ids::Multiple(vec![TweetId::new(20), TweetId::new(1)]))
+        .await
+        .unwrap();
+    assert_eq!(tweets.data[0].text, "just setting up my twttr");
+    assert_eq!(tweets.errors[0].resource_id.as_deref(), Some("1"));
+    // Fields the model does not cover are still in the raw JSON.
+    assert_eq!(
+        tweets.raw()["includes"]["users"][0]["withheld_reason"],
+        "new"
+    );
+
+    let hydrated = tweets.into_inner().hydrate();
+    let tweet = hydrated.tweets().next().unwrap();
+    assert_eq!(tweet.author().unwrap().username, "jack");
+
+    let like = client
+        .like_a_post(UserId::new(12), TweetId::new(20))
+        .await
+        .unwrap();
+    assert!(like.data.liked);
+}
+
+#[test]
+fn test_dm_events_are_typed() {
+    let response: DmEventsResponse = serde_json::from_str(
+        r#"{
+          "data": [
+            {"id": "1582838223", "event_type": "MessageCreate", "text": "Hello", "sender_id": "906948460078698496", "dm_conversation_id": "906948460078698496-1470797706", "created_at": "2022-10-19T20:58:00.000Z", "attachments": {"media_keys": ["3_1582838215"]}},
+            {"id": "1581048670", "event_type": "ParticipantsJoin", "participant_ids": ["944480690"], "dm_conversation_id": "1578398450982912000"}
+          ],
+          "meta": {"result_count": 2}
+        }"#,
+    )
+    .unwrap();
+
+    let message = &response.data[0];
+    assert_eq!(message.event_type, EventType::MessageCreate);
+    assert_eq!(message.text.as_deref(), Some("Hello"));
+    let conversation = message.dm_conversation_id.as_ref().unwrap();
+    assert_eq!(
+        conversation.participants().unwrap().1,
+        UserId::new(1470797706)
+    );
+    assert_eq!(
+        message.created_at.unwrap().to_string(),
+        "2022-10-19T20:58:00Z"
+    );
+
+    assert_eq!(message.sender_id, Some(UserId::new(906948460078698496)));
+
+    let join = &response.data[1];
+    assert_eq!(join.event_type, EventType::ParticipantsJoin);
+    assert_eq!(join.participant_ids, Some(vec![UserId::new(944480690)]));
+    assert!(join.dm_conversation_id.as_ref().unwrap().is_group());
+}
+
+#[tokio::test]
+async fn test_endpoints_take_typed_ids() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, r#"{"data":{"liked":false}}"#));
+    server.enqueue(MockResponse::json(200, r#"{"meta":{"result_count":0}}"#));
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"data":{"following":true,"pending_follow":false}}"#,
+    ));
+    server.enqueue(MockResponse::json(200, r#"{"data":[]}"#));
+
+    let client = mock_client(&server);
+    let unliked = client
+        .unlike_tweet(UserId::new(12), TweetId::new(20))
+        .await
+        .unwrap();
+    assert!(!unliked.data.liked);
+    let conversation = DmConversationId::one_to_one(UserId::new(2), UserId::new(1));
+    client
+        .get_dm_events_in_conversation(&conversation, Default::default())
+        .await
+        .unwrap();
+    client
+        .follow_user(UserId::new(12), UserId::new(2244994945))
+        .await
+        .unwrap();
+    client
+        .get_users(&[UserId::new(12), UserId::new(2244994945)], None)
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "DELETE");
+    assert_eq!(requests[0].path, "/2/users/12/likes/20");
+    assert_eq!(requests[1].path, "/2/dm_conversations/1-2/dm_events");
+    assert_eq!(requests[2].path, "/2/users/12/following");
+    assert_eq!(requests[2].body, r#"{"target_user_id":"2244994945"}"#);
+    assert_eq!(requests[3].path, "/2/users?ids=12%2C2244994945");
+}
@@ -0,0 +1,664 @@
+mod common;
+
+use bytes::Bytes;
+use common::{mock_client, MockResponse, MockServer};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tweety_rs::api::error::{MediaError, TweetyError};
+use tweety_rs::api::media_metadata::{
+    AltText, LanguageCode, MediaMetadata, SensitiveMediaWarning, Subtitles,
+};
+use tweety_rs::api::uploads::{
+    MediaCategory, ProcessingState, UploadParams, UploadProgress, CHUNK_SIZE,
+};
+use tweety_rs::types::id::MediaId;
+
+fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("tweety-{}-{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+const MEDIA_ID: &str = "710511363345354753";
+
+#[tokio::test]
+async fn test_chunked_upload_polls_status_until_processed() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        202,
+        r#"{"media_id":710511363345354753,"media_id_string":"710511363345354753","expires_after_secs":86400}"#,
+    ));
+    server.enqueue(MockResponse::json(204, ""));
+    server.enqueue(MockResponse::json(204, ""));
+    server.enqueue(MockResponse::json(
+        201,
+        r#"{"media_id":710511363345354753,"size":4194404,"processing_info":{"state":"pending","check_after_secs":0}}"#,
+    ));
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"media_id":710511363345354753,"processing_info":{"state":"in_progress","check_after_secs":0,"progress_percent":40}}"#,
+    ));
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"media_id":710511363345354753,"processing_info":{"state":"succeeded","progress_percent":100}}"#,
+    ));
+
+    let path = temp_file("clip.mp4", &vec![b'v'; CHUNK_SIZE + 100]);
+    let media = mock_client(&server)
+        .upload_media(&path, UploadParams::default())
+        .await
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(media.media_id.to_string(), MEDIA_ID);
+    assert_eq!(
+        media.processing_info.unwrap().state,
+        ProcessingState::Succeeded
+    );
+
+    let requests = server.requests();
+    let commands: Vec<_> = requests
+        .iter()
+        .map(|request| (request.method.as_str(), request.path.as_str()))
+        .collect();
+    let append = |segment: u32| {
+        format!(
+            "/1.1/media/upload.json?command=APPEND&media_id={}&segment_index={}",
+            MEDIA_ID, segment
+        )
+    };
+    let status = format!(
+        "/1.1/media/upload.json?command=STATUS&media_id={}",
+        MEDIA_ID
+    );
+    assert_eq!(
+        commands,
+        [
+            (
+                "POST",
+                "/1.1/media/upload.json?command=INIT&total_bytes=4194404\
+                 &media_type=video%2Fmp4&media_category=tweet_video"
+            ),
+            ("POST", append(0).as_str()),
+            ("POST", append(1).as_str()),
+            (
+                "POST",
+                format!(
+                    "/1.1/media/upload.json?command=FINALIZE&media_id={}",
+                    MEDIA_ID
+                )
+                .as_str()
+            ),
+            ("GET", status.as_str()),
+            ("GET", status.as_str()),
+        ]
+    );
+
+    // Every segment is a multipart `media` part, the last one with the remaining bytes.
+    assert!(requests[1]
+        .header("content-type")
+        .unwrap()
+        .starts_with("multipart/form-data"));
+    assert!(requests[1].body.contains(r#"name="media""#));
+    assert!(requests[1].body.len() > CHUNK_SIZE);
+    assert!(requests[2].body.len() < 1000);
+    assert!(requests.iter().all(|request| request
+        .header("authorization")
+        .unwrap()
+        .starts_with("OAuth ")));
+}
+
+#[tokio::test]
+async fn test_chunked_upload_reports_processing_failures() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        202,
+        r#"{"media_id":710511363345354753}"#,
+    ));
+    server.enqueue(MockResponse::json(204, ""));
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"media_id":710511363345354753,"processing_info":{"state":"failed","progress_percent":0,
+            "error":{"code":1,"name":"InvalidMedia","message":"Unsupported video format"}}}"#,
+    ));
+
+    let path = temp_file("clip.bin", b"not really a gif");
+    let params = UploadParams {
+        media_type: Some("image/gif".to_string()),
+        media_category: Some(MediaCategory::DmGif),
+        ..Default::default()
+    };
+    let err = mock_client(&server)
+        .upload_media(&path, params)
+        .await
+        .unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(
+        matches!(err, TweetyError::MediaProcessingFailed(ref message) if message.contains("Unsupported video format")),
+        "{:?}",
+        err
+    );
+    assert!(server.requests()[0]
+        .path
+        .contains("media_type=image%2Fgif&media_category=dm_gif"));
+}
+
+#[tokio::test]
+async fn test_chunked_upload_gives_up_waiting_for_processing() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        202,
+        r#"{"media_id":710511363345354753}"#,
+    ));
+    server.enqueue(MockResponse::json(204, ""));
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"media_id":710511363345354753,"processing_info":{"state":"in_progress","check_after_secs":0}}"#,
+    ));
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"media_id":710511363345354753,"processing_info":{"state":"in_progress","check_after_secs":60}}"#,
+    ));
+
+    let path = temp_file("slow.bin", b"not really a video");
+    let params = UploadParams {
+        media_type: Some("video/mp4".to_string()),
+        max_processing_wait: Some(Duration::from_secs(30)),
+        ..Default::default()
+    };
+    let err = mock_client(&server)
+        .upload_media(&path, params)
+        .await
+        .unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(
+        matches!(err, TweetyError::MediaProcessingFailed(ref message) if message.contains("not processed after 30s")),
+        "{:?}",
+        err
+    );
+    // INIT, APPEND, FINALIZE and a single STATUS: the next one would be too late.
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn test_chunked_upload_needs_a_media_type() {
+    let server = MockServer::start().await;
+    let path = temp_file("clip.unknown", b"data");
+
+    let err = mock_client(&server)
+        .upload_media(&path, UploadParams::default())
+        .await
+        .unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(matches!(err, TweetyError::InvalidParameter(_)), "{:?}", err);
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn test_media_category_defaults_from_media_type() {
+    assert_eq!(
+        MediaCategory::for_media_type("image/gif"),
+        MediaCategory::TweetGif
+    );
+    assert_eq!(
+        MediaCategory::for_media_type("video/mp4"),
+        MediaCategory::TweetVideo
+    );
+    assert_eq!(
+        MediaCategory::for_media_type("image/png"),
+        MediaCategory::TweetImage
+    );
+    assert_eq!(
+        MediaCategory::for_media_type("application/x-subrip"),
+        MediaCategory::Subtitles
+    );
+}
+
+fn png_params() -> UploadParams {
+    UploadParams {
+        media_type: Some("image/png".to_string()),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_upload_from_bytes_reports_progress() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        202,
+        r#"{"media_id":710511363345354753}"#,
+    ));
+    server.enqueue(MockResponse::json(204, ""));
+    server.enqueue(MockResponse::json(204, ""));
+    server.enqueue(MockResponse::json(
+        201,
+        r#"{"media_id":710511363345354753,"size":4194305}"#,
+    ));
+
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let total_bytes = CHUNK_SIZE as u64 + 1;
+    let media = mock_client(&server)
+        .upload_media_bytes(
+            vec![0u8; CHUNK_SIZE + 1],
+            png_params().progress_channel(sender),
+        )
+        .await
+        .unwrap();
+    assert_eq!(media.size, Some(total_bytes));
+
+    let mut progress = Vec::new();
+    while let Some(update) = receiver.recv().await {
+        progress.push(update);
+    }
+    assert_eq!(
+        progress,
+        [
+            UploadProgress {
+                bytes_sent: CHUNK_SIZE as u64,
+                total_bytes,
+            },
+            UploadProgress {
+                bytes_sent: total_bytes,
+                total_bytes,
+            },
+        ]
+    );
+    assert_eq!(progress[1].fraction(), 1.0);
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 4);
+    assert!(requests[0]
+        .path
+        .ends_with("media_type=image%2Fpng&media_category=tweet_image"));
+}
+
+#[tokio::test]
+async fn test_upload_from_stream_regroups_small_items() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        202,
+        r#"{"media_id":710511363345354753}"#,
+    ));
+    server.enqueue(MockResponse::json(204, ""));
+    server.enqueue(MockResponse::json(
+        201,
+        r#"{"media_id":710511363345354753}"#,
+    ));
+
+    let items = ["first-", "second-", "third"]
+        .map(|item| Ok::<_, std::io::Error>(Bytes::from_static(item.as_bytes())));
+    let calls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+    let counter = calls.clone();
+    let params = png_params().on_progress(move |_| {
+        counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    });
+    mock_client(&server)
+        .upload_media_stream(futures::stream::iter(items), 18, params)
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[0].path.contains("total_bytes=18"));
+    assert!(requests[1].path.ends_with("segment_index=0"));
+    assert!(requests[1].body.contains("first-second-third"));
+    assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_upload_from_reader_checks_the_length() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        202,
+        r#"{"media_id":710511363345354753}"#,
+    ));
+    server.enqueue(MockResponse::json(204, ""));
+
+    let err = mock_client(&server)
+        .upload_media_reader(&b"only 17 bytes ..."[..], 100, png_params())
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, TweetyError::InvalidParameter(ref message) if message.contains("100 bytes, got 17")),
+        "{:?}",
+        err
+    );
+    // Not finalized.
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn test_upload_from_bytes_needs_a_media_type() {
+    let server = MockServer::start().await;
+
+    let err = mock_client(&server)
+        .upload_media_bytes(Bytes::from_static(b"\x89PNG"), UploadParams::default())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, TweetyError::InvalidParameter(_)), "{:?}", err);
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_upload_file_reports_missing_files() {
+    let server = MockServer::start().await;
+
+    let err = mock_client(&server)
+        .upload_file(Path::new("/nonexistent/ferris.png"))
+        .await
+        .unwrap_err();
+
+    assert!(
+        matches!(err, TweetyError::FileIOError(ref message) if message.contains("ferris.png")),
+        "{:?}",
+        err
+    );
+    assert!(server.requests().is_empty());
+}
+
+fn media_id(id: &str) -> MediaId {
+    id.parse().unwrap()
+}
+
+#[tokio::test]
+async fn test_create_media_metadata() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, ""));
+
+    let metadata = MediaMetadata {
+        alt_text: Some(AltText::new("Ferris the crab waving a claw").unwrap()),
+        sensitive_media_warning: vec![SensitiveMediaWarning::GraphicViolence],
+    };
+    mock_client(&server)
+        .create_media_metadata(media_id(MEDIA_ID), &metadata)
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/1.1/media/metadata/create.json");
+    assert_eq!(
+        serde_json::from_str::<Value>(&requests[0].body).unwrap(),
+        json!({
+            "media_id": MEDIA_ID,
+            "alt_text": {"text": "Ferris the crab waving a claw"},
+            "sensitive_media_warning": ["graphic_violence"],
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_media_metadata_needs_a_value() {
+    let server = MockServer::start().await;
+
+    let err = mock_client(&server)
+        .create_media_metadata(media_id(MEDIA_ID), &MediaMetadata::default())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, TweetyError::InvalidParameter(_)), "{:?}", err);
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn test_alt_text_limits() {
+    assert!(AltText::new("é".repeat(AltText::MAX_CHARS)).is_ok());
+    for text in ["é".repeat(AltText::MAX_CHARS + 1), "  ".to_string()] {
+        let err = AltText::new(text).unwrap_err();
+        assert!(matches!(err, TweetyError::InvalidParameter(_)), "{:?}", err);
+    }
+}
+
+#[test]
+fn test_language_codes() {
+    assert_eq!(LanguageCode::new("en").unwrap().as_str(), "en");
+    for code in ["", "e", "english", "e1", "en-US"] {
+        assert!(LanguageCode::new(code).is_err(), "{}", code);
+    }
+}
+
+#[tokio::test]
+async fn test_create_and_delete_media_subtitles() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(200, ""));
+    server.enqueue(MockResponse::json(200, ""));
+
+    let client = mock_client(&server);
+    let english = LanguageCode::new("en").unwrap();
+    let subtitles = Subtitles {
+        media_id: media_id("1"),
+        language_code: english.clone(),
+        display_name: "English".to_string(),
+    };
+    client
+        .create_media_subtitles(media_id(MEDIA_ID), &[subtitles])
+        .await
+        .unwrap();
+    client
+        .delete_media_subtitles(media_id(MEDIA_ID), &[english])
+        .await
+        .unwrap();
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/1.1/media/subtitles/create.json");
+    assert_eq!(
+        serde_json::from_str::<Value>(&requests[0].body).unwrap(),
+        json!({
+            "media_id": MEDIA_ID,
+            "media_category": "TweetVideo",
+            "subtitle_info": {"subtitles": [
+                {"media_id": "1", "language_code": "en", "display_name": "English"}
+            ]},
+        })
+    );
+    assert_eq!(requests[1].path, "/1.1/media/subtitles/delete.json");
+    assert_eq!(
+        serde_json::from_str::<Value>(&requests[1].body).unwrap(),
+        json!({
+            "media_id": MEDIA_ID,
+            "media_category": "TweetVideo",
+            "subtitle_info": {"subtitles": [{"language_code": "en"}]},
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_subtitles_need_a_display_name() {
+    let server = MockServer::start().await;
+    let subtitles = Subtitles {
+        media_id: media_id("1"),
+        language_code: LanguageCode::new("en").unwrap(),
+        display_name: String::new(),
+    };
+
+    let err = mock_client(&server)
+        .create_media_subtitles(media_id(MEDIA_ID), &[subtitles])
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, TweetyError::InvalidParameter(_)), "{:?}", err);
+    assert!(server.requests().is_empty());
+}
+
+const FERRIS: &str = "tests/assets/ferrisTheRock.png";
+
+#[tokio::test]
+async fn test_upload_file() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        200,
+        r#"{"media_id":710511363345354753,"media_id_string":"710511363345354753","size":638606}"#,
+    ));
+
+    let media_id = mock_client(&server)
+        .upload_file(Path::new(FERRIS))
+        .await
+        .unwrap();
+
+    assert_eq!(media_id.to_string(), MEDIA_ID);
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/1.1/media/upload.json");
+    let body = requests[0].body.to_ascii_lowercase();
+    assert!(body.contains("filename=\"ferristherock.png\""));
+    assert!(body.contains("content-type: image/png"));
+}
+
+#[tokio::test]
+async fn test_upload_file_returns_api_errors() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        400,
+        r#"{"errors":[{"code":324,"message":"Image file size must be <= 5242880 bytes"}]}"#,
+    ));
+
+    let err = mock_client(&server)
+        .upload_file(Path::new(FERRIS))
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.api_error().map(|err| err.status),
+        Some(400),
+        "{:?}",
+        err
+    );
+}
+
+#[tokio::test]
+async fn test_upload_file_rejects_videos_before_sending() {
+    let server = MockServer::start().await;
+    let path = temp_file("movie.mp4", b"\0\0\0\x20ftypisom\0\0\x02\0");
+
+    let err = mock_client(&server).upload_file(&path).await.unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(
+        matches!(
+            err,
+            TweetyError::InvalidMedia(MediaError::ChunkedUploadRequired {
+                ref media_type,
+                category: MediaCategory::TweetVideo,
+            }) if media_type == "video/mp4"
+        ),
+        "{:?}",
+        err
+    );
+    assert!(err.to_string().contains("upload_media"), "{}", err);
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_upload_file_sends_large_gifs_to_the_chunked_upload() {
+    let server = MockServer::start().await;
+    let mut gif = b"GIF89a\x40\x01\xF0\x00".to_vec();
+    gif.resize(6 * 1024 * 1024, 0);
+    let path = temp_file("large.gif", &gif);
+
+    let err = mock_client(&server).upload_file(&path).await.unwrap_err();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(
+        matches!(
+            err,
+            TweetyError::InvalidMedia(MediaError::ChunkedUploadRequired {
+                ref media_type,
+                category: MediaCategory::TweetGif,
+            }) if media_type == "image/gif"
+        ),
+        "{:?}",
+        err
+    );
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn test_chunked_upload_detects_the_media_type() {
+    let server = MockServer::start().await;
+    server.enqueue(MockResponse::json(
+        202,
+        r#"{"media_id":710511363345354753}"#,
+    ));
+    server.enqueue(MockResponse::json(204, ""));
+    server.enqueue(MockResponse::json(
+        201,
+        r#"{"media_id":710511363345354753}"#,
+    ));
+
+    let png = std::fs::read(FERRIS).unwrap();
+    mock_client(&server)
+        .upload_media_bytes(png, UploadParams::default())
+        .await
+        .unwrap();
+
+    assert!(server.requests()[0]
+        .path
+        .ends_with("total_bytes=638606&media_type=image%2Fpng&media_category=tweet_image"));
+}
+
+#[tokio::test]
+async fn test_chunked_upload_validates_before_sending() {
+    let server = MockServer::start().await;
+    let png = Bytes::from(std::fs::read(FERRIS).unwrap());
+    let client = mock_client(&server);
+
+    let err = client
+        .upload_media_bytes(
+            png.clone(),
+            UploadParams {
+                media_type: Some("image/gif".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            TweetyError::InvalidMedia(MediaError::TypeMismatch {
+                detected: "image/png",
+                ..
+            })
+        ),
+        "{:?}",
+        err
+    );
+
+    let err = client
+        .upload_media_stream(
+            futures::stream::iter([Ok::<_, std::io::Error>(png.clone())]),
+            6 * 1024 * 1024,
+            UploadParams::default(),
+        )
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, TweetyError::InvalidMedia(MediaError::TooLarge { .. })),
+        "{:?}",
+        err
+    );
+    assert!(server.requests().is_empty());
+
+    // The limits can be skipped.
+    server.enqueue(MockResponse::json(
+        202,
+        r#"{"media_id":710511363345354753}"#,
+    ));
+    server.enqueue(MockResponse::json(204, ""));
+    server.enqueue(MockResponse::json(
+        201,
+        r#"{"media_id":710511363345354753}"#,
+    ));
+    let params = UploadParams {
+        media_category: Some(MediaCategory::TweetGif),
+        skip_validation: true,
+        ..Default::default()
+    };
+    client.upload_media_bytes(png, params).await.unwrap();
+    assert_eq!(server.requests().len(), 3);
+}